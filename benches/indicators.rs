@@ -5,16 +5,16 @@ extern crate ta;
 use bencher::Bencher;
 use rand::Rng;
 use ta::indicators::{
-    BollingerBands, EfficiencyRatio, ExponentialMovingAverage, FastStochastic, Maximum, Minimum,
-    MoneyFlowIndex, MovingAverageConvergenceDivergence, OnBalanceVolume, RateOfChange,
+    BollingerBands, EfficiencyRatio, ExponentialMovingAverage, FastStochastic, Ichimoku, Maximum,
+    Minimum, MoneyFlowIndex, MovingAverageConvergenceDivergence, OnBalanceVolume, RateOfChange,
     RelativeStrengthIndex, SimpleMovingAverage, SlowStochastic, StandardDeviation, TrueRange,
 };
 use ta::DataItem;
-use ta::Next;
+use ta::{High, Low, Next};
 
 const ITEMS_COUNT: usize = 5_000;
 
-fn rand_data_item() -> DataItem {
+fn rand_data_item() -> DataItem<f64> {
     let mut rng = rand::thread_rng();
 
     let low = rng.gen_range(0.0, 500.0);
@@ -36,8 +36,9 @@ fn rand_data_item() -> DataItem {
 macro_rules! bench_indicators {
     ($($indicator:ident), *) => {
         $(
+            #[allow(non_snake_case)]
             fn $indicator(bench: &mut Bencher) {
-                let items: Vec<DataItem> = (0..ITEMS_COUNT).map( |_| rand_data_item() ).collect();
+                let items: Vec<DataItem<f64>> = (0..ITEMS_COUNT).map( |_| rand_data_item() ).collect();
                 let mut indicator = $indicator::default();
 
                 bench.iter(|| {
@@ -49,7 +50,100 @@ macro_rules! bench_indicators {
         )*
 
         benchmark_group!(benches, $($indicator,)*);
-        benchmark_main!(benches);
+    }
+}
+
+// Window long enough for a rescan of the window to dominate
+const LONG_WINDOW: u32 = 1_000;
+
+// Steadily falling highs and lows: each extremum leaves the window as a new one comes in, the
+// worst case for indicators rescanning their window.
+fn downtrend_data_items() -> Vec<DataItem<f64>> {
+    (0..ITEMS_COUNT)
+        .map(|i| {
+            let high = (2 * ITEMS_COUNT - i) as f64;
+            DataItem::builder()
+                .open(high - 0.5)
+                .high(high)
+                .low(high - 1.0)
+                .close(high - 0.5)
+                .volume(1_000.0)
+                .build()
+                .unwrap()
+        })
+        .collect()
+}
+
+// Baseline for the long windows: the extremum of the window as `Maximum` and `Minimum` found it
+// before tracking it with a monotonic deque, rescanning the whole window unless the input
+// beats it.
+struct Rescan {
+    window: Vec<f64>,
+    cur_index: usize,
+    best_index: usize,
+    price: fn(&DataItem<f64>) -> f64,
+    beats: fn(f64, f64) -> bool,
+}
+
+impl Rescan {
+    fn maximum(n: u32) -> Self {
+        Self::new(n, f64::NEG_INFINITY, |item| item.high(), |a, b| a > b)
+    }
+
+    fn minimum(n: u32) -> Self {
+        Self::new(n, f64::INFINITY, |item| item.low(), |a, b| a < b)
+    }
+
+    fn new(
+        n: u32,
+        empty: f64,
+        price: fn(&DataItem<f64>) -> f64,
+        beats: fn(f64, f64) -> bool,
+    ) -> Self {
+        Self {
+            window: vec![empty; n as usize],
+            cur_index: 0,
+            best_index: 0,
+            price,
+            beats,
+        }
+    }
+
+    fn next(&mut self, item: &DataItem<f64>) -> f64 {
+        let input = (self.price)(item);
+        self.cur_index = (self.cur_index + 1) % self.window.len();
+        self.window[self.cur_index] = input;
+
+        if (self.beats)(input, self.window[self.best_index]) {
+            self.best_index = self.cur_index;
+        } else {
+            self.best_index = 0;
+            for i in 1..self.window.len() {
+                if (self.beats)(self.window[i], self.window[self.best_index]) {
+                    self.best_index = i;
+                }
+            }
+        }
+        self.window[self.best_index]
+    }
+}
+
+macro_rules! bench_long_windows {
+    ($($name:ident => $indicator:expr), *) => {
+        $(
+            fn $name(bench: &mut Bencher) {
+                let items = downtrend_data_items();
+                let mut indicator = $indicator;
+
+                bench.iter(|| {
+                    for item in items.iter() {
+                        indicator.next(item);
+                    }
+                })
+            }
+        )*
+
+        benchmark_group!(long_windows, $($name,)*);
     }
 }
 
@@ -68,5 +162,18 @@ bench_indicators!(
     SlowStochastic,
     TrueRange,
     MoneyFlowIndex,
-    OnBalanceVolume
+    OnBalanceVolume,
+    Ichimoku
+);
+
+bench_long_windows!(
+    rescan_maximum_long_window => Rescan::maximum(LONG_WINDOW),
+    rescan_minimum_long_window => Rescan::minimum(LONG_WINDOW),
+    maximum_long_window => Maximum::<f64>::new(LONG_WINDOW).unwrap(),
+    minimum_long_window => Minimum::<f64>::new(LONG_WINDOW).unwrap(),
+    fast_stochastic_long_window => FastStochastic::<f64>::new(LONG_WINDOW).unwrap(),
+    ichimoku_long_window =>
+        Ichimoku::<f64>::new(LONG_WINDOW / 4, LONG_WINDOW / 2, LONG_WINDOW).unwrap()
 );
+
+benchmark_main!(benches, long_windows);
@@ -0,0 +1,186 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, Mul};
+
+use num_traits::{FromPrimitive, Zero};
+
+use super::PartialBar;
+use crate::errors::*;
+use crate::{DataItem, Next, Raw, Reset};
+
+/// Bars of a fixed traded notional, i.e. the sum of price times size of the trades.
+///
+/// A bar is complete once its notional reaches the threshold. Trades are not split, so the
+/// notional of a bar can exceed the threshold. The timestamp of a bar is the timestamp of
+/// its first trade.
+///
+/// # Parameters
+///
+/// * _threshold_ - notional per bar, greater than 0
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::DollarBars;
+/// use ta::{Close, Next, Volume};
+///
+/// let mut bars = DollarBars::new(1_000.0).unwrap();
+/// assert!(bars.next((1_000, 10.0, 60.0)).is_none());
+///
+/// let bar = bars.next((1_500, 11.0, 40.0)).unwrap();
+/// assert_eq!((bar.close(), bar.volume()), (11.0, 100.0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DollarBars<T> {
+    threshold: T,
+    notional: T,
+    bar: Option<PartialBar<T>>,
+}
+
+impl<T> DollarBars<T>
+where
+    T: Copy + PartialOrd + Zero + fmt::Display,
+{
+    pub fn new(threshold: T) -> Result<Self> {
+        // NaN is not greater than 0 either
+        if threshold.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+            return Err(Error::invalid_parameter(
+                "DOLLAR_BARS",
+                "threshold",
+                threshold,
+                "greater than 0",
+            ));
+        }
+        Ok(Self {
+            threshold,
+            notional: T::zero(),
+            bar: None,
+        })
+    }
+
+    pub fn threshold(&self) -> T {
+        self.threshold
+    }
+}
+
+impl<T> DollarBars<T>
+where
+    T: Copy + PartialOrd + Zero + Add<Output = T>,
+{
+    /// Returns the bar being formed, if any, and starts over.
+    pub fn flush(&mut self) -> Option<DataItem<T>> {
+        self.notional = T::zero();
+        self.bar.take().map(|bar| bar.build())
+    }
+}
+
+impl<T> Next<(i64, T, T), Raw> for DollarBars<T>
+where
+    T: Copy + PartialOrd + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Option<DataItem<T>>;
+
+    fn next(&mut self, (timestamp, price, size): (i64, T, T)) -> Self::Output {
+        match self.bar {
+            Some(ref mut bar) => bar.add(price, size),
+            None => self.bar = Some(PartialBar::new(timestamp, price, size)),
+        }
+
+        self.notional = self.notional + price * size;
+        if self.notional >= self.threshold {
+            self.flush()
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Reset for DollarBars<T>
+where
+    T: Zero,
+{
+    fn reset(&mut self) {
+        self.notional = T::zero();
+        self.bar = None;
+    }
+}
+
+impl<T> Default for DollarBars<T>
+where
+    T: Copy + PartialOrd + Zero + FromPrimitive + fmt::Display,
+{
+    fn default() -> Self {
+        Self::new(T::from_u32(1_000_000).expect("default threshold fits in T")).unwrap()
+    }
+}
+
+impl<T> fmt::Display for DollarBars<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DOLLAR_BARS({})", self.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Close, High, Low, Open, Timestamp, Volume};
+
+    #[test]
+    fn test_new() {
+        assert!(DollarBars::new(0.0).is_err());
+        assert!(DollarBars::new(-1.0).is_err());
+        assert!(DollarBars::new(f64::NAN).is_err());
+        assert!(DollarBars::new(0.5).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bars = DollarBars::new(50.0).unwrap();
+
+        assert!(bars.next((3, 10.0, 1.0)).is_none());
+        assert!(bars.next((5, 12.0, 2.0)).is_none());
+        let bar = bars.next((9, 9.0, 2.0)).unwrap();
+        assert_eq!(bar.timestamp(), Some(3));
+        assert_eq!(bar.open(), 10.0);
+        assert_eq!(bar.high(), 12.0);
+        assert_eq!(bar.low(), 9.0);
+        assert_eq!(bar.close(), 9.0);
+        assert_eq!(bar.volume(), 5.0);
+
+        // a large trade completes a bar on its own
+        let bar = bars.next((12, 20.0, 3.0)).unwrap();
+        assert_eq!(bar.timestamp(), Some(12));
+        assert_eq!(bar.volume(), 3.0);
+
+        assert!(bars.next((13, 10.0, 4.0)).is_none());
+        let bar = bars.next((14, 5.0, 2.0)).unwrap();
+        assert_eq!(bar.volume(), 6.0);
+        assert!(bars.flush().is_none());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bars = DollarBars::new(50.0).unwrap();
+        bars.next((3, 10.0, 4.0));
+
+        bars.reset();
+        assert!(bars.next((5, 12.0, 2.0)).is_none());
+        assert!(bars.next((6, 12.0, 2.0)).is_none());
+        assert_eq!(bars.flush().unwrap().volume(), 4.0);
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(DollarBars::<f64>::default().threshold(), 1_000_000.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let bars = DollarBars::new(2.5).unwrap();
+        assert_eq!(format!("{}", bars), "DOLLAR_BARS(2.5)");
+    }
+}
@@ -0,0 +1,345 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::Sub;
+
+use num_traits::Zero;
+
+use crate::errors::*;
+use crate::{Close, High, Low, Next, Open, Raw, Reset};
+
+/// Kagi chart.
+///
+/// The line extends while the price moves in its direction, and turns once the price moves
+/// back by the reversal amount from its extreme. The line is thick (yang) after rising above
+/// the previous shoulder, and thin (yin) after falling below the previous waist.
+///
+/// Every turn completes a line, which is returned by `next`.
+///
+/// # Parameters
+///
+/// * _reversal_ - reversal amount, greater than 0
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::{Kagi, KagiThickness};
+/// use ta::Next;
+///
+/// let mut kagi = Kagi::new(2.0).unwrap();
+/// kagi.next(10.0);
+/// kagi.next(14.0);
+/// assert!(kagi.next(13.0).is_empty());
+///
+/// let lines = kagi.next(11.0);
+/// assert_eq!((lines[0].open, lines[0].close), (10.0, 14.0));
+/// assert_eq!(lines[0].thickness, KagiThickness::Yang);
+/// ```
+///
+/// # Links
+///
+/// * [Kagi chart, Wikipedia](https://en.wikipedia.org/wiki/Kagi_chart)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Kagi<T> {
+    reversal: T,
+    // First price, before the direction of the first line is known
+    start: Option<T>,
+    line: Option<KagiLine<T>>,
+    shoulder: Option<T>,
+    waist: Option<T>,
+}
+
+/// Line of a [Kagi](struct.Kagi.html) chart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KagiLine<T> {
+    pub open: T,
+    pub high: T,
+    pub low: T,
+    pub close: T,
+    /// Thickness at the end of the line.
+    pub thickness: KagiThickness,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KagiThickness {
+    Yang,
+    Yin,
+}
+
+impl<T> KagiLine<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn new(open: T, close: T, thickness: KagiThickness) -> Self {
+        let (high, low) = if close > open {
+            (close, open)
+        } else {
+            (open, close)
+        };
+        Self {
+            open,
+            high,
+            low,
+            close,
+            thickness,
+        }
+    }
+
+    /// Returns true if the line is rising.
+    pub fn is_up(&self) -> bool {
+        self.close > self.open
+    }
+}
+
+impl<T: Copy> Open<T> for KagiLine<T> {
+    fn open(&self) -> T {
+        self.open
+    }
+}
+
+impl<T: Copy> High<T> for KagiLine<T> {
+    fn high(&self) -> T {
+        self.high
+    }
+}
+
+impl<T: Copy> Low<T> for KagiLine<T> {
+    fn low(&self) -> T {
+        self.low
+    }
+}
+
+impl<T: Copy> Close<T> for KagiLine<T> {
+    fn close(&self) -> T {
+        self.close
+    }
+}
+
+impl<T> Kagi<T>
+where
+    T: Copy + PartialOrd + Zero + fmt::Display,
+{
+    pub fn new(reversal: T) -> Result<Self> {
+        // NaN is not greater than 0 either
+        if reversal.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+            return Err(Error::invalid_parameter(
+                "KAGI",
+                "reversal",
+                reversal,
+                "greater than 0",
+            ));
+        }
+        Ok(Self {
+            reversal,
+            start: None,
+            line: None,
+            shoulder: None,
+            waist: None,
+        })
+    }
+}
+
+impl<T> Kagi<T> {
+    /// Returns the line being drawn.
+    pub fn current(&self) -> Option<&KagiLine<T>> {
+        self.line.as_ref()
+    }
+}
+
+impl<T> Kagi<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn thickness(&self, price: T, thickness: KagiThickness) -> KagiThickness {
+        match (self.shoulder, self.waist) {
+            (Some(shoulder), _) if price > shoulder => KagiThickness::Yang,
+            (_, Some(waist)) if price < waist => KagiThickness::Yin,
+            _ => thickness,
+        }
+    }
+}
+
+impl<T> Next<T, Raw> for Kagi<T>
+where
+    T: Copy + PartialOrd + Sub<Output = T>,
+{
+    type Output = Vec<KagiLine<T>>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let line = match (self.line, self.start) {
+            (Some(line), _) => line,
+            (None, None) => {
+                self.start = Some(input);
+                return Vec::new();
+            }
+            (None, Some(start)) => {
+                if input != start {
+                    let thickness = if input > start {
+                        KagiThickness::Yang
+                    } else {
+                        KagiThickness::Yin
+                    };
+                    self.line = Some(KagiLine::new(start, input, thickness));
+                }
+                return Vec::new();
+            }
+        };
+
+        let extends = if line.is_up() {
+            input >= line.close
+        } else {
+            input <= line.close
+        };
+        if extends {
+            let thickness = self.thickness(input, line.thickness);
+            self.line = Some(KagiLine::new(line.open, input, thickness));
+            return Vec::new();
+        }
+
+        let turns = if line.is_up() {
+            line.close - input >= self.reversal
+        } else {
+            input - line.close >= self.reversal
+        };
+        if !turns {
+            return Vec::new();
+        }
+
+        if line.is_up() {
+            self.shoulder = Some(line.close);
+        } else {
+            self.waist = Some(line.close);
+        }
+        let thickness = self.thickness(input, line.thickness);
+        self.line = Some(KagiLine::new(line.close, input, thickness));
+        vec![line]
+    }
+}
+
+impl<'a, U, T> Next<&'a U, T> for Kagi<T>
+where
+    U: Close<T>,
+    T: Copy + PartialOrd + Sub<Output = T>,
+{
+    type Output = Vec<KagiLine<T>>;
+
+    fn next(&mut self, input: &'a U) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl<T> Reset for Kagi<T> {
+    fn reset(&mut self) {
+        self.start = None;
+        self.line = None;
+        self.shoulder = None;
+        self.waist = None;
+    }
+}
+
+impl<T> fmt::Display for Kagi<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KAGI({})", self.reversal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use KagiThickness::*;
+
+    fn lines(lines: Vec<KagiLine<f64>>) -> Vec<(f64, f64, KagiThickness)> {
+        lines
+            .iter()
+            .map(|l| (l.open, l.close, l.thickness))
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Kagi::new(0.0).is_err());
+        assert!(Kagi::new(-1.0).is_err());
+        assert!(Kagi::new(f64::NAN).is_err());
+        assert!(Kagi::new(0.5).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut kagi = Kagi::new(2.0).unwrap();
+
+        assert!(kagi.next(10.0).is_empty());
+        assert!(kagi.current().is_none());
+        assert!(kagi.next(10.0).is_empty());
+        assert!(kagi.next(12.0).is_empty());
+        assert!(kagi.next(15.0).is_empty());
+        assert!(kagi.next(13.5).is_empty());
+        assert_eq!(lines(kagi.next(13.0)), vec![(10.0, 15.0, Yang)]);
+
+        // shoulder at 15
+        assert!(kagi.next(11.0).is_empty());
+        assert_eq!(lines(kagi.next(14.0)), vec![(15.0, 11.0, Yang)]);
+
+        // waist at 11, rising above the shoulder keeps it yang
+        assert!(kagi.next(16.0).is_empty());
+        assert_eq!(lines(kagi.next(10.0)), vec![(11.0, 16.0, Yang)]);
+
+        // falling below the waist turns it yin
+        let current = kagi.current().unwrap();
+        assert_eq!(
+            (current.open, current.close, current.thickness),
+            (16.0, 10.0, Yin)
+        );
+        assert_eq!(lines(kagi.next(12.0)), vec![(16.0, 10.0, Yin)]);
+    }
+
+    #[test]
+    fn test_next_down_first() {
+        let mut kagi = Kagi::new(1.0).unwrap();
+
+        kagi.next(10.0);
+        kagi.next(8.0);
+        assert_eq!(kagi.current().unwrap().thickness, Yin);
+        assert_eq!(lines(kagi.next(9.0)), vec![(10.0, 8.0, Yin)]);
+
+        kagi.next(10.5);
+        assert_eq!(lines(kagi.next(9.0)), vec![(8.0, 10.5, Yin)]);
+
+        // rising above the shoulder at 10.5 turns it yang
+        assert_eq!(lines(kagi.next(11.0)), vec![(10.5, 9.0, Yin)]);
+        assert_eq!(kagi.current().unwrap().thickness, Yang);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut kagi = Kagi::new(1.0).unwrap();
+        kagi.next(&Bar::new().close(10));
+        kagi.next(&Bar::new().close(12));
+        assert_eq!(kagi.next(&Bar::new().close(11)).len(), 1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut kagi = Kagi::new(1.0).unwrap();
+        kagi.next(10.0);
+        kagi.next(12.0);
+
+        kagi.reset();
+        assert!(kagi.current().is_none());
+        kagi.next(20.0);
+        kagi.next(18.0);
+        assert_eq!(kagi.current().unwrap().open, 20.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Kagi::new(2.5).unwrap()), "KAGI(2.5)");
+    }
+}
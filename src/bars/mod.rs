@@ -0,0 +1,214 @@
+//! Builders of bars, from a stream of trades or of prices.
+//!
+//! Aggregators consume trades as `(timestamp, price, size)` tuples through
+//! [Next](../trait.Next.html), and return the [DataItem](../struct.DataItem.html) of a bar
+//! once it is complete. The timestamp is in milliseconds since the Unix epoch, and trades are
+//! expected in time order:
+//!
+//! * [TimeBars](struct.TimeBars.html)
+//! * [TickBars](struct.TickBars.html)
+//! * [VolumeBars](struct.VolumeBars.html)
+//! * [DollarBars](struct.DollarBars.html)
+//!
+//! Chart builders consume prices, or the close of bars, and return the bricks, lines or
+//! columns completed by every input, which implement `Open`, `High`, `Low` and `Close`:
+//!
+//! * [Renko](struct.Renko.html)
+//! * [RangeBars](struct.RangeBars.html)
+//! * [Kagi](struct.Kagi.html)
+//! * [PointAndFigure](struct.PointAndFigure.html)
+//!
+//! # Example
+//!
+//! ```
+//! use ta::bars::TickBars;
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::{IteratorExt, Next};
+//!
+//! let trades = vec![(0, 10.0, 1.0), (1, 12.0, 2.0), (2, 11.0, 1.0), (3, 13.0, 5.0)];
+//! let mut sma = SimpleMovingAverage::new(2).unwrap();
+//!
+//! let smas: Vec<f64> = trades
+//!     .into_iter()
+//!     .indicate(TickBars::new(2).unwrap())
+//!     .flatten()
+//!     .map(|bar| sma.next(&bar))
+//!     .collect();
+//! assert_eq!(smas, vec![12.0, 12.5]);
+//! ```
+
+use core::ops::Add;
+
+use num_traits::Zero;
+
+use crate::{Close, DataItem, High, Low, Open};
+
+mod time_bars;
+pub use self::time_bars::TimeBars;
+
+mod tick_bars;
+pub use self::tick_bars::TickBars;
+
+mod volume_bars;
+pub use self::volume_bars::VolumeBars;
+
+mod dollar_bars;
+pub use self::dollar_bars::DollarBars;
+
+mod renko;
+pub use self::renko::Renko;
+
+mod range_bars;
+pub use self::range_bars::RangeBars;
+
+mod kagi;
+pub use self::kagi::{Kagi, KagiLine, KagiThickness};
+
+mod point_and_figure;
+pub use self::point_and_figure::{PointAndFigure, PointAndFigureColumn, PointAndFigureMark};
+
+/// Brick of a [Renko](struct.Renko.html) or [RangeBars](struct.RangeBars.html) chart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Brick<T> {
+    pub open: T,
+    pub high: T,
+    pub low: T,
+    pub close: T,
+}
+
+impl<T> Brick<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn new(open: T, close: T) -> Self {
+        let (high, low) = if close > open {
+            (close, open)
+        } else {
+            (open, close)
+        };
+        Self {
+            open,
+            high,
+            low,
+            close,
+        }
+    }
+
+    /// Returns true if the brick closes above its open.
+    pub fn is_up(&self) -> bool {
+        self.close > self.open
+    }
+}
+
+impl<T: Copy> Open<T> for Brick<T> {
+    fn open(&self) -> T {
+        self.open
+    }
+}
+
+impl<T: Copy> High<T> for Brick<T> {
+    fn high(&self) -> T {
+        self.high
+    }
+}
+
+impl<T: Copy> Low<T> for Brick<T> {
+    fn low(&self) -> T {
+        self.low
+    }
+}
+
+impl<T: Copy> Close<T> for Brick<T> {
+    fn close(&self) -> T {
+        self.close
+    }
+}
+
+// Bar being formed by the trades received so far
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct PartialBar<T> {
+    timestamp: i64,
+    open: T,
+    high: T,
+    low: T,
+    close: T,
+    volume: T,
+}
+
+impl<T> PartialBar<T>
+where
+    T: Copy + PartialOrd + Zero + Add<Output = T>,
+{
+    fn new(timestamp: i64, price: T, size: T) -> Self {
+        Self {
+            timestamp,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: size,
+        }
+    }
+
+    fn add(&mut self, price: T, size: T) {
+        if price > self.high {
+            self.high = price;
+        }
+        if price < self.low {
+            self.low = price;
+        }
+        self.close = price;
+        self.volume = self.volume + size;
+    }
+
+    fn build(&self) -> DataItem<T> {
+        // Prices of trades always make a consistent bar, and may be negative, so there is nothing
+        // to validate
+        DataItem {
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            close: self.close,
+            volume: self.volume,
+            timestamp: Some(self.timestamp),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Timestamp, Volume};
+
+    #[test]
+    fn test_brick() {
+        let brick = Brick::new(10.0, 12.0);
+        assert!(brick.is_up());
+        assert_eq!((brick.high(), brick.low()), (12.0, 10.0));
+
+        let brick = Brick::new(10.0, 8.0);
+        assert!(!brick.is_up());
+        assert_eq!(
+            (brick.open(), brick.high(), brick.low(), brick.close()),
+            (10.0, 10.0, 8.0, 8.0)
+        );
+    }
+
+    #[test]
+    fn test_partial_bar() {
+        let mut bar = PartialBar::new(5, 10.0, 1.0);
+        bar.add(12.0, 2.0);
+        bar.add(9.0, 0.5);
+        bar.add(11.0, 1.0);
+
+        let item = bar.build();
+        assert_eq!(item.timestamp(), Some(5));
+        assert_eq!(item.open(), 10.0);
+        assert_eq!(item.high(), 12.0);
+        assert_eq!(item.low(), 9.0);
+        assert_eq!(item.close(), 11.0);
+        assert_eq!(item.volume(), 4.5);
+    }
+}
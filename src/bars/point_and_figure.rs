@@ -0,0 +1,416 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, Sub};
+
+use num_traits::Zero;
+
+use crate::errors::*;
+use crate::{Close, High, Low, Next, Open, Raw, Reset};
+
+/// Point and figure chart.
+///
+/// A column of Xs rises a box at a time, and a column of Os falls a box at a time. The column
+/// reverses once the price moves back by `reversal` boxes from its extreme, the new column
+/// starting one box away from that extreme, as a box holds a single mark. The first column
+/// starts a box away from the first price, once the price moves by a box.
+///
+/// Every reversal completes a column, which is returned by `next`.
+///
+/// # Parameters
+///
+/// * _box_size_ - box size, greater than 0
+/// * _reversal_ - number of boxes of a reversal, integer greater than 0 (usually 3)
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::{PointAndFigure, PointAndFigureMark};
+/// use ta::Next;
+///
+/// let mut pnf = PointAndFigure::new(1.0, 3).unwrap();
+/// pnf.next(10.0);
+/// pnf.next(13.5);
+/// assert!(pnf.next(11.0).is_empty());
+///
+/// let columns = pnf.next(10.0);
+/// assert_eq!(columns[0].mark, PointAndFigureMark::X);
+/// assert_eq!((columns[0].low, columns[0].high, columns[0].boxes), (11.0, 13.0, 3));
+/// ```
+///
+/// # Links
+///
+/// * [Point and figure chart, Wikipedia](https://en.wikipedia.org/wiki/Point_and_figure_chart)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointAndFigure<T> {
+    box_size: T,
+    reversal: u32,
+    // First price, the first column starts from
+    reference: Option<T>,
+    column: Option<PointAndFigureColumn<T>>,
+}
+
+/// Column of a [PointAndFigure](struct.PointAndFigure.html) chart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointAndFigureColumn<T> {
+    pub mark: PointAndFigureMark,
+    /// Level of the highest mark.
+    pub high: T,
+    /// Level of the lowest mark.
+    pub low: T,
+    /// Number of marks, saturating at `u32::MAX`.
+    pub boxes: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PointAndFigureMark {
+    /// Rising column
+    X,
+    /// Falling column
+    O,
+}
+
+impl<T: Copy> Open<T> for PointAndFigureColumn<T> {
+    fn open(&self) -> T {
+        match self.mark {
+            PointAndFigureMark::X => self.low,
+            PointAndFigureMark::O => self.high,
+        }
+    }
+}
+
+impl<T: Copy> High<T> for PointAndFigureColumn<T> {
+    fn high(&self) -> T {
+        self.high
+    }
+}
+
+impl<T: Copy> Low<T> for PointAndFigureColumn<T> {
+    fn low(&self) -> T {
+        self.low
+    }
+}
+
+impl<T: Copy> Close<T> for PointAndFigureColumn<T> {
+    fn close(&self) -> T {
+        match self.mark {
+            PointAndFigureMark::X => self.high,
+            PointAndFigureMark::O => self.low,
+        }
+    }
+}
+
+impl<T> PointAndFigure<T>
+where
+    T: Copy + PartialOrd + Zero + fmt::Display,
+{
+    pub fn new(box_size: T, reversal: u32) -> Result<Self> {
+        // NaN is not greater than 0 either
+        if box_size.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+            return Err(Error::invalid_parameter(
+                "PNF",
+                "box_size",
+                box_size,
+                "greater than 0",
+            ));
+        }
+        if reversal == 0 {
+            return Err(Error::invalid_parameter(
+                "PNF",
+                "reversal",
+                reversal,
+                "greater than 0",
+            ));
+        }
+        Ok(Self {
+            box_size,
+            reversal,
+            reference: None,
+            column: None,
+        })
+    }
+}
+
+impl<T> PointAndFigure<T> {
+    /// Returns the column being drawn.
+    pub fn current(&self) -> Option<&PointAndFigureColumn<T>> {
+        self.column.as_ref()
+    }
+}
+
+impl<T> PointAndFigure<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    // Boxes filled from `level` towards `price`, rising or falling, and the level reached. The
+    // count saturates at `u32::MAX`, and a box size below the resolution of the prices fills no
+    // box.
+    fn fill(&self, mut level: T, price: T, rising: bool) -> (u32, T) {
+        let step = |level: T, size: T| if rising { level + size } else { level - size };
+        let reaches = |level: T| {
+            if rising {
+                price >= level
+            } else {
+                price <= level
+            }
+        };
+
+        let mut boxes = 0u32;
+        while boxes < u32::MAX {
+            let next = step(level, self.box_size);
+            if next == level || !reaches(next) {
+                break;
+            }
+            // Takes the longest run of 2^k boxes which fits, so a gap of n boxes takes
+            // O(log^2 n) steps rather than n
+            let (mut run, mut size) = (1u32, self.box_size);
+            while run <= (u32::MAX - boxes) / 2 && reaches(step(level, size + size)) {
+                run *= 2;
+                size = size + size;
+            }
+            level = step(level, size);
+            boxes += run;
+        }
+        (boxes, level)
+    }
+
+    fn rise(&self, level: T, price: T) -> (u32, T) {
+        self.fill(level, price, true)
+    }
+
+    fn fall(&self, level: T, price: T) -> (u32, T) {
+        self.fill(level, price, false)
+    }
+}
+
+impl<T> Next<T, Raw> for PointAndFigure<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    type Output = Vec<PointAndFigureColumn<T>>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let mut column = match (self.column, self.reference) {
+            (Some(column), _) => column,
+            (None, None) => {
+                self.reference = Some(input);
+                return Vec::new();
+            }
+            (None, Some(reference)) => {
+                let (up, high) = self.rise(reference, input);
+                let (down, low) = self.fall(reference, input);
+                self.column = if up > 0 {
+                    Some(PointAndFigureColumn {
+                        mark: PointAndFigureMark::X,
+                        high,
+                        low: reference + self.box_size,
+                        boxes: up,
+                    })
+                } else if down > 0 {
+                    Some(PointAndFigureColumn {
+                        mark: PointAndFigureMark::O,
+                        high: reference - self.box_size,
+                        low,
+                        boxes: down,
+                    })
+                } else {
+                    None
+                };
+                return Vec::new();
+            }
+        };
+
+        let mut columns = Vec::new();
+        match column.mark {
+            PointAndFigureMark::X => {
+                let (up, high) = self.rise(column.high, input);
+                let (down, low) = self.fall(column.high, input);
+                if up > 0 {
+                    column.high = high;
+                    column.boxes = column.boxes.saturating_add(up);
+                } else if down >= self.reversal {
+                    columns.push(column);
+                    column = PointAndFigureColumn {
+                        mark: PointAndFigureMark::O,
+                        high: column.high - self.box_size,
+                        low,
+                        boxes: down,
+                    };
+                }
+            }
+            PointAndFigureMark::O => {
+                let (down, low) = self.fall(column.low, input);
+                let (up, high) = self.rise(column.low, input);
+                if down > 0 {
+                    column.low = low;
+                    column.boxes = column.boxes.saturating_add(down);
+                } else if up >= self.reversal {
+                    columns.push(column);
+                    column = PointAndFigureColumn {
+                        mark: PointAndFigureMark::X,
+                        high,
+                        low: column.low + self.box_size,
+                        boxes: up,
+                    };
+                }
+            }
+        }
+        self.column = Some(column);
+        columns
+    }
+}
+
+impl<'a, U, T> Next<&'a U, T> for PointAndFigure<T>
+where
+    U: Close<T>,
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    type Output = Vec<PointAndFigureColumn<T>>;
+
+    fn next(&mut self, input: &'a U) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl<T> Reset for PointAndFigure<T> {
+    fn reset(&mut self) {
+        self.reference = None;
+        self.column = None;
+    }
+}
+
+impl<T> fmt::Display for PointAndFigure<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PNF({}, {})", self.box_size, self.reversal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use PointAndFigureMark::*;
+
+    fn columns(
+        columns: Vec<PointAndFigureColumn<f64>>,
+    ) -> Vec<(PointAndFigureMark, f64, f64, u32)> {
+        columns
+            .iter()
+            .map(|c| (c.mark, c.high, c.low, c.boxes))
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(PointAndFigure::new(0.0, 3).is_err());
+        assert!(PointAndFigure::new(-1.0, 3).is_err());
+        assert!(PointAndFigure::new(f64::NAN, 3).is_err());
+        assert!(PointAndFigure::new(1.0, 0).is_err());
+        assert!(PointAndFigure::new(0.5, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut pnf = PointAndFigure::new(1.0, 3).unwrap();
+
+        assert!(pnf.next(10.0).is_empty());
+        assert!(pnf.next(10.5).is_empty());
+        assert!(pnf.current().is_none());
+
+        assert!(pnf.next(12.0).is_empty());
+        assert!(pnf.next(13.5).is_empty());
+        let current = pnf.current().unwrap();
+        assert_eq!(
+            (current.mark, current.high, current.low, current.boxes),
+            (X, 13.0, 11.0, 3)
+        );
+
+        // falling less than the reversal
+        assert!(pnf.next(10.5).is_empty());
+        assert_eq!(pnf.current().unwrap().high, 13.0);
+
+        // the Os start one box below the highest X
+        assert_eq!(columns(pnf.next(9.5)), vec![(X, 13.0, 11.0, 3)]);
+        let current = pnf.current().unwrap();
+        assert_eq!(
+            (current.mark, current.high, current.low, current.boxes),
+            (O, 12.0, 10.0, 3)
+        );
+
+        // and the Xs one box above the lowest O
+        assert!(pnf.next(8.0).is_empty());
+        assert_eq!(columns(pnf.next(11.0)), vec![(O, 12.0, 8.0, 5)]);
+        let current = pnf.current().unwrap();
+        assert_eq!((current.open(), current.close()), (9.0, 11.0));
+    }
+
+    #[test]
+    fn test_next_down_first() {
+        let mut pnf = PointAndFigure::new(2.0, 1).unwrap();
+
+        pnf.next(10.0);
+        pnf.next(5.0);
+        let current = pnf.current().unwrap();
+        assert_eq!(
+            (current.mark, current.high, current.low, current.boxes),
+            (O, 8.0, 6.0, 2)
+        );
+        assert_eq!((current.open(), current.close()), (8.0, 6.0));
+
+        assert_eq!(columns(pnf.next(8.0)), vec![(O, 8.0, 6.0, 2)]);
+        assert_eq!(pnf.current().unwrap().low, 8.0);
+    }
+
+    #[test]
+    fn test_next_tiny_box_size() {
+        let mut pnf = PointAndFigure::new(1e-20, 1).unwrap();
+        assert!(pnf.next(100.0).is_empty());
+        assert!(pnf.next(101.0).is_empty());
+        assert!(pnf.next(99.0).is_empty());
+    }
+
+    #[test]
+    fn test_next_many_boxes() {
+        let mut pnf = PointAndFigure::new(1.0, 1).unwrap();
+        pnf.next(0.0);
+        pnf.next(5e9);
+        assert_eq!(pnf.current().unwrap().boxes, u32::MAX);
+        pnf.next(1e10);
+        assert_eq!(pnf.current().unwrap().boxes, u32::MAX);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut pnf = PointAndFigure::new(1.0, 1).unwrap();
+        pnf.next(&Bar::new().close(10));
+        pnf.next(&Bar::new().close(12));
+        assert_eq!(pnf.next(&Bar::new().close(11)).len(), 1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut pnf = PointAndFigure::new(1.0, 3).unwrap();
+        pnf.next(10.0);
+        pnf.next(12.0);
+
+        pnf.reset();
+        assert!(pnf.current().is_none());
+        pnf.next(20.0);
+        pnf.next(21.0);
+        assert_eq!(pnf.current().unwrap().low, 21.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", PointAndFigure::new(0.5, 3).unwrap()),
+            "PNF(0.5, 3)"
+        );
+    }
+}
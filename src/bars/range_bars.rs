@@ -0,0 +1,220 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, Sub};
+
+use num_traits::Zero;
+
+use super::Brick;
+use crate::errors::*;
+use crate::{Close, Next, Raw, Reset};
+
+/// Range bars.
+///
+/// A bar is complete once its high and low are the range apart, it then closes at that
+/// extreme and the next bar opens there. A single input can complete several bars, or none.
+///
+/// # Parameters
+///
+/// * _range_ - range of a bar, greater than 0
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::RangeBars;
+/// use ta::Next;
+///
+/// let mut bars = RangeBars::new(1.0).unwrap();
+/// assert!(bars.next(10.0).is_empty());
+/// assert!(bars.next(9.5).is_empty());
+///
+/// let completed = bars.next(10.75);
+/// assert_eq!(completed.len(), 1);
+/// assert_eq!((completed[0].low, completed[0].close), (9.5, 10.5));
+/// assert_eq!(bars.current().unwrap().open, 10.5);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RangeBars<T> {
+    range: T,
+    bar: Option<Brick<T>>,
+}
+
+impl<T> RangeBars<T>
+where
+    T: Copy + PartialOrd + Zero + fmt::Display,
+{
+    pub fn new(range: T) -> Result<Self> {
+        // NaN is not greater than 0 either
+        if range.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+            return Err(Error::invalid_parameter(
+                "RANGE_BARS",
+                "range",
+                range,
+                "greater than 0",
+            ));
+        }
+        Ok(Self { range, bar: None })
+    }
+}
+
+impl<T> RangeBars<T> {
+    /// Returns the bar being formed.
+    pub fn current(&self) -> Option<&Brick<T>> {
+        self.bar.as_ref()
+    }
+}
+
+impl<T> Next<T, Raw> for RangeBars<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    type Output = Vec<Brick<T>>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let mut bars = Vec::new();
+        let mut bar = match self.bar {
+            Some(bar) => bar,
+            None => {
+                self.bar = Some(Brick::new(input, input));
+                return bars;
+            }
+        };
+
+        // A range below the resolution of the prices never completes a bar, rather than completing
+        // empty ones forever
+        loop {
+            let (high, low) = (bar.low + self.range, bar.high - self.range);
+            let close = if high > bar.low && input >= high {
+                bar.high = high;
+                high
+            } else if low < bar.high && input <= low {
+                bar.low = low;
+                low
+            } else {
+                break;
+            };
+            bar.close = close;
+            bars.push(bar);
+            bar = Brick::new(close, close);
+        }
+
+        if input > bar.high {
+            bar.high = input;
+        }
+        if input < bar.low {
+            bar.low = input;
+        }
+        bar.close = input;
+        self.bar = Some(bar);
+        bars
+    }
+}
+
+impl<'a, U, T> Next<&'a U, T> for RangeBars<T>
+where
+    U: Close<T>,
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    type Output = Vec<Brick<T>>;
+
+    fn next(&mut self, input: &'a U) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl<T> Reset for RangeBars<T> {
+    fn reset(&mut self) {
+        self.bar = None;
+    }
+}
+
+impl<T> fmt::Display for RangeBars<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RANGE_BARS({})", self.range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn ohlc(bars: Vec<Brick<f64>>) -> Vec<(f64, f64, f64, f64)> {
+        bars.iter()
+            .map(|b| (b.open, b.high, b.low, b.close))
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(RangeBars::new(0.0).is_err());
+        assert!(RangeBars::new(-1.0).is_err());
+        assert!(RangeBars::new(f64::NAN).is_err());
+        assert!(RangeBars::new(0.5).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bars = RangeBars::new(2.0).unwrap();
+
+        assert!(bars.next(10.0).is_empty());
+        assert!(bars.next(11.0).is_empty());
+        assert!(bars.next(9.5).is_empty());
+        assert_eq!(ohlc(bars.next(8.5)), vec![(10.0, 11.0, 9.0, 9.0)]);
+        assert_eq!(
+            bars.current().map(|b| (b.open, b.high, b.low, b.close)),
+            Some((9.0, 9.0, 8.5, 8.5))
+        );
+
+        // a gap completes several bars
+        assert_eq!(
+            ohlc(bars.next(13.0)),
+            vec![(9.0, 10.5, 8.5, 10.5), (10.5, 12.5, 10.5, 12.5)]
+        );
+        assert_eq!(
+            bars.current().map(|b| (b.open, b.high, b.low, b.close)),
+            Some((12.5, 13.0, 12.5, 13.0))
+        );
+
+        // reaching the range exactly completes the bar
+        assert_eq!(ohlc(bars.next(14.5)), vec![(12.5, 14.5, 12.5, 14.5)]);
+    }
+
+    #[test]
+    fn test_next_tiny_range() {
+        let mut bars = RangeBars::new(1e-20).unwrap();
+        assert!(bars.next(100.0).is_empty());
+        assert!(bars.next(101.0).is_empty());
+        assert!(bars.next(99.0).is_empty());
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut bars = RangeBars::new(1.0).unwrap();
+        bars.next(&Bar::new().close(10));
+        assert_eq!(bars.next(&Bar::new().close(11)).len(), 1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bars = RangeBars::new(1.0).unwrap();
+        bars.next(10.0);
+
+        bars.reset();
+        assert!(bars.current().is_none());
+        assert!(bars.next(20.0).is_empty());
+        assert_eq!(bars.current().unwrap().open, 20.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", RangeBars::new(2.5).unwrap()),
+            "RANGE_BARS(2.5)"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::Div;
+
+use num_traits::{FromPrimitive, One, Signed, Zero};
+
+use super::Brick;
+use crate::errors::*;
+use crate::indicators::AverageTrueRange;
+use crate::{Close, High, Low, Next, Raw, Reset};
+
+/// Renko chart.
+///
+/// A brick is added every time the price moves a box size past the close of the last brick
+/// in its direction, while a reversal needs a move of two box sizes. A single input can
+/// complete several bricks, or none.
+///
+/// The box size is either fixed, or the latest [AverageTrueRange](../indicators/struct.AverageTrueRange.html)
+/// of the inputs.
+///
+/// # Parameters
+///
+/// * _box_size_ - fixed box size, greater than 0
+/// * _length_ - number of periods of the ATR, integer greater than 0
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::Renko;
+/// use ta::Next;
+///
+/// let mut renko = Renko::new(1.0).unwrap();
+/// assert!(renko.next(10.0).is_empty());
+/// assert!(renko.next(10.5).is_empty());
+///
+/// let bricks = renko.next(12.2);
+/// assert_eq!(bricks.len(), 2);
+/// assert_eq!((bricks[1].open, bricks[1].close), (11.0, 12.0));
+///
+/// // reversals need two boxes
+/// assert!(renko.next(10.5).is_empty());
+/// assert_eq!(renko.next(10.0)[0].close, 10.0);
+/// ```
+///
+/// # Links
+///
+/// * [Renko chart, Wikipedia](https://en.wikipedia.org/wiki/Renko_chart)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Renko<T> {
+    box_size: BoxSize<T>,
+    // First price, the bricks start from
+    reference: Option<T>,
+    last: Option<Brick<T>>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum BoxSize<T> {
+    Fixed(T),
+    Atr(AverageTrueRange<T>),
+}
+
+impl<T> Renko<T>
+where
+    T: Copy + PartialOrd + Zero + fmt::Display,
+{
+    /// Creates a Renko chart with a fixed box size.
+    pub fn new(box_size: T) -> Result<Self> {
+        // NaN is not greater than 0 either
+        if box_size.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+            return Err(Error::invalid_parameter(
+                "RENKO",
+                "box_size",
+                box_size,
+                "greater than 0",
+            ));
+        }
+        Ok(Self {
+            box_size: BoxSize::Fixed(box_size),
+            reference: None,
+            last: None,
+        })
+    }
+}
+
+impl<T> Renko<T>
+where
+    T: Zero + One + Div<Output = T> + FromPrimitive,
+{
+    /// Creates a Renko chart whose box size is the ATR over `length` periods.
+    pub fn with_atr(length: u32) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter(
+                "RENKO_ATR",
+                "length",
+                length,
+                "greater than 0",
+            ));
+        }
+        Ok(Self {
+            box_size: BoxSize::Atr(AverageTrueRange::new(length)?),
+            reference: None,
+            last: None,
+        })
+    }
+}
+
+impl<T> Renko<T>
+where
+    T: Copy + PartialOrd + Signed,
+{
+    /// Returns the last completed brick.
+    pub fn last(&self) -> Option<&Brick<T>> {
+        self.last.as_ref()
+    }
+
+    fn bricks(&mut self, price: T, box_size: T) -> Vec<Brick<T>> {
+        let mut bricks = Vec::new();
+        let reference = match self.reference {
+            Some(reference) => reference,
+            None => {
+                self.reference = Some(price);
+                return bricks;
+            }
+        };
+        // The ATR is 0 until the price moves, and NaN after a NaN price
+        if box_size.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+            return bricks;
+        }
+
+        loop {
+            let (up_from, down_from) = match self.last {
+                None => (reference, reference),
+                Some(last) if last.is_up() => (last.close, last.open),
+                Some(last) => (last.open, last.close),
+            };
+            // A box size below the resolution of the prices, as the ATR of a long flat run, moves
+            // no level and would add bricks forever
+            let (up_to, down_to) = (up_from + box_size, down_from - box_size);
+            let brick = if up_to > up_from && price >= up_to {
+                Brick::new(up_from, up_to)
+            } else if down_to < down_from && price <= down_to {
+                Brick::new(down_from, down_to)
+            } else {
+                break;
+            };
+            self.last = Some(brick);
+            bricks.push(brick);
+        }
+        bricks
+    }
+}
+
+impl<T> Next<T, Raw> for Renko<T>
+where
+    T: Copy + PartialOrd + Signed,
+{
+    type Output = Vec<Brick<T>>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let box_size = match self.box_size {
+            BoxSize::Fixed(box_size) => box_size,
+            BoxSize::Atr(ref mut atr) => atr.next(input),
+        };
+        self.bricks(input, box_size)
+    }
+}
+
+impl<'a, U, T> Next<&'a U, T> for Renko<T>
+where
+    U: High<T> + Low<T> + Close<T>,
+    T: Copy + PartialOrd + Signed,
+{
+    type Output = Vec<Brick<T>>;
+
+    fn next(&mut self, input: &'a U) -> Self::Output {
+        let box_size = match self.box_size {
+            BoxSize::Fixed(box_size) => box_size,
+            BoxSize::Atr(ref mut atr) => atr.next(input),
+        };
+        self.bricks(input.close(), box_size)
+    }
+}
+
+impl<T> Reset for Renko<T>
+where
+    T: Copy + PartialOrd + Signed,
+{
+    fn reset(&mut self) {
+        if let BoxSize::Atr(ref mut atr) = self.box_size {
+            atr.reset();
+        }
+        self.reference = None;
+        self.last = None;
+    }
+}
+
+impl<T> fmt::Display for Renko<T>
+where
+    T: Zero + One + Div<Output = T> + FromPrimitive + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.box_size {
+            BoxSize::Fixed(ref box_size) => write!(f, "RENKO({})", box_size),
+            BoxSize::Atr(ref atr) => write!(f, "RENKO_{}", atr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn open_close(bricks: Vec<Brick<f64>>) -> Vec<(f64, f64)> {
+        bricks.iter().map(|b| (b.open, b.close)).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Renko::new(0.0).is_err());
+        assert!(Renko::new(-1.0).is_err());
+        assert!(Renko::new(f64::NAN).is_err());
+        assert!(Renko::new(0.5).is_ok());
+
+        assert!(Renko::<f64>::with_atr(0).is_err());
+        assert!(Renko::<f64>::with_atr(14).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut renko = Renko::new(2.0).unwrap();
+
+        assert!(renko.next(10.0).is_empty());
+        assert!(renko.next(11.9).is_empty());
+        assert_eq!(open_close(renko.next(12.0)), vec![(10.0, 12.0)]);
+        assert_eq!(
+            open_close(renko.next(17.0)),
+            vec![(12.0, 14.0), (14.0, 16.0)]
+        );
+        // a reversal starts from the open of the last brick
+        assert!(renko.next(14.5).is_empty());
+        assert_eq!(
+            open_close(renko.next(9.0)),
+            vec![(14.0, 12.0), (12.0, 10.0)]
+        );
+        assert!(renko.next(11.0).is_empty());
+        assert_eq!(open_close(renko.next(14.0)), vec![(12.0, 14.0)]);
+
+        let last = renko.last().unwrap();
+        assert_eq!((last.high, last.low), (14.0, 12.0));
+    }
+
+    #[test]
+    fn test_next_down_first() {
+        let mut renko = Renko::new(1.0).unwrap();
+
+        renko.next(10.0);
+        assert_eq!(open_close(renko.next(7.5)), vec![(10.0, 9.0), (9.0, 8.0)]);
+        assert_eq!(open_close(renko.next(7.0)), vec![(8.0, 7.0)]);
+    }
+
+    #[test]
+    fn test_next_with_atr() {
+        let mut renko = Renko::with_atr(1).unwrap();
+        let bar = |high, low, close| Bar::new().high(high).low(low).close(close);
+
+        // ATR(1) is the true range of the latest bar
+        assert!(renko.next(&bar(11.0, 9.0, 10.0)).is_empty());
+        assert_eq!(
+            open_close(renko.next(&bar(14.0, 12.0, 14.0))),
+            vec![(10.0, 14.0)]
+        );
+        assert_eq!(
+            open_close(renko.next(&bar(15.0, 14.0, 15.0))),
+            vec![(14.0, 15.0)]
+        );
+
+        let mut renko = Renko::with_atr(1).unwrap();
+        renko.next(10.0);
+        assert_eq!(open_close(renko.next(12.0)), vec![(10.0, 12.0)]);
+    }
+
+    #[test]
+    fn test_next_with_atr_flat() {
+        let mut renko = Renko::<f64>::with_atr(14).unwrap();
+        renko.next(100.0);
+        renko.next(101.0);
+
+        // the ATR decays below the resolution of the prices
+        for _ in 0..2000 {
+            renko.next(100.0);
+        }
+        assert!(renko.next(100.0).is_empty());
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut renko = Renko::new(1.0).unwrap();
+        renko.next(&Bar::new().high(12).low(9).close(10));
+        assert_eq!(
+            open_close(renko.next(&Bar::new().high(12).low(9).close(11))),
+            vec![(10.0, 11.0)]
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut renko = Renko::new(1.0).unwrap();
+        renko.next(10.0);
+        renko.next(12.0);
+
+        renko.reset();
+        assert!(renko.last().is_none());
+        assert!(renko.next(20.0).is_empty());
+        assert_eq!(open_close(renko.next(21.0)), vec![(20.0, 21.0)]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Renko::new(2.5).unwrap()), "RENKO(2.5)");
+        assert_eq!(
+            format!("{}", Renko::<f64>::with_atr(14).unwrap()),
+            "RENKO_ATR(14)"
+        );
+    }
+}
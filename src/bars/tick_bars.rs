@@ -0,0 +1,165 @@
+use core::fmt;
+use core::ops::Add;
+
+use num_traits::Zero;
+
+use super::PartialBar;
+use crate::errors::*;
+use crate::{DataItem, Next, Raw, Reset};
+
+/// Bars of a fixed number of trades.
+///
+/// The timestamp of a bar is the timestamp of its first trade.
+///
+/// # Parameters
+///
+/// * _n_ - number of trades per bar, integer greater than 0
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::TickBars;
+/// use ta::{Close, Next, Volume};
+///
+/// let mut bars = TickBars::new(2).unwrap();
+/// assert!(bars.next((1_000, 10.0, 1.0)).is_none());
+///
+/// let bar = bars.next((1_500, 11.0, 3.0)).unwrap();
+/// assert_eq!((bar.close(), bar.volume()), (11.0, 4.0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TickBars<T> {
+    n: u32,
+    count: u32,
+    bar: Option<PartialBar<T>>,
+}
+
+impl<T> TickBars<T> {
+    pub fn new(n: u32) -> Result<Self> {
+        match n {
+            0 => Err(Error::invalid_parameter(
+                "TICK_BARS",
+                "n",
+                n,
+                "greater than 0",
+            )),
+            _ => Ok(Self {
+                n,
+                count: 0,
+                bar: None,
+            }),
+        }
+    }
+
+    pub fn n(&self) -> u32 {
+        self.n
+    }
+}
+
+impl<T> TickBars<T>
+where
+    T: Copy + PartialOrd + Zero + Add<Output = T>,
+{
+    /// Returns the bar being formed, if any, and starts over.
+    pub fn flush(&mut self) -> Option<DataItem<T>> {
+        self.count = 0;
+        self.bar.take().map(|bar| bar.build())
+    }
+}
+
+impl<T> Next<(i64, T, T), Raw> for TickBars<T>
+where
+    T: Copy + PartialOrd + Zero + Add<Output = T>,
+{
+    type Output = Option<DataItem<T>>;
+
+    fn next(&mut self, (timestamp, price, size): (i64, T, T)) -> Self::Output {
+        match self.bar {
+            Some(ref mut bar) => bar.add(price, size),
+            None => self.bar = Some(PartialBar::new(timestamp, price, size)),
+        }
+
+        self.count += 1;
+        if self.count == self.n {
+            self.flush()
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Reset for TickBars<T> {
+    fn reset(&mut self) {
+        self.count = 0;
+        self.bar = None;
+    }
+}
+
+impl<T> Default for TickBars<T> {
+    fn default() -> Self {
+        Self::new(100).unwrap()
+    }
+}
+
+impl<T> fmt::Display for TickBars<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TICK_BARS({})", self.n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{High, Low, Open, Timestamp, Volume};
+
+    #[test]
+    fn test_new() {
+        assert!(TickBars::<f64>::new(0).is_err());
+        assert!(TickBars::<f64>::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bars = TickBars::new(3).unwrap();
+
+        assert!(bars.next((3, 10.0, 1.0)).is_none());
+        assert!(bars.next((5, 12.0, 2.0)).is_none());
+        let bar = bars.next((9, 9.0, 1.0)).unwrap();
+        assert_eq!(bar.timestamp(), Some(3));
+        assert_eq!(bar.open(), 10.0);
+        assert_eq!(bar.high(), 12.0);
+        assert_eq!(bar.low(), 9.0);
+        assert_eq!(bar.volume(), 4.0);
+
+        assert!(bars.next((12, 11.0, 1.0)).is_none());
+        let bar = bars.flush().unwrap();
+        assert_eq!(bar.timestamp(), Some(12));
+        assert_eq!(bar.volume(), 1.0);
+
+        let mut bars = TickBars::new(1).unwrap();
+        assert_eq!(bars.next((1, 10.0, 1.0)).unwrap().open(), 10.0);
+        assert_eq!(bars.next((2, 11.0, 1.0)).unwrap().open(), 11.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bars = TickBars::new(2).unwrap();
+        bars.next((3, 10.0, 1.0));
+
+        bars.reset();
+        assert!(bars.next((5, 12.0, 2.0)).is_none());
+        assert_eq!(bars.next((6, 13.0, 2.0)).unwrap().open(), 12.0);
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(TickBars::<f64>::default().n(), 100);
+    }
+
+    #[test]
+    fn test_display() {
+        let bars = TickBars::<f64>::new(50).unwrap();
+        assert_eq!(format!("{}", bars), "TICK_BARS(50)");
+    }
+}
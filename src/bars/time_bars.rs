@@ -0,0 +1,181 @@
+use core::fmt;
+use core::ops::Add;
+
+use num_traits::Zero;
+
+use super::PartialBar;
+use crate::errors::*;
+use crate::{DataItem, Next, Raw, Reset};
+
+/// Bars covering fixed periods of time.
+///
+/// Periods are aligned to the Unix epoch and the timestamp of a bar is the start of its
+/// period. Periods without trades give no bar. A bar is complete when a trade of a later
+/// period arrives, call `flush` to get the bar of the last period.
+///
+/// # Parameters
+///
+/// * _length_ - length of a period in milliseconds, integer greater than 0
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::TimeBars;
+/// use ta::{Close, Next, Timestamp};
+///
+/// let mut bars = TimeBars::new(60_000).unwrap();
+/// assert!(bars.next((1_000, 10.0, 1.0)).is_none());
+/// assert!(bars.next((59_000, 11.0, 1.0)).is_none());
+///
+/// let bar = bars.next((61_000, 12.0, 1.0)).unwrap();
+/// assert_eq!((bar.timestamp(), bar.close()), (Some(0), 11.0));
+///
+/// let bar = bars.flush().unwrap();
+/// assert_eq!((bar.timestamp(), bar.close()), (Some(60_000), 12.0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeBars<T> {
+    length: i64,
+    bar: Option<PartialBar<T>>,
+}
+
+impl<T> TimeBars<T> {
+    pub fn new(length: i64) -> Result<Self> {
+        if length <= 0 {
+            return Err(Error::invalid_parameter(
+                "TIME_BARS",
+                "length",
+                length,
+                "greater than 0",
+            ));
+        }
+        Ok(Self { length, bar: None })
+    }
+
+    pub fn length(&self) -> i64 {
+        self.length
+    }
+}
+
+impl<T> TimeBars<T>
+where
+    T: Copy + PartialOrd + Zero + Add<Output = T>,
+{
+    /// Returns the bar being formed, if any, and starts over.
+    pub fn flush(&mut self) -> Option<DataItem<T>> {
+        self.bar.take().map(|bar| bar.build())
+    }
+}
+
+impl<T> Next<(i64, T, T), Raw> for TimeBars<T>
+where
+    T: Copy + PartialOrd + Zero + Add<Output = T>,
+{
+    type Output = Option<DataItem<T>>;
+
+    fn next(&mut self, (timestamp, price, size): (i64, T, T)) -> Self::Output {
+        let start = timestamp - timestamp.rem_euclid(self.length);
+        match self.bar {
+            Some(ref mut bar) if bar.timestamp == start => {
+                bar.add(price, size);
+                None
+            }
+            _ => {
+                let complete = self.flush();
+                self.bar = Some(PartialBar::new(start, price, size));
+                complete
+            }
+        }
+    }
+}
+
+impl<T> Reset for TimeBars<T> {
+    fn reset(&mut self) {
+        self.bar = None;
+    }
+}
+
+impl<T> Default for TimeBars<T> {
+    fn default() -> Self {
+        // one minute
+        Self::new(60_000).unwrap()
+    }
+}
+
+impl<T> fmt::Display for TimeBars<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TIME_BARS({})", self.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Close, High, Low, Open, Timestamp, Volume};
+
+    #[test]
+    fn test_new() {
+        assert!(TimeBars::<f64>::new(0).is_err());
+        assert!(TimeBars::<f64>::new(-1).is_err());
+        assert!(TimeBars::<f64>::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bars = TimeBars::new(10).unwrap();
+
+        assert!(bars.next((3, 10.0, 1.0)).is_none());
+        assert!(bars.next((5, 12.0, 2.0)).is_none());
+        assert!(bars.next((9, 9.0, 1.0)).is_none());
+
+        // no trade between 10 and 30
+        let bar = bars.next((34, 11.0, 4.0)).unwrap();
+        assert_eq!(bar.timestamp(), Some(0));
+        assert_eq!(bar.open(), 10.0);
+        assert_eq!(bar.high(), 12.0);
+        assert_eq!(bar.low(), 9.0);
+        assert_eq!(bar.close(), 9.0);
+        assert_eq!(bar.volume(), 4.0);
+
+        let bar = bars.next((40, 11.5, 1.0)).unwrap();
+        assert_eq!(bar.timestamp(), Some(30));
+        assert_eq!(bar.open(), 11.0);
+        assert_eq!(bar.close(), 11.0);
+        assert_eq!(bar.volume(), 4.0);
+
+        let bar = bars.flush().unwrap();
+        assert_eq!(bar.timestamp(), Some(40));
+        assert!(bars.flush().is_none());
+    }
+
+    #[test]
+    fn test_next_before_epoch() {
+        let mut bars = TimeBars::new(10).unwrap();
+
+        assert!(bars.next((-3, 10.0, 1.0)).is_none());
+        let bar = bars.next((0, 12.0, 2.0)).unwrap();
+        assert_eq!(bar.timestamp(), Some(-10));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bars = TimeBars::new(10).unwrap();
+        bars.next((3, 10.0, 1.0));
+
+        bars.reset();
+        assert!(bars.next((5, 12.0, 2.0)).is_none());
+        assert_eq!(bars.flush().unwrap().open(), 12.0);
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(TimeBars::<f64>::default().length(), 60_000);
+    }
+
+    #[test]
+    fn test_display() {
+        let bars = TimeBars::<f64>::new(60_000).unwrap();
+        assert_eq!(format!("{}", bars), "TIME_BARS(60000)");
+    }
+}
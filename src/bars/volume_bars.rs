@@ -0,0 +1,183 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::Add;
+
+use num_traits::{FromPrimitive, Zero};
+
+use super::PartialBar;
+use crate::errors::*;
+use crate::{DataItem, Next, Raw, Reset};
+
+/// Bars of a fixed traded volume.
+///
+/// A bar is complete once its volume reaches the threshold. Trades are not split, so the
+/// volume of a bar can exceed the threshold. The timestamp of a bar is the timestamp of its
+/// first trade.
+///
+/// # Parameters
+///
+/// * _threshold_ - volume per bar, greater than 0
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::VolumeBars;
+/// use ta::{Close, Next, Volume};
+///
+/// let mut bars = VolumeBars::new(100.0).unwrap();
+/// assert!(bars.next((1_000, 10.0, 60.0)).is_none());
+///
+/// let bar = bars.next((1_500, 11.0, 50.0)).unwrap();
+/// assert_eq!((bar.close(), bar.volume()), (11.0, 110.0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeBars<T> {
+    threshold: T,
+    bar: Option<PartialBar<T>>,
+}
+
+impl<T> VolumeBars<T>
+where
+    T: Copy + PartialOrd + Zero + fmt::Display,
+{
+    pub fn new(threshold: T) -> Result<Self> {
+        // NaN is not greater than 0 either
+        if threshold.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+            return Err(Error::invalid_parameter(
+                "VOLUME_BARS",
+                "threshold",
+                threshold,
+                "greater than 0",
+            ));
+        }
+        Ok(Self {
+            threshold,
+            bar: None,
+        })
+    }
+
+    pub fn threshold(&self) -> T {
+        self.threshold
+    }
+}
+
+impl<T> VolumeBars<T>
+where
+    T: Copy + PartialOrd + Zero + Add<Output = T>,
+{
+    /// Returns the bar being formed, if any, and starts over.
+    pub fn flush(&mut self) -> Option<DataItem<T>> {
+        self.bar.take().map(|bar| bar.build())
+    }
+}
+
+impl<T> Next<(i64, T, T), Raw> for VolumeBars<T>
+where
+    T: Copy + PartialOrd + Zero + Add<Output = T>,
+{
+    type Output = Option<DataItem<T>>;
+
+    fn next(&mut self, (timestamp, price, size): (i64, T, T)) -> Self::Output {
+        let volume = match self.bar {
+            Some(ref mut bar) => {
+                bar.add(price, size);
+                bar.volume
+            }
+            None => {
+                self.bar = Some(PartialBar::new(timestamp, price, size));
+                size
+            }
+        };
+
+        if volume >= self.threshold {
+            self.flush()
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Reset for VolumeBars<T> {
+    fn reset(&mut self) {
+        self.bar = None;
+    }
+}
+
+impl<T> Default for VolumeBars<T>
+where
+    T: Copy + PartialOrd + Zero + FromPrimitive + fmt::Display,
+{
+    fn default() -> Self {
+        Self::new(T::from_u32(1000).expect("default threshold fits in T")).unwrap()
+    }
+}
+
+impl<T> fmt::Display for VolumeBars<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VOLUME_BARS({})", self.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Close, High, Low, Open, Timestamp, Volume};
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeBars::new(0.0).is_err());
+        assert!(VolumeBars::new(-1.0).is_err());
+        assert!(VolumeBars::new(f64::NAN).is_err());
+        assert!(VolumeBars::new(0.5).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bars = VolumeBars::new(5.0).unwrap();
+
+        assert!(bars.next((3, 10.0, 1.0)).is_none());
+        assert!(bars.next((5, 12.0, 2.0)).is_none());
+        let bar = bars.next((9, 9.0, 2.0)).unwrap();
+        assert_eq!(bar.timestamp(), Some(3));
+        assert_eq!(bar.open(), 10.0);
+        assert_eq!(bar.high(), 12.0);
+        assert_eq!(bar.low(), 9.0);
+        assert_eq!(bar.close(), 9.0);
+        assert_eq!(bar.volume(), 5.0);
+
+        // a large trade completes a bar on its own
+        let bar = bars.next((12, 11.0, 8.0)).unwrap();
+        assert_eq!(bar.timestamp(), Some(12));
+        assert_eq!(bar.volume(), 8.0);
+
+        assert!(bars.next((13, 11.0, 4.0)).is_none());
+        let bar = bars.next((14, 10.0, 3.0)).unwrap();
+        assert_eq!(bar.volume(), 7.0);
+        assert!(bars.flush().is_none());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bars = VolumeBars::new(5.0).unwrap();
+        bars.next((3, 10.0, 4.0));
+
+        bars.reset();
+        assert!(bars.next((5, 12.0, 2.0)).is_none());
+        assert_eq!(bars.flush().unwrap().volume(), 2.0);
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(VolumeBars::<f64>::default().threshold(), 1000.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let bars = VolumeBars::new(2.5).unwrap();
+        assert_eq!(format!("{}", bars), "VOLUME_BARS(2.5)");
+    }
+}
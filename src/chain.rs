@@ -0,0 +1,320 @@
+use core::fmt;
+
+use crate::{Next, Peek, Raw, Reset, UpdateLast, Warmup};
+
+/// Extends indicators with combinators.
+pub trait NextExt: Sized {
+    /// Feeds the outputs of this indicator to `second`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ta::indicators::{ExponentialMovingAverage, RelativeStrengthIndex};
+    /// use ta::{Next, NextExt};
+    ///
+    /// let rsi = RelativeStrengthIndex::new(14).unwrap();
+    /// let mut ema_of_rsi = rsi.then(ExponentialMovingAverage::new(9).unwrap());
+    /// assert_eq!(format!("{}", ema_of_rsi), "EMA(9)∘RSI(14)");
+    /// assert_eq!(ema_of_rsi.next(10.0), 50.0);
+    /// ```
+    fn then<B>(self, second: B) -> Chain<Self, B> {
+        Chain::new(self, second)
+    }
+
+    /// Maps the outputs of this indicator with `projection`, e.g. to pick a field of a
+    /// multi-output indicator before passing it on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ta::indicators::{MacdOutput, MovingAverageConvergenceDivergence, SimpleMovingAverage};
+    /// use ta::{Next, NextExt};
+    ///
+    /// let macd = MovingAverageConvergenceDivergence::new(3, 6, 4).unwrap();
+    /// let mut sma_of_histogram = macd
+    ///     .project(|out: MacdOutput<f64>| out.histogram)
+    ///     .then(SimpleMovingAverage::new(2).unwrap());
+    /// assert_eq!(sma_of_histogram.next(2.0), 0.0);
+    /// ```
+    fn project<F>(self, projection: F) -> Project<Self, F> {
+        Project {
+            indicator: self,
+            projection,
+        }
+    }
+}
+
+impl<N> NextExt for N {}
+
+/// Indicator feeding the outputs of `A` to `B`.
+///
+/// It is created by [NextExt::then](trait.NextExt.html#method.then).
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+    count: usize,
+}
+
+impl<A, B> Chain<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            count: 0,
+        }
+    }
+
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A, B, T, U> Next<T, U> for Chain<A, B>
+where
+    A: Next<T, U>,
+    B: Next<A::Output, Raw>,
+{
+    type Output = B::Output;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        self.count = self.count.saturating_add(1);
+        self.second.next(self.first.next(input))
+    }
+}
+
+impl<A, B, T, U> UpdateLast<T, U> for Chain<A, B>
+where
+    A: UpdateLast<T, U>,
+    B: UpdateLast<A::Output, Raw>,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        if self.count == 0 {
+            return self.next(input);
+        }
+        self.second.update_last(self.first.update_last(input))
+    }
+}
+
+impl<A, B, T, U> Peek<T, U> for Chain<A, B>
+where
+    A: Peek<T, U>,
+    B: Peek<A::Output, Raw>,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        self.second.peek(self.first.peek(input))
+    }
+}
+
+impl<A: Reset, B: Reset> Reset for Chain<A, B> {
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.count = 0;
+    }
+}
+
+impl<A: Warmup, B: Warmup> Warmup for Chain<A, B> {
+    fn warmup_period(&self) -> usize {
+        // The first ready output of A is the first input of B to count
+        (self.first.warmup_period() + self.second.warmup_period()).saturating_sub(1)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for Chain<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}∘{}", self.second, self.first)
+    }
+}
+
+/// Indicator mapping the outputs of `N` with a function.
+///
+/// It is created by [NextExt::project](trait.NextExt.html#method.project).
+#[derive(Debug, Clone, Default)]
+pub struct Project<N, F> {
+    indicator: N,
+    projection: F,
+}
+
+impl<N, F> Project<N, F> {
+    /// Returns the wrapped indicator.
+    pub fn indicator(&self) -> &N {
+        &self.indicator
+    }
+}
+
+impl<N, F, O, T, U> Next<T, U> for Project<N, F>
+where
+    N: Next<T, U>,
+    F: Fn(N::Output) -> O,
+{
+    type Output = O;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        (self.projection)(self.indicator.next(input))
+    }
+}
+
+impl<N, F, O, T, U> UpdateLast<T, U> for Project<N, F>
+where
+    N: UpdateLast<T, U>,
+    F: Fn(N::Output) -> O,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        (self.projection)(self.indicator.update_last(input))
+    }
+}
+
+impl<N, F, O, T, U> Peek<T, U> for Project<N, F>
+where
+    N: Peek<T, U>,
+    F: Fn(N::Output) -> O,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        (self.projection)(self.indicator.peek(input))
+    }
+}
+
+impl<N: Reset, F> Reset for Project<N, F> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+impl<N: Warmup, F> Warmup for Project<N, F> {
+    fn warmup_period(&self) -> usize {
+        self.indicator.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.indicator.is_ready()
+    }
+}
+
+impl<N: fmt::Display, F> fmt::Display for Project<N, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.indicator.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        BollingerBands, BollingerBandsOutput, ExponentialMovingAverage, MacdOutput, Maximum,
+        MovingAverageConvergenceDivergence, OnBalanceVolume, RelativeStrengthIndex,
+        SimpleMovingAverage,
+    };
+    use crate::test_helper::*;
+    use crate::Compute;
+
+    #[test]
+    fn test_next() {
+        let values = [10.0, 10.5, 10.0, 9.5, 11.0, 12.5];
+        let mut rsi = RelativeStrengthIndex::<f64>::new(3).unwrap();
+        let mut ema = ExponentialMovingAverage::<f64>::new(2).unwrap();
+        let expected: Vec<f64> = values.iter().map(|&v| ema.next(rsi.next(v))).collect();
+
+        rsi.reset();
+        ema.reset();
+        let mut chain = rsi.then(ema);
+        assert_eq!(chain.compute(&values), expected);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut sma_of_obv = OnBalanceVolume::new().then(SimpleMovingAverage::new(2).unwrap());
+        let bar = |close, volume| Bar::new().close(close).volume(volume);
+
+        assert_eq!(sma_of_obv.next(&bar(1.0, 100.0)), 100.0);
+        assert_eq!(sma_of_obv.next(&bar(2.0, 200.0)), 200.0);
+        assert_eq!(sma_of_obv.next(&bar(1.0, 50.0)), 275.0);
+    }
+
+    #[test]
+    fn test_project() {
+        let mut bb = BollingerBands::new(2, 2.0_f64).unwrap();
+        let mut max = Maximum::new(3).unwrap();
+        let values = [1.0, 3.0, 2.0, 7.0];
+        let expected: Vec<f64> = values.iter().map(|&v| max.next(bb.next(v).upper)).collect();
+
+        bb.reset();
+        max.reset();
+        let mut chain = bb
+            .project(|bands: BollingerBandsOutput<f64>| bands.upper)
+            .then(max);
+        assert_eq!(chain.compute(&values), expected);
+
+        let mut histogram = MovingAverageConvergenceDivergence::new(3, 6, 4)
+            .unwrap()
+            .project(|out: MacdOutput<f64>| out.histogram);
+        assert_eq!(histogram.next(2.0), 0.0);
+    }
+
+    #[test]
+    fn test_update_last_and_peek() {
+        let mut chain = RelativeStrengthIndex::<f64>::new(3)
+            .unwrap()
+            .then(ExponentialMovingAverage::new(2).unwrap());
+        let mut expected = chain.clone();
+
+        for &v in &[10.0, 10.5, 10.0, 9.5, 11.0] {
+            let output = expected.next(v);
+            assert_eq!(chain.peek(v), output);
+            chain.next(v + 1.0);
+            assert_eq!(chain.update_last(v), output);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut chain = RelativeStrengthIndex::<f64>::new(3)
+            .unwrap()
+            .then(ExponentialMovingAverage::new(2).unwrap());
+        let first = chain.next(10.0);
+        chain.next(12.0);
+
+        chain.reset();
+        assert_eq!(chain.next(10.0), first);
+    }
+
+    #[test]
+    fn test_warmup() {
+        let mut chain = SimpleMovingAverage::<f64>::new(3)
+            .unwrap()
+            .then(Maximum::new(2).unwrap());
+        assert_eq!(chain.warmup_period(), 4);
+
+        for _ in 0..3 {
+            chain.next(1.0);
+            assert!(!chain.is_ready());
+        }
+        chain.next(1.0);
+        assert!(chain.is_ready());
+    }
+
+    #[test]
+    fn test_default() {
+        Chain::<RelativeStrengthIndex<f64>, ExponentialMovingAverage<f64>>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let chain = RelativeStrengthIndex::<f64>::new(14)
+            .unwrap()
+            .then(ExponentialMovingAverage::<f64>::new(9).unwrap());
+        assert_eq!(format!("{}", chain), "EMA(9)∘RSI(14)");
+
+        let chain = chain.then(Maximum::<f64>::new(3).unwrap());
+        assert_eq!(format!("{}", chain), "MAX(3)∘EMA(9)∘RSI(14)");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::errors::*;
-use crate::traits::{Close, High, Low, Open, Volume};
+use crate::helpers::{max3, min3};
+use crate::traits::{Close, High, Low, MaybeMissing, Open, Timestamp, Volume};
 
 use num_traits::identities::Zero;
 
@@ -9,7 +10,7 @@ use num_traits::identities::Zero;
 ///
 /// ```
 /// use ta::DataItem;
-/// use ta::{Open, High, Low, Close, Volume};
+/// use ta::{Open, High, Low, Close, Volume, Timestamp};
 ///
 /// let item = DataItem::builder()
 ///     .open(20.0)
@@ -25,15 +26,19 @@ use num_traits::identities::Zero;
 /// assert_eq!(item.low(), 15.0);
 /// assert_eq!(item.close(), 21.0);
 /// assert_eq!(item.volume(), 7500.0);
+/// assert_eq!(item.timestamp(), None);
 /// ```
 ///
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DataItem<T> {
-    open: T,
-    high: T,
-    low: T,
-    close: T,
-    volume: T,
+    pub(crate) open: T,
+    pub(crate) high: T,
+    pub(crate) low: T,
+    pub(crate) close: T,
+    pub(crate) volume: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) timestamp: Option<i64>,
 }
 
 impl<T: PartialOrd + Zero> DataItem<T> {
@@ -72,12 +77,47 @@ impl<T: Copy> Volume<T> for DataItem<T> {
     }
 }
 
+impl<T> Timestamp for DataItem<T> {
+    fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+}
+
+impl<T: MaybeMissing> MaybeMissing for DataItem<T> {
+    fn is_missing(&self) -> bool {
+        self.open.is_missing()
+            || self.high.is_missing()
+            || self.low.is_missing()
+            || self.close.is_missing()
+            || self.volume.is_missing()
+    }
+}
+
+/// How [DataItemBuilder](struct.DataItemBuilder.html) validates a data item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValidationPolicy {
+    /// Consistent OHLC, non-negative prices and volume.
+    #[default]
+    Strict,
+    /// Consistent OHLC and non-negative volume, prices may be negative, e.g. for spreads.
+    AllowNegative,
+    /// Consistent OHLC only.
+    OhlcOnly,
+    /// No validation at all.
+    Unchecked,
+    /// Extends high and low to cover open and close, then validates as `AllowNegative`.
+    Repair,
+}
+
 pub struct DataItemBuilder<T> {
     open: Option<T>,
     high: Option<T>,
     low: Option<T>,
     close: Option<T>,
     volume: Option<T>,
+    timestamp: Option<i64>,
+    policy: ValidationPolicy,
 }
 
 impl<T: PartialOrd + Zero> DataItemBuilder<T> {
@@ -88,6 +128,8 @@ impl<T: PartialOrd + Zero> DataItemBuilder<T> {
             low: None,
             close: None,
             volume: None,
+            timestamp: None,
+            policy: ValidationPolicy::Strict,
         }
     }
 
@@ -116,36 +158,69 @@ impl<T: PartialOrd + Zero> DataItemBuilder<T> {
         self
     }
 
-    pub fn build(self) -> Result<DataItem<T>> {
-        if let (Some(open), Some(high), Some(low), Some(close), Some(volume)) =
-            (self.open, self.high, self.low, self.close, self.volume)
+    /// Sets the start time of the period, in milliseconds since the Unix epoch.
+    pub fn timestamp(mut self, val: i64) -> Self {
+        self.timestamp = Some(val);
+        self
+    }
+
+    /// Sets how the data item is validated, `ValidationPolicy::Strict` by default.
+    pub fn validation(mut self, policy: ValidationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn build(self) -> Result<DataItem<T>>
+    where
+        T: Clone,
+    {
+        let open = required(self.open, "open")?;
+        let mut high = required(self.high, "high")?;
+        let mut low = required(self.low, "low")?;
+        let close = required(self.close, "close")?;
+        let volume = required(self.volume, "volume")?;
+
+        if self.policy == ValidationPolicy::Repair {
+            high = max3(high, open.clone(), close.clone());
+            low = min3(low, open.clone(), close.clone());
+        }
+
+        // validate
+        let ohlc = self.policy != ValidationPolicy::Unchecked;
+        let volume_policy = ohlc && self.policy != ValidationPolicy::OhlcOnly;
+        let prices_policy = self.policy == ValidationPolicy::Strict;
+        let invariants = [
+            (ohlc, low <= open, "low <= open"),
+            (ohlc, low <= close, "low <= close"),
+            (ohlc, low <= high, "low <= high"),
+            (ohlc, high >= open, "high >= open"),
+            (ohlc, high >= close, "high >= close"),
+            (volume_policy, volume >= Zero::zero(), "volume >= 0"),
+            (prices_policy, low >= Zero::zero(), "low >= 0"),
+        ];
+        if let Some(&(_, _, invariant)) = invariants
+            .iter()
+            .find(|&&(checked, holds, _)| checked && !holds)
         {
-            // validate
-            if low <= open
-                && low <= close
-                && low <= high
-                && high >= open
-                && high >= close
-                && volume >= Zero::zero()
-                && low >= Zero::zero()
-            {
-                let item = DataItem {
-                    open,
-                    high,
-                    low,
-                    close,
-                    volume,
-                };
-                Ok(item)
-            } else {
-                Err(Error::from_kind(ErrorKind::DataItemInvalid))
-            }
-        } else {
-            Err(Error::from_kind(ErrorKind::DataItemIncomplete))
+            return Err(Error::DataItemInvalid { invariant });
         }
+
+        let item = DataItem {
+            open,
+            high,
+            low,
+            close,
+            volume,
+            timestamp: self.timestamp,
+        };
+        Ok(item)
     }
 }
 
+fn required<T>(value: Option<T>, field: &'static str) -> Result<T> {
+    value.ok_or(Error::DataItemIncomplete { field })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,4 +276,152 @@ mod tests {
             assert_invalid(record)
         }
     }
+
+    #[test]
+    fn test_build_errors() {
+        let result = DataItem::builder()
+            .open(20.0)
+            .high(25.0)
+            .low(15.0)
+            .volume(7500.0)
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            Error::DataItemIncomplete { field: "close" }
+        );
+
+        let result = DataItem::builder()
+            .open(14.9)
+            .high(25.0)
+            .low(15.0)
+            .close(21.0)
+            .volume(7500.0)
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            Error::DataItemInvalid {
+                invariant: "low <= open"
+            }
+        );
+
+        let result = DataItem::builder()
+            .open(20.0)
+            .high(25.0)
+            .low(15.0)
+            .close(21.0)
+            .volume(-1.0)
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            Error::DataItemInvalid {
+                invariant: "volume >= 0"
+            }
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        fn build(
+            policy: ValidationPolicy,
+            (open, high, low, close, volume): (f64, f64, f64, f64, f64),
+        ) -> Result<DataItem<f64>> {
+            DataItem::builder()
+                .open(open)
+                .high(high)
+                .low(low)
+                .close(close)
+                .volume(volume)
+                .validation(policy)
+                .build()
+        }
+        fn invariant(result: Result<DataItem<f64>>) -> &'static str {
+            match result {
+                Err(Error::DataItemInvalid { invariant }) => invariant,
+                _ => panic!("expected an invalid data item"),
+            }
+        }
+        use ValidationPolicy::*;
+
+        // open, high, low , close, volume
+        let negative = (-2.0, -1.0, -3.0, -1.5, 100.0);
+        let inconsistent = (20.0, 19.0, 15.0, 21.0, 7500.0);
+        let no_volume = (20.0, 25.0, 15.0, 21.0, -1.0);
+
+        assert_eq!(invariant(build(Strict, negative)), "low >= 0");
+        assert_eq!(invariant(build(Strict, inconsistent)), "high >= open");
+        assert_eq!(invariant(build(Strict, no_volume)), "volume >= 0");
+
+        assert!(build(AllowNegative, negative).is_ok());
+        assert_eq!(
+            invariant(build(AllowNegative, inconsistent)),
+            "high >= open"
+        );
+        assert_eq!(invariant(build(AllowNegative, no_volume)), "volume >= 0");
+
+        assert!(build(OhlcOnly, negative).is_ok());
+        assert_eq!(invariant(build(OhlcOnly, inconsistent)), "high >= open");
+        assert!(build(OhlcOnly, no_volume).is_ok());
+
+        assert!(build(Unchecked, negative).is_ok());
+        assert!(build(Unchecked, inconsistent).is_ok());
+        assert!(build(Unchecked, no_volume).is_ok());
+
+        assert!(build(Repair, negative).is_ok());
+        assert_eq!(invariant(build(Repair, no_volume)), "volume >= 0");
+        let item = build(Repair, (20.0, 19.0, 21.5, 21.0, 7500.0)).unwrap();
+        assert_eq!((item.high(), item.low()), (21.0, 20.0));
+        let item = build(Repair, (20.0, 25.0, 15.0, 21.0, 7500.0)).unwrap();
+        assert_eq!((item.high(), item.low()), (25.0, 15.0));
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let builder = DataItem::builder()
+            .open(20.0)
+            .high(25.0)
+            .low(15.0)
+            .close(21.0)
+            .volume(7500.0);
+        assert_eq!(builder.build().unwrap().timestamp(), None);
+
+        let builder = DataItem::builder()
+            .open(20.0)
+            .high(25.0)
+            .low(15.0)
+            .close(21.0)
+            .volume(7500.0)
+            .timestamp(1_577_836_800_000);
+        assert_eq!(
+            builder.build().unwrap().timestamp(),
+            Some(1_577_836_800_000)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let item = DataItem::builder()
+            .open(20.1)
+            .high(25.3)
+            .low(15.7)
+            .close(21.9)
+            .volume(7500.0)
+            .timestamp(1_577_836_800_000)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&item).unwrap();
+        let restored: DataItem<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.open(), item.open());
+        assert_eq!(restored.high(), item.high());
+        assert_eq!(restored.low(), item.low());
+        assert_eq!(restored.close(), item.close());
+        assert_eq!(restored.volume(), item.volume());
+        assert_eq!(restored.timestamp(), item.timestamp());
+
+        // snapshots without a timestamp are still accepted
+        let json = r#"{"open":1.0,"high":2.0,"low":1.0,"close":2.0,"volume":5.0}"#;
+        let restored: DataItem<f64> = serde_json::from_str(json).unwrap();
+        assert_eq!(restored.timestamp(), None);
+    }
 }
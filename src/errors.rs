@@ -1,7 +1,134 @@
-error_chain! {
-    errors {
-        InvalidParameter { description("invalid parameter") }
-        DataItemIncomplete { description("data item is incomplete") }
-        DataItemInvalid { description("data item is invalid") }
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::result;
+#[cfg(feature = "std")]
+use std::error;
+
+pub type Result<T> = result::Result<T, Error>;
+
+/// Errors returned by the library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A parameter passed to the constructor of an indicator is out of range.
+    InvalidParameter {
+        /// Name of the indicator, as displayed, e.g. `SMA`
+        indicator: &'static str,
+        /// Name of the parameter, e.g. `length`
+        name: &'static str,
+        /// The rejected value
+        value: String,
+        /// The constraint the value breaks, e.g. `greater than 0`
+        constraint: &'static str,
+    },
+    /// A field of a data item was not set.
+    DataItemIncomplete {
+        /// Name of the missing field, e.g. `close`
+        field: &'static str,
+    },
+    /// A data item breaks an invariant.
+    DataItemInvalid {
+        /// The broken invariant, e.g. `low <= open`
+        invariant: &'static str,
+    },
+    /// An indicator spec can't be parsed.
+    InvalidSpec {
+        /// The rejected spec, e.g. `SMA(0)`
+        spec: String,
+    },
+    /// A number can't be parsed, or is out of range.
+    InvalidNumber {
+        /// The rejected number, e.g. `1.2.3`
+        value: String,
+    },
+    /// An input carries a missing value, e.g. a `NaN` price.
+    MissingValue,
+}
+
+impl Error {
+    pub(crate) fn invalid_parameter<V: fmt::Display>(
+        indicator: &'static str,
+        name: &'static str,
+        value: V,
+        constraint: &'static str,
+    ) -> Self {
+        Error::InvalidParameter {
+            indicator,
+            name,
+            value: value.to_string(),
+            constraint,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidParameter {
+                indicator,
+                name,
+                value,
+                constraint,
+            } => write!(
+                f,
+                "invalid parameter of {}: {} is {}, but must be {}",
+                indicator, name, value, constraint
+            ),
+            Error::DataItemIncomplete { field } => {
+                write!(f, "data item is incomplete: {} is missing", field)
+            }
+            Error::DataItemInvalid { invariant } => {
+                write!(f, "data item is invalid: {} does not hold", invariant)
+            }
+            Error::InvalidSpec { spec } => write!(f, "invalid indicator spec: '{}'", spec),
+            Error::InvalidNumber { value } => write!(f, "invalid number: '{}'", value),
+            Error::MissingValue => write!(f, "input is missing a value"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = Error::invalid_parameter("SMA", "n", 0, "greater than 0");
+        assert_eq!(
+            err.to_string(),
+            "invalid parameter of SMA: n is 0, but must be greater than 0"
+        );
+
+        let err = Error::DataItemIncomplete { field: "close" };
+        assert_eq!(err.to_string(), "data item is incomplete: close is missing");
+
+        let err = Error::DataItemInvalid {
+            invariant: "low <= open",
+        };
+        assert_eq!(
+            err.to_string(),
+            "data item is invalid: low <= open does not hold"
+        );
+
+        let err = Error::InvalidSpec {
+            spec: "SMA(".to_string(),
+        };
+        assert_eq!(err.to_string(), "invalid indicator spec: 'SMA('");
+
+        let err = Error::InvalidNumber {
+            value: "1.2.3".to_string(),
+        };
+        assert_eq!(err.to_string(), "invalid number: '1.2.3'");
+
+        assert_eq!(Error::MissingValue.to_string(), "input is missing a value");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_error_trait() {
+        fn check<E: error::Error + Send + Sync + 'static>(_: E) {}
+        check(Error::DataItemIncomplete { field: "open" });
     }
 }
@@ -0,0 +1,485 @@
+use alloc::string::ToString;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use core::str::FromStr;
+
+use num_traits::float::FloatCore;
+use num_traits::{FromPrimitive, Num, One, Signed, ToPrimitive, Zero};
+
+use crate::errors::*;
+use crate::{MaybeMissing, Sqrt};
+
+/// Fixed-point number, stored as an `i64` count of ticks of `10^-SCALE`.
+///
+/// It satisfies the bounds of the indicators, so they can run directly on the integer prices
+/// sent by exchanges: a price of `123.45` with a scale of 2 is the tick count `12345`.
+///
+/// # Rounding
+///
+/// Addition, subtraction, remainder and negation are exact. Every other operation whose result
+/// falls between two ticks is rounded to the nearest tick, halfway cases away from zero:
+///
+/// * multiplication and division, e.g. averages;
+/// * square roots;
+/// * conversions from floats, and parsing of numbers with more than `SCALE` decimals.
+///
+/// Conversions to integers truncate towards zero.
+///
+/// Pick a scale with more decimals than the prices to keep the precision of averages, as long
+/// as the intermediate values fit in an `i64`.
+///
+/// # Panics
+///
+/// Like integers, arithmetic panics on overflow and division by zero.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Fixed, Next};
+///
+/// type Price = Fixed<2>;
+///
+/// let mut sma = SimpleMovingAverage::<Price>::new(3).unwrap();
+/// sma.next(Price::from_ticks(1000));
+/// sma.next(Price::from_ticks(1001));
+///
+/// // 30.02 / 3 is rounded to the nearest tick
+/// let average = sma.next(Price::from_ticks(1001));
+/// assert_eq!(average.ticks(), 1001);
+/// assert_eq!(average.to_string(), "10.01");
+///
+/// let price: Price = "10.005".parse().unwrap();
+/// assert_eq!(price.ticks(), 1001);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fixed<const SCALE: u32>(i64);
+
+impl<const SCALE: u32> Fixed<SCALE> {
+    // Number of ticks in one
+    const FACTOR: i128 = 10_i128.pow(SCALE);
+
+    /// Creates a number from a count of ticks of `10^-SCALE`.
+    pub const fn from_ticks(ticks: i64) -> Self {
+        Fixed(ticks)
+    }
+
+    /// Returns the count of ticks of `10^-SCALE`.
+    pub const fn ticks(self) -> i64 {
+        self.0
+    }
+
+    fn from_wide(ticks: i128) -> Self {
+        Fixed(i64::try_from(ticks).expect("fixed-point overflow"))
+    }
+}
+
+// Divides, rounding halfway cases away from zero.
+fn div_round(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if 2 * remainder.abs() >= denominator.abs() {
+        quotient + numerator.signum() * denominator.signum()
+    } else {
+        quotient
+    }
+}
+
+impl<const SCALE: u32> Add for Fixed<SCALE> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Fixed(self.0.checked_add(other.0).expect("fixed-point overflow"))
+    }
+}
+
+impl<const SCALE: u32> Sub for Fixed<SCALE> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Fixed(self.0.checked_sub(other.0).expect("fixed-point overflow"))
+    }
+}
+
+impl<const SCALE: u32> Mul for Fixed<SCALE> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::from_wide(div_round(self.0 as i128 * other.0 as i128, Self::FACTOR))
+    }
+}
+
+impl<const SCALE: u32> Div for Fixed<SCALE> {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if other.0 == 0 {
+            panic!("attempt to divide by zero");
+        }
+        Self::from_wide(div_round(self.0 as i128 * Self::FACTOR, other.0 as i128))
+    }
+}
+
+impl<const SCALE: u32> Rem for Fixed<SCALE> {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self {
+        Fixed(self.0 % other.0)
+    }
+}
+
+impl<const SCALE: u32> Neg for Fixed<SCALE> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Fixed(self.0.checked_neg().expect("fixed-point overflow"))
+    }
+}
+
+impl<const SCALE: u32> AddAssign for Fixed<SCALE> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const SCALE: u32> SubAssign for Fixed<SCALE> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const SCALE: u32> MulAssign for Fixed<SCALE> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<const SCALE: u32> DivAssign for Fixed<SCALE> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl<const SCALE: u32> RemAssign for Fixed<SCALE> {
+    fn rem_assign(&mut self, other: Self) {
+        *self = *self % other;
+    }
+}
+
+impl<const SCALE: u32> Zero for Fixed<SCALE> {
+    fn zero() -> Self {
+        Fixed(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const SCALE: u32> One for Fixed<SCALE> {
+    fn one() -> Self {
+        Self::from_wide(Self::FACTOR)
+    }
+}
+
+impl<const SCALE: u32> Num for Fixed<SCALE> {
+    type FromStrRadixErr = Error;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self> {
+        if radix != 10 {
+            return Err(Error::InvalidNumber {
+                value: s.to_string(),
+            });
+        }
+        s.parse()
+    }
+}
+
+impl<const SCALE: u32> Signed for Fixed<SCALE> {
+    fn abs(&self) -> Self {
+        Fixed(self.0.checked_abs().expect("fixed-point overflow"))
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        if *self <= *other {
+            Self::zero()
+        } else {
+            *self - *other
+        }
+    }
+
+    fn signum(&self) -> Self {
+        match self.0.cmp(&0) {
+            Ordering::Greater => Self::one(),
+            Ordering::Equal => Self::zero(),
+            Ordering::Less => -Self::one(),
+        }
+    }
+
+    fn is_positive(&self) -> bool {
+        self.0 > 0
+    }
+
+    fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+}
+
+impl<const SCALE: u32> FromPrimitive for Fixed<SCALE> {
+    fn from_i64(n: i64) -> Option<Self> {
+        i64::try_from(n as i128 * Self::FACTOR).ok().map(Fixed)
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        i64::try_from(n as i128 * Self::FACTOR).ok().map(Fixed)
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        let ticks = FloatCore::round(n * Self::FACTOR as f64);
+        // i64::MAX as f64 is rounded up to 2^63, which is out of range
+        if ticks.is_finite() && ticks >= i64::MIN as f64 && ticks < i64::MAX as f64 {
+            Some(Fixed(ticks as i64))
+        } else {
+            None
+        }
+    }
+}
+
+impl<const SCALE: u32> ToPrimitive for Fixed<SCALE> {
+    fn to_i64(&self) -> Option<i64> {
+        Some((self.0 as i128 / Self::FACTOR) as i64)
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        u64::try_from(self.0 as i128 / Self::FACTOR).ok()
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.0 as f64 / Self::FACTOR as f64)
+    }
+}
+
+impl<const SCALE: u32> MaybeMissing for Fixed<SCALE> {
+    fn is_missing(&self) -> bool {
+        false
+    }
+}
+
+impl<const SCALE: u32> Sqrt for Fixed<SCALE> {
+    fn sqrt(self) -> Self {
+        // Only negative numbers have no square root
+        if self.0 <= 0 {
+            return Self::zero();
+        }
+        let scaled = self.0 as i128 * Self::FACTOR;
+        let root = scaled.isqrt();
+        // (root + 1/2)^2 = root^2 + root + 1/4
+        if scaled - root * root > root {
+            Self::from_wide(root + 1)
+        } else {
+            Self::from_wide(root)
+        }
+    }
+}
+
+impl<const SCALE: u32> fmt::Display for Fixed<SCALE> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let ticks = self.0.unsigned_abs() as u128;
+        let factor = Self::FACTOR as u128;
+        if SCALE == 0 {
+            write!(f, "{}{}", sign, ticks)
+        } else {
+            write!(
+                f,
+                "{}{}.{:0width$}",
+                sign,
+                ticks / factor,
+                ticks % factor,
+                width = SCALE as usize
+            )
+        }
+    }
+}
+
+impl<const SCALE: u32> FromStr for Fixed<SCALE> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidNumber {
+            value: s.to_string(),
+        };
+        let trimmed = s.trim();
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(invalid());
+        }
+
+        let mut mantissa: i128 = 0;
+        for c in integer.chars().chain(fraction.chars()) {
+            let digit = c.to_digit(10).ok_or_else(invalid)?;
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add(digit as i128))
+                .ok_or_else(invalid)?;
+        }
+        if negative {
+            mantissa = -mantissa;
+        }
+
+        let decimals = fraction.len() as u32;
+        let ticks = if decimals <= SCALE {
+            10_i128
+                .checked_pow(SCALE - decimals)
+                .and_then(|factor| mantissa.checked_mul(factor))
+        } else {
+            10_i128
+                .checked_pow(decimals - SCALE)
+                .map(|factor| div_round(mantissa, factor))
+        };
+        ticks
+            .and_then(|ticks| i64::try_from(ticks).ok())
+            .map(Fixed)
+            .ok_or_else(invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Price = Fixed<2>;
+
+    fn price(s: &str) -> Price {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_ticks() {
+        assert_eq!(Price::from_ticks(12345).ticks(), 12345);
+        assert_eq!(Price::one().ticks(), 100);
+        assert_eq!(Fixed::<0>::one().ticks(), 1);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(price("1.25") + price("2.5"), price("3.75"));
+        assert_eq!(price("1.25") - price("2.5"), price("-1.25"));
+        assert_eq!(price("1.5") * price("2.5"), price("3.75"));
+        assert_eq!(price("7.5") / price("2.5"), price("3"));
+        assert_eq!(price("7.5") % price("2"), price("1.5"));
+        assert_eq!(-price("7.5"), price("-7.5"));
+        assert_eq!(price("-7.5").abs(), price("7.5"));
+        assert_eq!(price("-7.5").signum(), -Price::one());
+    }
+
+    #[test]
+    fn test_rounding() {
+        // 0.15 * 0.5 = 0.075
+        assert_eq!(price("0.15") * price("0.5"), price("0.08"));
+        assert_eq!(price("-0.15") * price("0.5"), price("-0.08"));
+        // 0.14 * 0.5 = 0.07
+        assert_eq!(price("0.14") * price("0.5"), price("0.07"));
+
+        // 1 / 3 = 0.333...
+        assert_eq!(price("1") / price("3"), price("0.33"));
+        // 2 / 3 = 0.666...
+        assert_eq!(price("2") / price("3"), price("0.67"));
+        assert_eq!(price("-2") / price("3"), price("-0.67"));
+        assert_eq!(price("2") / price("-3"), price("-0.67"));
+        // 0.01 / 2 = 0.005
+        assert_eq!(price("0.01") / price("2"), price("0.01"));
+
+        assert_eq!(
+            Fixed::<0>::from_ticks(5) / Fixed::from_ticks(2),
+            Fixed::from_ticks(3)
+        );
+        assert_eq!(
+            Fixed::<0>::from_ticks(-5) / Fixed::from_ticks(2),
+            Fixed::from_ticks(-3)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "divide by zero")]
+    fn test_division_by_zero() {
+        let _ = price("1") / Price::zero();
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn test_overflow() {
+        let _ = Price::from_ticks(i64::MAX) + Price::from_ticks(1);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(price("2.25").sqrt(), price("1.5"));
+        // sqrt(2) = 1.414...
+        assert_eq!(price("2").sqrt(), price("1.41"));
+        // sqrt(0.03) = 0.1732...
+        assert_eq!(price("0.03").sqrt(), price("0.17"));
+        // sqrt(3) = 1.7320...
+        assert_eq!(price("3").sqrt(), price("1.73"));
+        assert_eq!(price("0").sqrt(), price("0"));
+        assert_eq!(price("-4").sqrt(), price("0"));
+    }
+
+    #[test]
+    fn test_from_primitive() {
+        assert_eq!(Price::from_u32(100), Some(price("100")));
+        assert_eq!(Price::from_i64(-3), Some(price("-3")));
+        assert_eq!(Price::from_i64(i64::MAX), None);
+        assert_eq!(Price::from_f64(0.125), Some(price("0.13")));
+        assert_eq!(Price::from_f64(-0.125), Some(price("-0.13")));
+        assert_eq!(Price::from_f64(f64::NAN), None);
+        assert_eq!(Price::from_f64(1e30), None);
+    }
+
+    #[test]
+    fn test_to_primitive() {
+        assert_eq!(price("12.99").to_i64(), Some(12));
+        assert_eq!(price("-12.99").to_i64(), Some(-12));
+        assert_eq!(price("-12.99").to_u64(), None);
+        assert_eq!(price("12.5").to_f64(), Some(12.5));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(price("12.5").to_string(), "12.50");
+        assert_eq!(price("-0.05").to_string(), "-0.05");
+        assert_eq!(price("0").to_string(), "0.00");
+        assert_eq!(Fixed::<0>::from_ticks(-42).to_string(), "-42");
+        assert_eq!(Fixed::<3>::from_ticks(1).to_string(), "0.001");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(price("12").ticks(), 1200);
+        assert_eq!(price("+12.3").ticks(), 1230);
+        assert_eq!(price(".5").ticks(), 50);
+        assert_eq!(price("5.").ticks(), 500);
+        assert_eq!(price("0.125").ticks(), 13);
+        assert_eq!(price("-0.125").ticks(), -13);
+        assert_eq!(price("0.1249").ticks(), 12);
+
+        for s in &["", ".", "-", "1.2.3", "abc", "1e5", "99999999999999999999"] {
+            assert_eq!(
+                s.parse::<Price>(),
+                Err(Error::InvalidNumber {
+                    value: s.to_string()
+                })
+            );
+        }
+        assert_eq!(Price::from_str_radix("12", 10), Ok(price("12")));
+        assert!(Price::from_str_radix("12", 16).is_err());
+    }
+}
@@ -1,3 +1,9 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use crate::errors::*;
+
 /// Returns the largest of 3 given numbers.
 pub fn max3<T>(a: T, b: T, c: T) -> T
 where
@@ -6,6 +12,14 @@ where
     max(max(a, b), c)
 }
 
+/// Returns the smallest of 3 given numbers.
+pub fn min3<T>(a: T, b: T, c: T) -> T
+where
+    T: PartialOrd,
+{
+    min(min(a, b), c)
+}
+
 fn max<T>(refer: T, other: T) -> T
 where
     T: PartialOrd,
@@ -17,6 +31,59 @@ where
     }
 }
 
+fn min<T>(refer: T, other: T) -> T
+where
+    T: PartialOrd,
+{
+    if refer < other {
+        refer
+    } else {
+        other
+    }
+}
+
+/// Splits an indicator spec such as `BB(20, 2)` into its name and parameters.
+///
+/// The parentheses may be omitted when there are no parameters, e.g. `OBV`.
+pub fn split_spec(spec: &str) -> Result<(&str, Vec<&str>)> {
+    let spec = spec.trim();
+    let (name, params) = match spec.find('(') {
+        Some(open) if spec.ends_with(')') => (&spec[..open], &spec[open + 1..spec.len() - 1]),
+        None => (spec, ""),
+        _ => return Err(invalid_spec(spec)),
+    };
+    let name = name.trim();
+    if name.is_empty() || params.contains(['(', ')']) {
+        return Err(invalid_spec(spec));
+    }
+
+    let params = if params.trim().is_empty() {
+        Vec::new()
+    } else {
+        params.split(',').map(str::trim).collect()
+    };
+    Ok((name, params))
+}
+
+/// Parses the parameters of the spec of indicator `name`, expecting exactly `count`.
+pub fn parse_params<'a>(spec: &'a str, name: &str, count: usize) -> Result<Vec<&'a str>> {
+    match split_spec(spec)? {
+        (spec_name, params) if spec_name == name && params.len() == count => Ok(params),
+        _ => Err(invalid_spec(spec)),
+    }
+}
+
+/// Parses a single parameter of `spec`.
+pub fn parse_param<P: FromStr>(spec: &str, param: &str) -> Result<P> {
+    param.parse().map_err(|_| invalid_spec(spec))
+}
+
+fn invalid_spec(spec: &str) -> Error {
+    Error::InvalidSpec {
+        spec: spec.trim().to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -27,4 +94,40 @@ mod tests {
         assert_eq!(max3::<f64>(2.0, 3.0, 1.0), 3.0);
         assert_eq!(max3::<f64>(2.0, 1.0, 3.0), 3.0);
     }
+
+    #[test]
+    fn test_min3() {
+        assert_eq!(min3::<f64>(3.0, 2.0, 1.0), 1.0);
+        assert_eq!(min3::<f64>(2.0, 1.0, 3.0), 1.0);
+        assert_eq!(min3::<f64>(1.0, 3.0, 2.0), 1.0);
+    }
+
+    #[test]
+    fn test_split_spec() {
+        assert_eq!(split_spec("SMA(9)").unwrap(), ("SMA", vec!["9"]));
+        assert_eq!(
+            split_spec(" BB(20, 2.5) ").unwrap(),
+            ("BB", vec!["20", "2.5"])
+        );
+        assert_eq!(split_spec("HA()").unwrap(), ("HA", vec![]));
+        assert_eq!(split_spec("OBV").unwrap(), ("OBV", vec![]));
+
+        assert!(split_spec("").is_err());
+        assert!(split_spec("(9)").is_err());
+        assert!(split_spec("SMA(9").is_err());
+        assert!(split_spec("SMA(9))").is_err());
+    }
+
+    #[test]
+    fn test_parse_params() {
+        assert_eq!(
+            parse_params("MACD(3, 6, 4)", "MACD", 3).unwrap(),
+            vec!["3", "6", "4"]
+        );
+        assert!(parse_params("MACD(3, 6)", "MACD", 3).is_err());
+        assert!(parse_params("SMA(3)", "EMA", 1).is_err());
+
+        assert_eq!(parse_param::<u32>("SMA(3)", "3").unwrap(), 3);
+        assert!(parse_param::<u32>("SMA(-3)", "-3").is_err());
+    }
 }
@@ -0,0 +1,451 @@
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use core::fmt;
+use core::ops::{AddAssign, SubAssign};
+use core::str::FromStr;
+
+use num_traits::{cast::FromPrimitive, Signed};
+
+use crate::errors::*;
+use crate::helpers::split_spec;
+use crate::indicators::*;
+use crate::{
+    Close, High, Low, Next, Open, Peek, Reset, Sqrt, Timestamp, UpdateLast, Volume, Warmup,
+};
+
+/// Any indicator, picked at runtime from its spec.
+///
+/// The spec of an indicator is its `Display` output, e.g. `EMA(9)` or `BB(20, 2)`,
+/// so indicators can be listed as text in a configuration and parsed back.
+///
+/// Since some indicators need the full bar, `AnyIndicator` is fed with bars only.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{AnyIndicator, AnyOutput};
+/// use ta::{DataItem, Next};
+///
+/// let mut indicators: Vec<AnyIndicator<f64>> = ["SMA(2)", "BB(2, 2)", "OBV"]
+///     .iter()
+///     .map(|spec| spec.parse().unwrap())
+///     .collect();
+/// assert_eq!(format!("{}", indicators[1]), "BB(2, 2)");
+///
+/// let bar = DataItem::builder()
+///     .open(10.0)
+///     .high(11.0)
+///     .low(9.0)
+///     .close(10.0)
+///     .volume(500.0)
+///     .build()
+///     .unwrap();
+/// assert_eq!(indicators[0].next(&bar), AnyOutput::Value(10.0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnyIndicator<T> {
+    AverageTrueRange(AverageTrueRange<T>),
+    BollingerBands(BollingerBands<T>),
+    EfficiencyRatio(EfficiencyRatio<T>),
+    ExponentialMovingAverage(ExponentialMovingAverage<T>),
+    FastStochastic(FastStochastic<T>),
+    HeikinAshi(HeikinAshi<T>),
+    Ichimoku(Box<Ichimoku<T>>),
+    IchimokuSignals(Box<IchimokuSignals<T>>),
+    Maximum(Maximum<T>),
+    MaximumAge(MaximumAge<T>),
+    Minimum(Minimum<T>),
+    MinimumAge(MinimumAge<T>),
+    MoneyFlowIndex(MoneyFlowIndex<T>),
+    MovingAverageConvergenceDivergence(MovingAverageConvergenceDivergence<T>),
+    OnBalanceVolume(OnBalanceVolume<T>),
+    RateOfChange(RateOfChange<T>),
+    RelativeStrengthIndex(RelativeStrengthIndex<T>),
+    RelativeStrengthIndexSmma(RelativeStrengthIndexSmma<T>),
+    SimpleMovingAverage(SimpleMovingAverage<T>),
+    SlowStochastic(SlowStochastic<T>),
+    SmoothedOrModifiedMovingAverage(SmoothedOrModifiedMovingAverage<T>),
+    StandardDeviation(StandardDeviation<T>),
+    TrueRange(TrueRange<T>),
+    VolumeWeightedAveragePrice(VolumeWeightedAveragePrice<T>),
+}
+
+/// Output of an [AnyIndicator](enum.AnyIndicator.html).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnyOutput<T> {
+    Value(T),
+    Macd(MacdOutput<T>),
+    BollingerBands(BollingerBandsOutput<T>),
+    HeikinAshi(HeikinAshiCandle<T>),
+    Ichimoku(IchimokuOutput<T>),
+    IchimokuSignals(IchimokuSignalsOutput<T>),
+    Extreme(Extreme<T>),
+}
+
+impl<T: Copy> AnyOutput<T> {
+    /// Returns the value of single valued indicators.
+    pub fn value(&self) -> Option<T> {
+        match *self {
+            AnyOutput::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+// Evaluates `$body` with `$ind` bound to the wrapped indicator
+macro_rules! each_indicator {
+    ($self:expr, $ind:ident => $body:expr) => {
+        match $self {
+            AnyIndicator::AverageTrueRange($ind) => $body,
+            AnyIndicator::BollingerBands($ind) => $body,
+            AnyIndicator::EfficiencyRatio($ind) => $body,
+            AnyIndicator::ExponentialMovingAverage($ind) => $body,
+            AnyIndicator::FastStochastic($ind) => $body,
+            AnyIndicator::HeikinAshi($ind) => $body,
+            AnyIndicator::Ichimoku($ind) => $body,
+            AnyIndicator::IchimokuSignals($ind) => $body,
+            AnyIndicator::Maximum($ind) => $body,
+            AnyIndicator::MaximumAge($ind) => $body,
+            AnyIndicator::Minimum($ind) => $body,
+            AnyIndicator::MinimumAge($ind) => $body,
+            AnyIndicator::MoneyFlowIndex($ind) => $body,
+            AnyIndicator::MovingAverageConvergenceDivergence($ind) => $body,
+            AnyIndicator::OnBalanceVolume($ind) => $body,
+            AnyIndicator::RateOfChange($ind) => $body,
+            AnyIndicator::RelativeStrengthIndex($ind) => $body,
+            AnyIndicator::RelativeStrengthIndexSmma($ind) => $body,
+            AnyIndicator::SimpleMovingAverage($ind) => $body,
+            AnyIndicator::SlowStochastic($ind) => $body,
+            AnyIndicator::SmoothedOrModifiedMovingAverage($ind) => $body,
+            AnyIndicator::StandardDeviation($ind) => $body,
+            AnyIndicator::TrueRange($ind) => $body,
+            AnyIndicator::VolumeWeightedAveragePrice($ind) => $body,
+        }
+    };
+}
+
+// Evaluates `$body` with `$ind` bound to the wrapped indicator, and wraps the result in
+// the matching `AnyOutput`
+macro_rules! each_output {
+    ($self:expr, $ind:ident => $body:expr) => {
+        match $self {
+            AnyIndicator::BollingerBands($ind) => AnyOutput::BollingerBands($body),
+            AnyIndicator::HeikinAshi($ind) => AnyOutput::HeikinAshi($body),
+            AnyIndicator::Ichimoku($ind) => AnyOutput::Ichimoku($body),
+            AnyIndicator::IchimokuSignals($ind) => AnyOutput::IchimokuSignals($body),
+            AnyIndicator::MaximumAge($ind) => AnyOutput::Extreme($body),
+            AnyIndicator::MinimumAge($ind) => AnyOutput::Extreme($body),
+            AnyIndicator::MovingAverageConvergenceDivergence($ind) => AnyOutput::Macd($body),
+            AnyIndicator::AverageTrueRange($ind) => AnyOutput::Value($body),
+            AnyIndicator::EfficiencyRatio($ind) => AnyOutput::Value($body),
+            AnyIndicator::ExponentialMovingAverage($ind) => AnyOutput::Value($body),
+            AnyIndicator::FastStochastic($ind) => AnyOutput::Value($body),
+            AnyIndicator::Maximum($ind) => AnyOutput::Value($body),
+            AnyIndicator::Minimum($ind) => AnyOutput::Value($body),
+            AnyIndicator::MoneyFlowIndex($ind) => AnyOutput::Value($body),
+            AnyIndicator::OnBalanceVolume($ind) => AnyOutput::Value($body),
+            AnyIndicator::RateOfChange($ind) => AnyOutput::Value($body),
+            AnyIndicator::RelativeStrengthIndex($ind) => AnyOutput::Value($body),
+            AnyIndicator::RelativeStrengthIndexSmma($ind) => AnyOutput::Value($body),
+            AnyIndicator::SimpleMovingAverage($ind) => AnyOutput::Value($body),
+            AnyIndicator::SlowStochastic($ind) => AnyOutput::Value($body),
+            AnyIndicator::SmoothedOrModifiedMovingAverage($ind) => AnyOutput::Value($body),
+            AnyIndicator::StandardDeviation($ind) => AnyOutput::Value($body),
+            AnyIndicator::TrueRange($ind) => AnyOutput::Value($body),
+            AnyIndicator::VolumeWeightedAveragePrice($ind) => AnyOutput::Value($body),
+        }
+    };
+}
+
+impl<'a, U, T> Next<&'a U, T> for AnyIndicator<T>
+where
+    U: Open<T> + High<T> + Low<T> + Close<T> + Volume<T> + Timestamp,
+    T: Copy + Signed + FromPrimitive + AddAssign + SubAssign + PartialOrd + Sqrt,
+{
+    type Output = AnyOutput<T>;
+
+    fn next(&mut self, input: &'a U) -> Self::Output {
+        each_output!(self, ind => ind.next(input))
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for AnyIndicator<T>
+where
+    U: Open<T> + High<T> + Low<T> + Close<T> + Volume<T> + Timestamp,
+    T: Copy + Signed + FromPrimitive + AddAssign + SubAssign + PartialOrd + Sqrt,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        each_output!(self, ind => ind.update_last(input))
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for AnyIndicator<T>
+where
+    U: Open<T> + High<T> + Low<T> + Close<T> + Volume<T> + Timestamp,
+    T: Copy + Signed + FromPrimitive + AddAssign + SubAssign + PartialOrd + Sqrt,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        each_output!(self, ind => ind.peek(input))
+    }
+}
+
+impl<T> Reset for AnyIndicator<T>
+where
+    T: Copy + Signed + FromPrimitive + AddAssign + SubAssign + PartialOrd + Sqrt,
+{
+    fn reset(&mut self) {
+        each_indicator!(self, ind => ind.reset())
+    }
+}
+
+impl<T> Warmup for AnyIndicator<T> {
+    fn warmup_period(&self) -> usize {
+        each_indicator!(self, ind => ind.warmup_period())
+    }
+
+    fn is_ready(&self) -> bool {
+        each_indicator!(self, ind => ind.is_ready())
+    }
+}
+
+impl<T> FromStr for AnyIndicator<T>
+where
+    T: Copy + Signed + FromPrimitive + PartialOrd + FromStr + fmt::Display,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let indicator = match split_spec(s)?.0 {
+            "ATR" => AnyIndicator::AverageTrueRange(s.parse()?),
+            "BB" => AnyIndicator::BollingerBands(s.parse()?),
+            "ER" => AnyIndicator::EfficiencyRatio(s.parse()?),
+            "EMA" => AnyIndicator::ExponentialMovingAverage(s.parse()?),
+            "FAST_STOCH" => AnyIndicator::FastStochastic(s.parse()?),
+            "HA" => AnyIndicator::HeikinAshi(s.parse()?),
+            "ICHIMOKU" => AnyIndicator::Ichimoku(Box::new(s.parse()?)),
+            "ICHIMOKU_SIGNALS" => AnyIndicator::IchimokuSignals(Box::new(s.parse()?)),
+            "MAX" => AnyIndicator::Maximum(s.parse()?),
+            "MAX_AGE" => AnyIndicator::MaximumAge(s.parse()?),
+            "MIN" => AnyIndicator::Minimum(s.parse()?),
+            "MIN_AGE" => AnyIndicator::MinimumAge(s.parse()?),
+            "MFI" => AnyIndicator::MoneyFlowIndex(s.parse()?),
+            "MACD" => AnyIndicator::MovingAverageConvergenceDivergence(s.parse()?),
+            "OBV" => AnyIndicator::OnBalanceVolume(s.parse()?),
+            "ROC" => AnyIndicator::RateOfChange(s.parse()?),
+            "RSI" => AnyIndicator::RelativeStrengthIndex(s.parse()?),
+            "RSI_SMMA" => AnyIndicator::RelativeStrengthIndexSmma(s.parse()?),
+            "SMA" => AnyIndicator::SimpleMovingAverage(s.parse()?),
+            "SLOW_STOCH" => AnyIndicator::SlowStochastic(s.parse()?),
+            "SMMA" => AnyIndicator::SmoothedOrModifiedMovingAverage(s.parse()?),
+            "SD" => AnyIndicator::StandardDeviation(s.parse()?),
+            "TRUE_RANGE" => AnyIndicator::TrueRange(s.parse()?),
+            "VWAP" => AnyIndicator::VolumeWeightedAveragePrice(s.parse()?),
+            _ => {
+                return Err(Error::InvalidSpec {
+                    spec: s.trim().to_string(),
+                })
+            }
+        };
+        Ok(indicator)
+    }
+}
+
+impl<T> fmt::Display for AnyIndicator<T>
+where
+    T: Copy + Signed + FromPrimitive + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        each_indicator!(self, ind => ind.fmt(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    const SPECS: [&str; 24] = [
+        "ATR(14)",
+        "BB(20, 2.5)",
+        "ER(10)",
+        "EMA(9)",
+        "FAST_STOCH(14)",
+        "HA()",
+        "ICHIMOKU(9, 26, 52)",
+        "ICHIMOKU_SIGNALS(9, 26, 52)",
+        "MAX(3)",
+        "MAX_AGE(3)",
+        "MIN(3)",
+        "MIN_AGE(3)",
+        "MFI(14)",
+        "MACD(12, 26, 9)",
+        "OBV",
+        "ROC(9)",
+        "RSI(14)",
+        "RSI_SMMA(14)",
+        "SMA(9)",
+        "SLOW_STOCH(14, 3)",
+        "SMMA(9)",
+        "SD(20)",
+        "TRUE_RANGE()",
+        "VWAP(3600000)",
+    ];
+
+    fn bars() -> Vec<Bar> {
+        (0..30)
+            .map(|i| {
+                let close = 10.0 + ((i * 7) % 11) as f64 * 0.75;
+                Bar::new()
+                    .open(close - 0.5)
+                    .high(close + 1.0)
+                    .low(close - 1.0)
+                    .close(close)
+                    .volume(100.0 + i as f64)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_missing_values() {
+        use crate::{MissingPolicy, MissingValues};
+
+        let missing = Bar::new().high(1).low(0).close(f64::NAN);
+
+        for spec in SPECS.iter() {
+            let mut expected: AnyIndicator<f64> = spec.parse().unwrap();
+            let mut held: AnyIndicator<f64> = spec.parse().unwrap();
+            let mut skip = MissingValues::new(
+                spec.parse::<AnyIndicator<f64>>().unwrap(),
+                MissingPolicy::Skip,
+            );
+            let mut hold = MissingValues::new(
+                spec.parse::<AnyIndicator<f64>>().unwrap(),
+                MissingPolicy::HoldLast,
+            );
+
+            for (i, bar) in bars().iter().enumerate() {
+                let output = expected.next(bar);
+                assert_eq!(skip.next(bar), Some(output), "{}", spec);
+                assert_eq!(hold.next(bar), Some(held.next(bar)), "{}", spec);
+
+                // a skipped bar is never seen, a held one is seen twice
+                if i % 4 == 1 {
+                    assert_eq!(skip.next(&missing), None, "{}", spec);
+                    assert_eq!(hold.next(&missing), Some(held.next(bar)), "{}", spec);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_decimal() {
+        use crate::DataItem;
+        use rust_decimal::Decimal;
+
+        let bars: Vec<_> = bars()
+            .iter()
+            .map(|bar| {
+                let decimal = |v: f64| Decimal::try_from(v).unwrap();
+                DataItem::builder()
+                    .open(decimal(bar.open()))
+                    .high(decimal(bar.high()))
+                    .low(decimal(bar.low()))
+                    .close(decimal(bar.close()))
+                    .volume(decimal(bar.volume()))
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+
+        for spec in SPECS.iter() {
+            let mut any: AnyIndicator<Decimal> = spec.parse().unwrap();
+            assert_eq!(&format!("{}", any), spec);
+            for bar in &bars {
+                let expected = any.peek(bar);
+                assert_eq!(any.next(bar), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        for spec in SPECS.iter() {
+            let indicator: AnyIndicator<f64> = spec.parse().unwrap();
+            assert_eq!(&format!("{}", indicator), spec);
+        }
+
+        assert!("".parse::<AnyIndicator<f64>>().is_err());
+        assert!("FOO(3)".parse::<AnyIndicator<f64>>().is_err());
+        assert!("SMA(0)".parse::<AnyIndicator<f64>>().is_err());
+        assert!("SMA(3, 4)".parse::<AnyIndicator<f64>>().is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let bars = bars();
+
+        let mut any: AnyIndicator<f64> = "BB(3, 2)".parse().unwrap();
+        let mut bb = BollingerBands::new(3, 2.0).unwrap();
+        for bar in &bars {
+            assert_eq!(any.next(bar), AnyOutput::BollingerBands(bb.next(bar)));
+        }
+
+        let mut any: AnyIndicator<f64> = "MACD(3, 6, 4)".parse().unwrap();
+        let mut macd = MovingAverageConvergenceDivergence::new(3, 6, 4).unwrap();
+        for bar in &bars {
+            assert_eq!(any.next(bar), AnyOutput::Macd(macd.next(bar)));
+        }
+
+        let mut any: AnyIndicator<f64> = "ICHIMOKU_SIGNALS(2, 4, 8)".parse().unwrap();
+        let mut signals = IchimokuSignals::new(2, 4, 8).unwrap();
+        for bar in &bars {
+            assert_eq!(any.next(bar), AnyOutput::IchimokuSignals(signals.next(bar)));
+        }
+
+        let mut any: AnyIndicator<f64> = "MIN_AGE(4)".parse().unwrap();
+        let mut min = MinimumAge::new(4).unwrap();
+        for bar in &bars {
+            assert_eq!(any.next(bar), AnyOutput::Extreme(min.next(bar)));
+        }
+
+        let mut any: AnyIndicator<f64> = "MFI(3)".parse().unwrap();
+        let mut mfi = MoneyFlowIndex::new(3).unwrap();
+        for bar in &bars {
+            assert_eq!(any.next(bar).value(), Some(mfi.next(bar)));
+        }
+    }
+
+    #[test]
+    fn test_update_last_and_peek() {
+        for spec in SPECS.iter() {
+            let mut any: AnyIndicator<f64> = spec.parse().unwrap();
+            let mut expected = any.clone();
+
+            for bar in &bars() {
+                let output = expected.next(bar);
+                assert_eq!(any.peek(bar), output);
+                any.next(&Bar::new().high(20).low(5).close(12).volume(1.0));
+                assert_eq!(any.update_last(bar), output);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reset_and_warmup() {
+        let mut any: AnyIndicator<f64> = "SMA(3)".parse().unwrap();
+        assert_eq!(any.warmup_period(), 3);
+
+        for bar in bars().iter().take(3) {
+            assert!(!any.is_ready());
+            any.next(bar);
+        }
+        assert!(any.is_ready());
+
+        any.reset();
+        assert!(!any.is_ready());
+        assert_eq!(any.next(&Bar::new().close(4)), AnyOutput::Value(4.0));
+    }
+}
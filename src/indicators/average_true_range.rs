@@ -1,11 +1,13 @@
-use std::fmt;
-use std::ops::{Add, Div, Mul, Sub};
+use core::fmt;
+use core::ops::{Add, Div, Mul, Sub};
+use core::str::FromStr;
 
 use num_traits::{cast::FromPrimitive, One, Signed, Zero};
 
 use crate::errors::*;
+use crate::helpers::{parse_param, parse_params};
 use crate::indicators::{ExponentialMovingAverage, TrueRange};
-use crate::{Close, High, Low, Next, Reset};
+use crate::{Close, High, Low, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// Average true range (ATR).
 ///
@@ -57,9 +59,11 @@ use crate::{Close, High, Low, Next, Reset};
 ///     }
 /// }
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AverageTrueRange<T> {
     true_range: TrueRange<T>,
     ema: ExponentialMovingAverage<T>,
+    count: usize,
 }
 
 impl<T> AverageTrueRange<T>
@@ -67,15 +71,24 @@ where
     T: Zero + One + Div<Output = T> + FromPrimitive,
 {
     pub fn new(length: u32) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter(
+                "ATR",
+                "length",
+                length,
+                "greater than 0",
+            ));
+        }
         let indicator = Self {
             true_range: TrueRange::<T>::new(),
             ema: ExponentialMovingAverage::<T>::new(length)?,
+            count: 0,
         };
         Ok(indicator)
     }
 }
 
-impl<T> Next<T, !> for AverageTrueRange<T>
+impl<T> Next<T, Raw> for AverageTrueRange<T>
 where
     T: Copy
         + One
@@ -89,6 +102,7 @@ where
     type Output = T;
 
     fn next(&mut self, input: T) -> Self::Output {
+        self.count += 1;
         self.ema.next(self.true_range.next(input))
     }
 }
@@ -108,10 +122,79 @@ where
     type Output = T;
 
     fn next(&mut self, input: &'a U) -> Self::Output {
+        self.count += 1;
         self.ema.next(self.true_range.next(input))
     }
 }
 
+impl<T> UpdateLast<T, Raw> for AverageTrueRange<T>
+where
+    T: Copy
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + PartialOrd
+        + Zero
+        + Signed,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        self.count = self.count.max(1);
+        self.ema.update_last(self.true_range.update_last(input))
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for AverageTrueRange<T>
+where
+    U: High<T> + Low<T> + Close<T>,
+    T: Copy
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + PartialOrd
+        + Zero
+        + Signed,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.count = self.count.max(1);
+        self.ema.update_last(self.true_range.update_last(input))
+    }
+}
+
+impl<T> Peek<T, Raw> for AverageTrueRange<T>
+where
+    T: Copy
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + PartialOrd
+        + Zero
+        + Signed,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        self.ema.peek(self.true_range.peek(input))
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for AverageTrueRange<T>
+where
+    U: High<T> + Low<T> + Close<T>,
+    T: Copy
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + PartialOrd
+        + Zero
+        + Signed,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.ema.peek(self.true_range.peek(input))
+    }
+}
+
 impl<T> Reset for AverageTrueRange<T>
 where
     T: Zero,
@@ -119,6 +202,18 @@ where
     fn reset(&mut self) {
         self.true_range.reset();
         self.ema.reset();
+        self.count = 0;
+    }
+}
+
+impl<T> Warmup for AverageTrueRange<T> {
+    fn warmup_period(&self) -> usize {
+        self.true_range.warmup_period() + self.ema.warmup_period() - 1
+    }
+
+    fn is_ready(&self) -> bool {
+        // The first true range of raw values is not meaningful, yet it is averaged
+        self.count >= self.warmup_period()
     }
 }
 
@@ -140,6 +235,19 @@ where
     }
 }
 
+impl<T> FromStr for AverageTrueRange<T>
+where
+    T: Zero + One + Div<Output = T> + FromPrimitive,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "ATR", 1)?;
+        let length = parse_param(s, params[0])?;
+        Self::new(length)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,6 +273,29 @@ mod tests {
         assert_eq!(atr.next(&bar3), 3.375);
     }
 
+    #[test]
+    fn test_warmup() {
+        let mut atr = AverageTrueRange::<f64>::new(3).unwrap();
+        assert_eq!(atr.warmup_period(), 4);
+        for _ in 0..3 {
+            atr.next(10.0);
+        }
+        assert!(!atr.is_ready());
+        atr.next(10.0);
+        assert!(atr.is_ready());
+
+        atr.reset();
+        for _ in 0..3 {
+            atr.next(&Bar::new().high(10).low(7.5).close(9));
+        }
+        assert!(!atr.is_ready());
+        atr.update_last(&Bar::new().high(11).low(7.5).close(9));
+        assert!(!atr.is_ready());
+        atr.next(&Bar::new().high(10).low(7.5).close(9));
+        assert_eq!(atr.warmup_period(), 4);
+        assert!(atr.is_ready());
+    }
+
     #[test]
     fn test_reset() {
         let mut atr = AverageTrueRange::<f64>::new(9).unwrap();
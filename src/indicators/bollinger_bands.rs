@@ -1,11 +1,13 @@
-use std::fmt;
-use std::ops::{Add, AddAssign, Div, Mul, Sub};
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, Mul, Sub};
+use core::str::FromStr;
 
-use num_traits::{FromPrimitive, One, Zero};
+use num_traits::{FromPrimitive, Zero};
 
 use crate::errors::*;
-use crate::indicators::StandardDeviation as Sd;
-use crate::{Close, Next, Reset};
+use crate::helpers::{parse_param, parse_params};
+use crate::indicators::{DeviationType, StandardDeviation as Sd, UpdateMode};
+use crate::{Close, Next, Peek, Raw, Reset, Sqrt, UpdateLast, Warmup};
 
 /// A Bollinger Bands (BB).
 /// (BB).
@@ -22,6 +24,10 @@ use crate::{Close, Next, Reset};
 ///  * _BB<sub>Upper Band</sub>_ = SMA + SD of observation * multipler (usually 2.0)
 ///  * _BB<sub>Lower Band</sub>_ = SMA - SD of observation * multipler (usually 2.0)
 ///
+/// The standard deviation is the population one by default, divided by N; most charting
+/// platforms use the sample one, divided by N - 1, see
+/// [with_options](struct.BollingerBands.html#method.with_options).
+///
 /// # Example
 ///
 ///```
@@ -47,6 +53,7 @@ use crate::{Close, Next, Reset};
 ///
 /// ![Bollinger Bands, Wikipedia](https://en.wikipedia.org/wiki/Bollinger_Bands)
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BollingerBands<T> {
     length: u32,
     multiplier: T,
@@ -54,6 +61,7 @@ pub struct BollingerBands<T> {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BollingerBandsOutput<T> {
     pub average: T,
     pub upper: T,
@@ -62,16 +70,45 @@ pub struct BollingerBandsOutput<T> {
 
 impl<T> BollingerBands<T>
 where
-    T: Copy + Zero + PartialOrd,
+    T: Copy + Zero + PartialOrd + fmt::Display,
 {
     pub fn new(length: u32, multiplier: T) -> Result<Self> {
+        Self::with_options(
+            length,
+            multiplier,
+            DeviationType::Population,
+            UpdateMode::Incremental,
+        )
+    }
+
+    /// Creates Bollinger Bands whose standard deviation is of type `deviation_type`, and kept up
+    /// to date according to `update_mode`.
+    pub fn with_options(
+        length: u32,
+        multiplier: T,
+        deviation_type: DeviationType,
+        update_mode: UpdateMode,
+    ) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter(
+                "BB",
+                "length",
+                length,
+                "greater than 0",
+            ));
+        }
         if multiplier <= T::zero() {
-            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+            return Err(Error::invalid_parameter(
+                "BB",
+                "multiplier",
+                multiplier,
+                "greater than 0",
+            ));
         }
         Ok(Self {
             length,
             multiplier,
-            sd: Sd::new(length)?,
+            sd: Sd::with_options(length, deviation_type, update_mode)?,
         })
     }
 
@@ -82,20 +119,28 @@ where
     pub fn multiplier(&self) -> T {
         self.multiplier
     }
+
+    pub fn deviation_type(&self) -> DeviationType {
+        self.sd.deviation_type()
+    }
+
+    pub fn update_mode(&self) -> UpdateMode {
+        self.sd.update_mode()
+    }
 }
 
-impl<T> Next<T, !> for BollingerBands<T>
+impl<T> Next<T, Raw> for BollingerBands<T>
 where
     T: Copy
         + Zero
-        + One
         + FromPrimitive
         + Add<Output = T>
         + Div<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
         + AddAssign
-        + PartialEq,
+        + PartialOrd
+        + Sqrt,
 {
     type Output = BollingerBandsOutput<T>;
 
@@ -116,14 +161,14 @@ where
     U: Close<T>,
     T: Copy
         + Zero
-        + One
         + FromPrimitive
         + Add<Output = T>
         + Div<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
         + AddAssign
-        + PartialEq,
+        + PartialOrd
+        + Sqrt,
 {
     type Output = BollingerBandsOutput<T>;
 
@@ -132,6 +177,93 @@ where
     }
 }
 
+impl<T> UpdateLast<T, Raw> for BollingerBands<T>
+where
+    T: Copy
+        + Zero
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + AddAssign
+        + PartialOrd
+        + Sqrt,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        let sd = self.sd.update_last(input);
+        let mean = self.sd.mean();
+
+        Self::Output {
+            average: mean,
+            upper: mean + sd * self.multiplier,
+            lower: mean - sd * self.multiplier,
+        }
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for BollingerBands<T>
+where
+    U: Close<T>,
+    T: Copy
+        + Zero
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + AddAssign
+        + PartialOrd
+        + Sqrt,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.close())
+    }
+}
+
+impl<T> Peek<T, Raw> for BollingerBands<T>
+where
+    T: Copy
+        + Zero
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + AddAssign
+        + PartialOrd
+        + Sqrt,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        let (mean, sd) = self.sd.peek_with_mean(input);
+
+        Self::Output {
+            average: mean,
+            upper: mean + sd * self.multiplier,
+            lower: mean - sd * self.multiplier,
+        }
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for BollingerBands<T>
+where
+    U: Close<T>,
+    T: Copy
+        + Zero
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + AddAssign
+        + PartialOrd
+        + Sqrt,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.close())
+    }
+}
+
 impl<T> Reset for BollingerBands<T>
 where
     T: Zero,
@@ -141,9 +273,19 @@ where
     }
 }
 
+impl<T> Warmup for BollingerBands<T> {
+    fn warmup_period(&self) -> usize {
+        self.sd.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.sd.is_ready()
+    }
+}
+
 impl<T> Default for BollingerBands<T>
 where
-    T: Copy + Zero + PartialOrd + FromPrimitive,
+    T: Copy + Zero + PartialOrd + FromPrimitive + fmt::Display,
 {
     fn default() -> Self {
         Self::new(9, T::from_u32(2).expect("Woot ?")).unwrap()
@@ -155,7 +297,31 @@ where
     T: fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "BB({}, {})", self.length, self.multiplier)
+        write!(f, "BB({}, {}", self.length, self.multiplier)?;
+        // The defaults are implied
+        let (deviation_type, update_mode) = (self.sd.deviation_type(), self.sd.update_mode());
+        if deviation_type != DeviationType::Population || update_mode != UpdateMode::Incremental {
+            write!(f, ", {}, {}", deviation_type, update_mode)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<T> FromStr for BollingerBands<T>
+where
+    T: Copy + Zero + PartialOrd + FromStr + fmt::Display,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "BB", 2).or_else(|_| parse_params(s, "BB", 4))?;
+        let length = parse_param(s, params[0])?;
+        let multiplier = parse_param(s, params[1])?;
+        let (deviation_type, update_mode) = match params.len() {
+            4 => (parse_param(s, params[2])?, parse_param(s, params[3])?),
+            _ => (DeviationType::Population, UpdateMode::Incremental),
+        };
+        Self::with_options(length, multiplier, deviation_type, update_mode)
     }
 }
 
@@ -171,6 +337,16 @@ mod tests {
         assert!(BollingerBands::<f64>::new(0, 2_f64).is_err());
         assert!(BollingerBands::<f64>::new(1, 2_f64).is_ok());
         assert!(BollingerBands::<f64>::new(2, 2_f64).is_ok());
+
+        assert_eq!(
+            BollingerBands::<f64>::new(2, -0.5_f64).unwrap_err(),
+            Error::InvalidParameter {
+                indicator: "BB",
+                name: "multiplier",
+                value: "-0.5".to_string(),
+                constraint: "greater than 0",
+            }
+        );
     }
 
     #[test]
@@ -198,6 +374,29 @@ mod tests {
         assert_eq!(round(d.lower), -0.395);
     }
 
+    #[test]
+    fn test_next_sample() {
+        let mut bb = BollingerBands::<f64>::with_options(
+            3,
+            2.0_f64,
+            DeviationType::Sample,
+            UpdateMode::Incremental,
+        )
+        .unwrap();
+
+        let a = bb.next(2.0);
+        let b = bb.next(5.0);
+        let c = bb.next(1.0);
+
+        assert_eq!(a.upper, 2.0);
+        assert_eq!(round(b.average), 3.5);
+        assert_eq!(round(b.upper), 7.743);
+        assert_eq!(round(b.lower), -0.743);
+        assert_eq!(round(c.average), 2.667);
+        assert_eq!(round(c.upper), 6.830);
+        assert_eq!(round(c.lower), -1.497);
+    }
+
     #[test]
     fn test_reset() {
         let mut bb = BollingerBands::<f64>::new(5, 2.0_f64).unwrap();
@@ -234,5 +433,29 @@ mod tests {
     fn test_display() {
         let bb = BollingerBands::<f64>::new(10, 3.0_f64).unwrap();
         assert_eq!(format!("{}", bb), "BB(10, 3)");
+
+        let bb = BollingerBands::<f64>::with_options(
+            10,
+            3.0_f64,
+            DeviationType::Population,
+            UpdateMode::Stable,
+        )
+        .unwrap();
+        assert_eq!(format!("{}", bb), "BB(10, 3, POPULATION, STABLE)");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let bb: BollingerBands<f64> = "BB(20, 2.5)".parse().unwrap();
+        assert_eq!(format!("{}", bb), "BB(20, 2.5)");
+
+        let bb: BollingerBands<f64> = "BB(20, 2, SAMPLE, INCREMENTAL)".parse().unwrap();
+        assert_eq!(bb.deviation_type(), DeviationType::Sample);
+        assert_eq!(bb.update_mode(), UpdateMode::Incremental);
+        assert_eq!(format!("{}", bb), "BB(20, 2, SAMPLE, INCREMENTAL)");
+
+        assert!("BB(20)".parse::<BollingerBands<f64>>().is_err());
+        assert!("BB(20, 0)".parse::<BollingerBands<f64>>().is_err());
+        assert!("BB(20, x)".parse::<BollingerBands<f64>>().is_err());
     }
 }
@@ -1,11 +1,14 @@
-use std::collections::VecDeque;
-use std::fmt;
-use std::ops::Sub;
+use alloc::collections::VecDeque;
+use core::fmt;
+use core::iter;
+use core::ops::Sub;
+use core::str::FromStr;
 
 use num_traits::{One, Signed, Zero};
 
 use crate::errors::*;
-use crate::traits::{Close, Next, Reset};
+use crate::helpers::{parse_param, parse_params};
+use crate::traits::{Close, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// Kaufman's Efficiency Ratio (ER).
 ///
@@ -30,80 +33,156 @@ use crate::traits::{Close, Next, Reset};
 /// assert_eq!(er.next(18.0), 0.8);
 /// assert_eq!(er.next(19.0), 0.75);
 /// ```
-
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EfficiencyRatio<T> {
     length: u32,
     prices: VecDeque<T>,
+    count: usize,
 }
 
 impl<T> EfficiencyRatio<T> {
     pub fn new(length: u32) -> Result<Self> {
         if length == 0 {
-            Err(Error::from_kind(ErrorKind::InvalidParameter))
+            Err(Error::invalid_parameter(
+                "ER",
+                "length",
+                length,
+                "greater than 0",
+            ))
         } else {
             let indicator = Self {
-                length: length,
+                length,
                 prices: VecDeque::<T>::with_capacity(length as usize + 1),
+                count: 0,
             };
             Ok(indicator)
         }
     }
 }
 
-impl<T> Next<T, !> for EfficiencyRatio<T>
+impl<T> Next<T, Raw> for EfficiencyRatio<T>
 where
     T: Copy + Zero + One + Sub<Output = T> + Signed,
 {
     type Output = T;
 
     fn next(&mut self, input: T) -> T {
+        if self.count < self.warmup_period() {
+            self.count += 1;
+        }
+
+        // Get rid of the first element, the latest input is kept until the next one comes
+        if self.prices.len() > (self.length as usize).max(2) {
+            self.prices.pop_front();
+        }
+        let output = self.peek(input);
         self.prices.push_back(input);
+        output
+    }
+}
 
-        if self.prices.len() <= 2 {
+impl<'a, U, T> Next<&'a U, T> for EfficiencyRatio<T>
+where
+    U: Close<T>,
+    T: Copy + Zero + One + Sub<Output = T> + Signed,
+{
+    type Output = T;
+
+    fn next(&mut self, input: &'a U) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl<T> Peek<T, Raw> for EfficiencyRatio<T>
+where
+    T: Copy + Zero + One + Sub<Output = T> + Signed,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        // The first element is pushed out by the input once there are enough of them
+        let skip = if self.prices.len() > (self.length as usize).max(2) {
+            1
+        } else {
+            0
+        };
+        if self.prices.len() - skip < 2 {
             return T::one();
         }
 
-        let first = self.prices[0];
+        let first = self.prices[skip];
 
         // Calculate volatility
         let volatility = self
             .prices
             .iter()
-            .skip(1)
+            .skip(skip + 1)
+            .chain(iter::once(&input))
             .fold((first, T::zero()), |(prev, sum), &val| {
                 (val, sum + (prev - val).abs())
             })
             .1;
 
         // Calculate direction
-        let last_index = self.prices.len() - 1;
-        let direction = (first - self.prices[last_index]).abs();
+        let direction = (first - input).abs();
 
-        // Get rid of the first element
-        if self.prices.len() > (self.length as usize) {
-            self.prices.pop_front();
+        // Return actual efficiency ratio, a price that did not move is as efficient as it gets
+        if volatility.is_zero() {
+            return T::one();
         }
-
-        // Return actual efficiency ratio
         direction / volatility
     }
 }
 
-impl<'a, U, T> Next<&'a U, T> for EfficiencyRatio<T>
+impl<'a, U, T> Peek<&'a U, T> for EfficiencyRatio<T>
 where
     U: Close<T>,
     T: Copy + Zero + One + Sub<Output = T> + Signed,
 {
-    type Output = T;
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.close())
+    }
+}
 
-    fn next(&mut self, input: &'a U) -> Self::Output {
-        self.next(input.close())
+impl<T> UpdateLast<T, Raw> for EfficiencyRatio<T>
+where
+    T: Copy + Zero + One + Sub<Output = T> + Signed,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        if self.prices.pop_back().is_none() {
+            return self.next(input);
+        }
+        let output = self.peek(input);
+        self.prices.push_back(input);
+        output
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for EfficiencyRatio<T>
+where
+    U: Close<T>,
+    T: Copy + Zero + One + Sub<Output = T> + Signed,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.close())
     }
 }
 
 impl<T> Reset for EfficiencyRatio<T> {
     fn reset(&mut self) {
         self.prices.clear();
+        self.count = 0;
+    }
+}
+
+impl<T> Warmup for EfficiencyRatio<T> {
+    fn warmup_period(&self) -> usize {
+        // The first input only gives the price to compare with
+        self.length as usize + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count == self.warmup_period()
     }
 }
 
@@ -119,6 +198,16 @@ impl<T> fmt::Display for EfficiencyRatio<T> {
     }
 }
 
+impl<T> FromStr for EfficiencyRatio<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "ER", 1)?;
+        let length = parse_param(s, params[0])?;
+        Self::new(length)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,10 +1,12 @@
-use std::fmt;
-use std::ops::{Add, Div, Mul, Sub};
+use core::fmt;
+use core::ops::{Add, Div, Mul, Sub};
+use core::str::FromStr;
 
 use num_traits::{cast::FromPrimitive, One, Zero};
 
 use crate::errors::*;
-use crate::{Close, Next, Reset};
+use crate::helpers::{parse_param, parse_params};
+use crate::{Close, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// An exponential moving average (EMA), also known as an exponentially weighted moving average
 /// (EWMA).
@@ -54,11 +56,14 @@ use crate::{Close, Next, Reset};
 ///
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExponentialMovingAverage<T> {
     length: u32,
     k: T,
     current: T,
-    is_new: bool,
+    // value before the latest input, if any
+    previous: Option<T>,
+    count: u32,
 }
 
 impl<T> ExponentialMovingAverage<T>
@@ -67,7 +72,12 @@ where
 {
     pub fn new(length: u32) -> Result<Self> {
         match length {
-            0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
+            0 => Err(Error::invalid_parameter(
+                "EMA",
+                "length",
+                length,
+                "greater than 0",
+            )),
             _ => {
                 let k: T = T::from_u32(2).expect("Woot ?")
                     / (T::from_u32(length).expect("Woot ?") + T::one());
@@ -75,7 +85,8 @@ where
                     length,
                     k,
                     current: T::zero(),
-                    is_new: true,
+                    previous: None,
+                    count: 0,
                 };
                 Ok(indicator)
             }
@@ -89,20 +100,35 @@ impl<T> ExponentialMovingAverage<T> {
     }
 }
 
-impl<T> Next<T, !> for ExponentialMovingAverage<T>
+impl<T> ExponentialMovingAverage<T>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn average(&self, previous: Option<T>, input: T) -> T {
+        match previous {
+            None => input,
+            // EMA = (CLOSE (i) * P) + (EMA (i - 1) * (1 - P))
+            Some(previous) => (input * self.k) + (previous * (T::one() - self.k)),
+        }
+    }
+}
+
+impl<T> Next<T, Raw> for ExponentialMovingAverage<T>
 where
     T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
 {
     type Output = T;
 
     fn next(&mut self, input: T) -> Self::Output {
-        if self.is_new {
-            self.is_new = false;
-            self.current = input;
+        self.previous = if self.count == 0 {
+            None
         } else {
-            // EMA = (CLOSE (i) * P) + (EMA (i - 1) * (1 - P))
-            self.current = (input * self.k) + (self.current * (T::one() - self.k));
+            Some(self.current)
+        };
+        if self.count < self.length {
+            self.count += 1;
         }
+        self.current = self.average(self.previous, input);
         self.current
     }
 }
@@ -119,13 +145,71 @@ where
     }
 }
 
+impl<T> UpdateLast<T, Raw> for ExponentialMovingAverage<T>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        if self.count == 0 {
+            return self.next(input);
+        }
+        self.current = self.average(self.previous, input);
+        self.current
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for ExponentialMovingAverage<T>
+where
+    U: Close<T>,
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.close())
+    }
+}
+
+impl<T> Peek<T, Raw> for ExponentialMovingAverage<T>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        let previous = if self.count == 0 {
+            None
+        } else {
+            Some(self.current)
+        };
+        self.average(previous, input)
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for ExponentialMovingAverage<T>
+where
+    U: Close<T>,
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.close())
+    }
+}
+
 impl<T> Reset for ExponentialMovingAverage<T>
 where
     T: Zero,
 {
     fn reset(&mut self) {
         self.current = T::zero();
-        self.is_new = true;
+        self.previous = None;
+        self.count = 0;
+    }
+}
+
+impl<T> Warmup for ExponentialMovingAverage<T> {
+    fn warmup_period(&self) -> usize {
+        self.length as usize
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count == self.length
     }
 }
 
@@ -144,6 +228,19 @@ impl<T> fmt::Display for ExponentialMovingAverage<T> {
     }
 }
 
+impl<T> FromStr for ExponentialMovingAverage<T>
+where
+    T: Zero + One + Div<Output = T> + FromPrimitive,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "EMA", 1)?;
+        let length = parse_param(s, params[0])?;
+        Self::new(length)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +270,30 @@ mod tests {
         assert_eq!(ema.next(&bar2), 3.5);
     }
 
+    #[test]
+    fn test_next_fixed() {
+        use crate::{DataItem, Fixed};
+
+        // k = 2 / 3 is rounded to 0.67
+        let mut ema = ExponentialMovingAverage::<Fixed<2>>::new(2).unwrap();
+        assert_eq!(ema.next(Fixed::from_ticks(1000)), Fixed::from_ticks(1000));
+        // 11 * 0.67 + 10 * 0.33
+        assert_eq!(ema.next(Fixed::from_ticks(1100)), Fixed::from_ticks(1067));
+        // 12 * 0.67 + 10.67 * 0.33 = 8.04 + 3.5211, each product is rounded
+        assert_eq!(ema.next(Fixed::from_ticks(1200)), Fixed::from_ticks(1156));
+
+        let bar = DataItem::builder()
+            .open(Fixed::from_ticks(1300))
+            .high(Fixed::from_ticks(1300))
+            .low(Fixed::from_ticks(1300))
+            .close(Fixed::from_ticks(1300))
+            .volume(Fixed::from_ticks(0))
+            .build()
+            .unwrap();
+        // 13 * 0.67 + 11.56 * 0.33 = 8.71 + 3.8148
+        assert_eq!(ema.next(&bar), Fixed::from_ticks(1252));
+    }
+
     #[test]
     fn test_reset() {
         let mut ema = ExponentialMovingAverage::new(5).unwrap();
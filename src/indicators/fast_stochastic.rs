@@ -1,11 +1,13 @@
-use std::fmt;
-use std::ops::{Div, Mul, Sub};
+use core::fmt;
+use core::ops::{Div, Mul, Sub};
+use core::str::FromStr;
 
 use num_traits::cast::FromPrimitive;
 
 use crate::errors::*;
+use crate::helpers::{parse_param, parse_params};
 use crate::indicators::{Maximum, Minimum};
-use crate::{Close, High, Low, Next, Reset};
+use crate::{Close, High, Low, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// Fast stochastic oscillator.
 ///
@@ -42,6 +44,7 @@ use crate::{Close, High, Low, Next, Reset};
 /// assert_eq!(stoch.next(15.0), 0.0);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FastStochastic<T> {
     length: u32,
     minimum: Minimum<T>,
@@ -53,8 +56,16 @@ where
     T: Copy + PartialOrd,
 {
     pub fn new(length: u32) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter(
+                "FAST_STOCH",
+                "length",
+                length,
+                "greater than 0",
+            ));
+        }
         let indicator = Self {
-            length: length,
+            length,
             minimum: Minimum::<T>::new(length)?,
             maximum: Maximum::<T>::new(length)?,
         };
@@ -68,7 +79,22 @@ impl<T> FastStochastic<T> {
     }
 }
 
-impl<T> Next<T, !> for FastStochastic<T>
+impl<T> FastStochastic<T>
+where
+    T: Copy + PartialOrd + FromPrimitive + Div<Output = T> + Mul<Output = T> + Sub<Output = T>,
+{
+    fn stochastic(close: T, lowest: T, highest: T) -> T {
+        if highest == lowest {
+            // When only 1 input was given, than min and max are the same,
+            // therefore it makes sense to return 50. It also avoids division by zero.
+            T::from_u32(50).expect("constant fits in T")
+        } else {
+            (close - lowest) / (highest - lowest) * T::from_u32(100).expect("constant fits in T")
+        }
+    }
+}
+
+impl<T> Next<T, Raw> for FastStochastic<T>
 where
     T: Copy + PartialOrd + FromPrimitive + Div<Output = T> + Mul<Output = T> + Sub<Output = T>,
 {
@@ -77,14 +103,7 @@ where
     fn next(&mut self, input: T) -> Self::Output {
         let min = self.minimum.next(input);
         let max = self.maximum.next(input);
-
-        if min == max {
-            // When only 1 input was given, than min and max are the same,
-            // therefore it makes sense to return 50
-            T::from_u32(50).expect("Woot ?")
-        } else {
-            (input - min) / (max - min) * T::from_u32(100).expect("Woot ?")
-        }
+        Self::stochastic(input, min, max)
     }
 }
 
@@ -98,14 +117,53 @@ where
     fn next(&mut self, input: &'a U) -> Self::Output {
         let highest = self.maximum.next(input.high());
         let lowest = self.minimum.next(input.low());
-        let close = input.close();
+        Self::stochastic(input.close(), lowest, highest)
+    }
+}
 
-        if highest == lowest {
-            // To avoid division by zero, return 50.0
-            T::from_u32(50).expect("Woot ?")
-        } else {
-            (close - lowest) / (highest - lowest) * T::from_u32(100).expect("Woot ?")
-        }
+impl<T> UpdateLast<T, Raw> for FastStochastic<T>
+where
+    T: Copy + PartialOrd + FromPrimitive + Div<Output = T> + Mul<Output = T> + Sub<Output = T>,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        let min = self.minimum.update_last(input);
+        let max = self.maximum.update_last(input);
+        Self::stochastic(input, min, max)
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for FastStochastic<T>
+where
+    U: High<T> + Low<T> + Close<T>,
+    T: Copy + PartialOrd + FromPrimitive + Div<Output = T> + Mul<Output = T> + Sub<Output = T>,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        let highest = self.maximum.update_last(input.high());
+        let lowest = self.minimum.update_last(input.low());
+        Self::stochastic(input.close(), lowest, highest)
+    }
+}
+
+impl<T> Peek<T, Raw> for FastStochastic<T>
+where
+    T: Copy + PartialOrd + FromPrimitive + Div<Output = T> + Mul<Output = T> + Sub<Output = T>,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        let min = self.minimum.peek(input);
+        let max = self.maximum.peek(input);
+        Self::stochastic(input, min, max)
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for FastStochastic<T>
+where
+    U: High<T> + Low<T> + Close<T>,
+    T: Copy + PartialOrd + FromPrimitive + Div<Output = T> + Mul<Output = T> + Sub<Output = T>,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        let highest = self.maximum.peek(input.high());
+        let lowest = self.minimum.peek(input.low());
+        Self::stochastic(input.close(), lowest, highest)
     }
 }
 
@@ -116,6 +174,18 @@ impl<T> Reset for FastStochastic<T> {
     }
 }
 
+impl<T> Warmup for FastStochastic<T> {
+    fn warmup_period(&self) -> usize {
+        self.minimum
+            .warmup_period()
+            .max(self.maximum.warmup_period())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.minimum.is_ready() && self.maximum.is_ready()
+    }
+}
+
 impl<T> Default for FastStochastic<T>
 where
     T: Copy + PartialOrd,
@@ -131,6 +201,19 @@ impl<T> fmt::Display for FastStochastic<T> {
     }
 }
 
+impl<T> FromStr for FastStochastic<T>
+where
+    T: Copy + PartialOrd,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "FAST_STOCH", 1)?;
+        let length = parse_param(s, params[0])?;
+        Self::new(length)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,12 @@
 use num_traits::cast::FromPrimitive;
 
-use crate::{Close, High, Low, Next, Open, Reset};
+use crate::errors::*;
+use crate::helpers::parse_params;
+use crate::{Close, High, Low, Next, Open, Peek, Reset, UpdateLast, Warmup};
 
-use std::fmt;
-use std::ops::{Add, Div};
+use core::fmt;
+use core::ops::{Add, Div};
+use core::str::FromStr;
 
 /// Heikin-Ashi candlesticks.
 ///
@@ -40,11 +43,15 @@ use std::ops::{Add, Div};
 /// 10. Very narrow body of the Heikin-Ashi candlestick with tall and low shadow of large size (Probable upward trend reversal / Close short positions)
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HeikinAshi<T> {
     prev: Option<PreviousValues<T>>,
+    // Values before the latest input, restored by `update_last`
+    saved: Option<PreviousValues<T>>,
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HeikinAshiCandle<T> {
     pub open: T,
     pub close: T,
@@ -54,6 +61,7 @@ pub struct HeikinAshiCandle<T> {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HeikinAshiColor {
     Green,
     Red,
@@ -61,11 +69,15 @@ pub enum HeikinAshiColor {
 
 impl<T> HeikinAshi<T> {
     pub fn new() -> Self {
-        Self { prev: None }
+        Self {
+            prev: None,
+            saved: None,
+        }
     }
 }
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct PreviousValues<T> {
     open: T,
     close: T,
@@ -79,6 +91,22 @@ where
     type Output = HeikinAshiCandle<T>;
 
     fn next(&mut self, input: &'a U) -> Self::Output {
+        let candle = self.peek(input);
+        self.saved = self.prev;
+        self.prev = Some(PreviousValues {
+            open: candle.open,
+            close: candle.close,
+        });
+        candle
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for HeikinAshi<T>
+where
+    U: Open<T> + Close<T> + High<T> + Low<T>,
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
         let open = if let Some(prev) = self.prev {
             (prev.open + prev.close) / T::from_u32(2).unwrap()
         } else {
@@ -87,7 +115,6 @@ where
         };
         let close =
             (input.open() + input.close() + input.high() + input.low()) / T::from_u32(4).unwrap();
-        self.prev = Some(PreviousValues { open, close });
         use HeikinAshiColor::{Green, Red};
         Self::Output {
             open,
@@ -99,6 +126,19 @@ where
     }
 }
 
+impl<'a, U, T> UpdateLast<&'a U, T> for HeikinAshi<T>
+where
+    U: Open<T> + Close<T> + High<T> + Low<T>,
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        if self.prev.is_some() {
+            self.prev = self.saved;
+        }
+        self.next(input)
+    }
+}
+
 fn partial_max<T>(a: T, b: T) -> T
 where
     T: PartialOrd,
@@ -127,6 +167,16 @@ impl<T> Reset for HeikinAshi<T> {
     }
 }
 
+impl<T> Warmup for HeikinAshi<T> {
+    fn warmup_period(&self) -> usize {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.prev.is_some()
+    }
+}
+
 impl<T> Default for HeikinAshi<T> {
     fn default() -> Self {
         Self::new()
@@ -139,22 +189,20 @@ impl<T> fmt::Display for HeikinAshi<T> {
     }
 }
 
+impl<T> FromStr for HeikinAshi<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_params(s, "HA", 0)?;
+        Ok(Self::new())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test_helper::Bar;
-
-    impl<T> PartialEq for HeikinAshiCandle<T>
-    where
-        T: PartialEq,
-    {
-        fn eq(&self, other: &Self) -> bool {
-            self.open == other.open
-                && self.close == other.close
-                && self.high == other.high
-                && self.low == other.low
-        }
-    }
+    use crate::Compute;
 
     #[test]
     fn test_next() {
@@ -222,6 +270,131 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compute() {
+        let bars = vec![
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(1000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(2000.0),
+            Bar::new().open(8).high(9).low(7).close(8).volume(3000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(4000.0),
+            Bar::new().open(4).high(5).low(3).close(4.5).volume(5000.0),
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(6000.0),
+        ];
+
+        let mut ha = HeikinAshi::<f64>::new();
+        let expected: Vec<_> = bars.iter().map(|bar| ha.next(bar)).collect();
+
+        ha.reset();
+        assert_eq!(ha.compute(&bars), expected);
+    }
+
+    #[test]
+    fn test_update_last() {
+        let bars = [
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(1000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(2000.0),
+            Bar::new().open(8).high(9).low(7).close(8).volume(3000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(4000.0),
+            Bar::new().open(4).high(5).low(3).close(4.5).volume(5000.0),
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(6000.0),
+        ];
+
+        let mut ha = HeikinAshi::<f64>::new();
+        let expected: Vec<_> = bars.iter().map(|bar| ha.next(bar)).collect();
+
+        ha.reset();
+        for (i, bar) in bars.iter().enumerate() {
+            ha.next(&bars[(i + 3) % bars.len()]);
+            assert_eq!(ha.update_last(bar), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_peek() {
+        let bars = [
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(1000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(2000.0),
+            Bar::new().open(8).high(9).low(7).close(8).volume(3000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(4000.0),
+            Bar::new().open(4).high(5).low(3).close(4.5).volume(5000.0),
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(6000.0),
+        ];
+
+        let mut ha = HeikinAshi::<f64>::new();
+        let mut expected = HeikinAshi::<f64>::new();
+
+        for (i, bar) in bars.iter().enumerate() {
+            ha.peek(&bars[(i + 3) % bars.len()]);
+            let output = expected.next(bar);
+            assert_eq!(ha.peek(bar), output);
+            assert_eq!(ha.next(bar), output);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let bars = [
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(1000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(2000.0),
+            Bar::new().open(8).high(9).low(7).close(8).volume(3000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(4000.0),
+            Bar::new().open(4).high(5).low(3).close(4.5).volume(5000.0),
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(6000.0),
+        ];
+
+        let mut ha = HeikinAshi::<f64>::new();
+        for bar in &bars[..4] {
+            ha.next(bar);
+        }
+
+        let json = serde_json::to_string(&ha).unwrap();
+        let mut restored: HeikinAshi<f64> = serde_json::from_str(&json).unwrap();
+        for bar in &bars[4..] {
+            assert_eq!(restored.next(bar), ha.next(bar));
+        }
+    }
+
     #[test]
     fn test_default() {
         HeikinAshi::<f64>::default();
@@ -232,4 +405,12 @@ mod tests {
         let ha = HeikinAshi::<f64>::new();
         assert_eq!(format!("{}", ha), "HA()");
     }
+
+    #[test]
+    fn test_from_str() {
+        let ha: HeikinAshi<f64> = "HA()".parse().unwrap();
+        assert_eq!(format!("{}", ha), "HA()");
+
+        assert!("HA(1)".parse::<HeikinAshi<f64>>().is_err());
+    }
 }
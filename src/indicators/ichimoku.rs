@@ -1,11 +1,16 @@
-use crate::{Close, High, Low, Next, Reset};
+use alloc::collections::VecDeque;
+use core::fmt;
+use core::ops::{Add, Div};
+use core::str::FromStr;
 
 use num_traits::cast::FromPrimitive;
 
-use std::collections::VecDeque;
-use std::ops::{Add, Div};
+use crate::errors::*;
+use crate::helpers::{parse_param, parse_params};
+use crate::rolling::{Extremum, RollingExtremum};
+use crate::{Close, High, Low, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
-/// chimoku Kinko Hyo (IKH) (Japanese一目均衡表, Ichimoku Kinkō Hyō),
+/// Ichimoku Kinko Hyo (IKH) (Japanese一目均衡表, Ichimoku Kinkō Hyō),
 ///
 /// usually shortened to "Ichimoku", is a technical analysis method that builds
 /// on candlestick charting to improve the accuracy of forecast price moves.
@@ -51,589 +56,615 @@ use std::ops::{Add, Div};
 /// a signal of potential trend reversals.
 /// In addition to thickness, the strength of the cloud can also be ascertained by its angle;
 /// upwards for bullish and downwards for bearish. Any clouds behind price are also known as Kumo Shadows
-
-#[derive(Debug)]
+///
+/// # Parameters
+///
+/// * _tenkan_sen_length_ - number of periods of the Tenkan-sen (integer greater than 0). Default is 9.
+/// * _kijun_sen_length_ - number of periods of the Kijun-sen, greater than the Tenkan-sen one.
+///   It is also the number of periods the Senkou spans are plotted ahead, and the Chikou span
+///   back. Default is 26.
+/// * _senkou_span_b_length_ - number of periods of the Senkou span B, greater than the
+///   Kijun-sen one. Default is 52.
+///
+/// Every line of the [output](struct.IchimokuOutput.html) is `None` until enough periods are
+/// seen. The cloud of the current period is known after
+/// `senkou_span_b_length + kijun_sen_length` periods, which is the warm-up period.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{Ichimoku, KumoColor};
+/// use ta::Next;
+///
+/// let mut ichimoku = Ichimoku::new(2, 4, 8).unwrap();
+/// for _ in 0..7 {
+///     ichimoku.next(20.0);
+/// }
+///
+/// let output = ichimoku.next(28.0);
+/// assert_eq!(output.tenkan_sen, Some(24.0));
+/// assert_eq!(output.kijun_sen, Some(24.0));
+/// assert_eq!(output.kumo, None);
+///
+/// let future_kumo = output.future_kumo.unwrap();
+/// assert_eq!(future_kumo.senkou_span_a, 24.0);
+/// assert_eq!(future_kumo.color, KumoColor::Red);
+/// ```
+///
+/// # Links
+///
+/// * [Ichimoku Kinkō Hyō, Wikipedia](https://en.wikipedia.org/wiki/Ichimoku_Kink%C5%8D_Hy%C5%8D)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ichimoku<T> {
-    tenkan_sen_length: usize,    // 9
-    kijun_sen_length: usize,     // 26
-    senkou_span_b_length: usize, // 52
-    nb_elemts: usize,
-    // data: CircularQueue<IchimokuOutput<T>>,
-    pub data: VecDeque<IchimokuOutput<T>>,
+    tenkan_sen_length: u32,
+    kijun_sen_length: u32,
+    senkou_span_b_length: u32,
+    count: usize,
+    tenkan_sen: Range<T>,
+    kijun_sen: Range<T>,
+    senkou_span_b: Range<T>,
+    // Clouds projected by the last kijun_sen_length + 1 periods
+    kumos: VecDeque<Kumo<T>>,
+}
+
+// Highest high and lowest low of the last periods
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Range<T> {
+    highs: RollingExtremum<T>,
+    lows: RollingExtremum<T>,
+}
+
+impl<T> Range<T> {
+    fn new(length: u32) -> Self {
+        Self {
+            highs: RollingExtremum::new(Extremum::Max, length as usize),
+            lows: RollingExtremum::new(Extremum::Min, length as usize),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.highs.reset();
+        self.lows.reset();
+    }
+}
+
+impl<T> Range<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    // (highest high + lowest low) / 2, the latest period being given by `high` and `low`
+    fn midpoint(&self, high: T, low: T) -> T {
+        (self.highs.peek(high) + self.lows.peek(low)) / T::from_u32(2).expect("constant fits in T")
+    }
+
+    fn push(&mut self, high: T, low: T) {
+        self.highs.push(high);
+        self.lows.push(low);
+    }
+
+    fn pop(&mut self) {
+        self.highs.pop();
+        self.lows.pop();
+    }
 }
 
-#[derive(Debug, Copy, Clone, Default, PartialEq)]
+/// Lines of an [Ichimoku](struct.Ichimoku.html) for a period.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IchimokuOutput<T> {
-    pub close: Option<T>,
-    pub high: Option<T>,
-    pub low: Option<T>,
     pub tenkan_sen: Option<T>,
     pub kijun_sen: Option<T>,
-    pub senkou_span_a: Option<T>,
-    pub senkou_span_b: Option<T>,
+    /// Cloud of the current period, projected `kijun_sen_length` periods ago.
+    pub kumo: Option<Kumo<T>>,
+    /// Cloud projected by the current period, `kijun_sen_length` periods ahead.
+    pub future_kumo: Option<Kumo<T>>,
+    /// Close of the current period, plotted `kijun_sen_length` periods back.
     pub chikou_span: Option<T>,
-    pub kumo_color: Option<KumoColor>,
 }
 
+/// Cloud between the Senkou spans of an [Ichimoku](struct.Ichimoku.html).
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Kumo<T> {
+    pub senkou_span_a: T,
+    pub senkou_span_b: T,
+    pub color: KumoColor,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KumoColor {
+    /// Senkou span A above Senkou span B
     Green,
     Red,
 }
 
-impl<T> Ichimoku<T>
-where
-    T: Clone + Default,
-{
-    pub fn new(tenkan_sen_length: u32, kijun_sen_length: u32, senkou_span_b_length: u32) -> Self {
-        if tenkan_sen_length != 0
-            && tenkan_sen_length < kijun_sen_length
-            && kijun_sen_length < senkou_span_b_length
-        {
-            let mut data =
-                VecDeque::with_capacity((kijun_sen_length + senkou_span_b_length) as usize);
-            for _i in 0..(kijun_sen_length + senkou_span_b_length) {
-                data.push_back(IchimokuOutput::default());
-            }
-            Self {
-                tenkan_sen_length: tenkan_sen_length as usize,
-                kijun_sen_length: kijun_sen_length as usize,
-                senkou_span_b_length: senkou_span_b_length as usize,
-                nb_elemts: 0,
-                data,
-            }
+/// Position of a price relative to a [Kumo](struct.Kumo.html).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CloudPosition {
+    Above,
+    /// Between the Senkou spans, edges included
+    Inside,
+    Below,
+}
+
+impl<T: Copy + PartialOrd> Kumo<T> {
+    /// Returns the upper edge of the cloud.
+    pub fn top(&self) -> T {
+        if self.senkou_span_a > self.senkou_span_b {
+            self.senkou_span_a
+        } else {
+            self.senkou_span_b
+        }
+    }
+
+    /// Returns the lower edge of the cloud.
+    pub fn bottom(&self) -> T {
+        if self.senkou_span_a > self.senkou_span_b {
+            self.senkou_span_b
+        } else {
+            self.senkou_span_a
+        }
+    }
+
+    pub fn position(&self, price: T) -> CloudPosition {
+        if price > self.top() {
+            CloudPosition::Above
+        } else if price < self.bottom() {
+            CloudPosition::Below
         } else {
-            panic!("bad ichimoku parameters");
+            CloudPosition::Inside
         }
     }
 }
 
+impl<T: PartialOrd> Kumo<T> {
+    fn new(senkou_span_a: T, senkou_span_b: T) -> Self {
+        let color = if senkou_span_a > senkou_span_b {
+            KumoColor::Green
+        } else {
+            KumoColor::Red
+        };
+        Self {
+            senkou_span_a,
+            senkou_span_b,
+            color,
+        }
+    }
+}
+
+impl<T> Ichimoku<T> {
+    pub fn new(
+        tenkan_sen_length: u32,
+        kijun_sen_length: u32,
+        senkou_span_b_length: u32,
+    ) -> Result<Self> {
+        if tenkan_sen_length == 0 {
+            return Err(Error::invalid_parameter(
+                "ICHIMOKU",
+                "tenkan_sen_length",
+                tenkan_sen_length,
+                "greater than 0",
+            ));
+        }
+        if kijun_sen_length <= tenkan_sen_length {
+            return Err(Error::invalid_parameter(
+                "ICHIMOKU",
+                "kijun_sen_length",
+                kijun_sen_length,
+                "greater than tenkan_sen_length",
+            ));
+        }
+        if senkou_span_b_length <= kijun_sen_length {
+            return Err(Error::invalid_parameter(
+                "ICHIMOKU",
+                "senkou_span_b_length",
+                senkou_span_b_length,
+                "greater than kijun_sen_length",
+            ));
+        }
+        Ok(Self {
+            tenkan_sen_length,
+            kijun_sen_length,
+            senkou_span_b_length,
+            count: 0,
+            tenkan_sen: Range::new(tenkan_sen_length),
+            kijun_sen: Range::new(kijun_sen_length),
+            senkou_span_b: Range::new(senkou_span_b_length),
+            kumos: VecDeque::with_capacity(kijun_sen_length as usize + 1),
+        })
+    }
+
+    pub fn tenkan_sen_length(&self) -> u32 {
+        self.tenkan_sen_length
+    }
+
+    pub fn kijun_sen_length(&self) -> u32 {
+        self.kijun_sen_length
+    }
+
+    pub fn senkou_span_b_length(&self) -> u32 {
+        self.senkou_span_b_length
+    }
+}
+
 impl<T> Ichimoku<T>
 where
-    T: Clone + Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
 {
-    fn get_average(&self, offset: usize) -> T {
-        let mut high: Option<T> = None;
-        let mut low: Option<T> = None;
-        for i in (self.senkou_span_b_length - offset)..self.senkou_span_b_length {
-            if high.is_none() || self.data[i].high.unwrap() > high.unwrap() {
-                high = Some(self.data[i].high.unwrap());
+    // Lines of an input following the periods seen so far
+    fn lines(&self, high: T, low: T, close: T) -> IchimokuOutput<T> {
+        let count = self.count + 1;
+        let line = |range: &Range<T>, length: u32| {
+            if count >= length as usize {
+                Some(range.midpoint(high, low))
+            } else {
+                None
             }
-            if low.is_none() || self.data[i].low.unwrap() < low.unwrap() {
-                low = Some(self.data[i].low.unwrap());
+        };
+
+        let tenkan_sen = line(&self.tenkan_sen, self.tenkan_sen_length);
+        let kijun_sen = line(&self.kijun_sen, self.kijun_sen_length);
+        let senkou_span_b = line(&self.senkou_span_b, self.senkou_span_b_length);
+        let future_kumo = match (tenkan_sen, kijun_sen, senkou_span_b) {
+            (Some(tenkan_sen), Some(kijun_sen), Some(senkou_span_b)) => Some(Kumo::new(
+                (tenkan_sen + kijun_sen) / T::from_u32(2).expect("constant fits in T"),
+                senkou_span_b,
+            )),
+            _ => None,
+        };
+        // The last cloud is the one projected by the previous period
+        let kumo = self
+            .kumos
+            .len()
+            .checked_sub(self.kijun_sen_length as usize)
+            .and_then(|index| self.kumos.get(index))
+            .copied();
+        let chikou_span = if count > self.kijun_sen_length as usize {
+            Some(close)
+        } else {
+            None
+        };
+
+        IchimokuOutput {
+            tenkan_sen,
+            kijun_sen,
+            kumo,
+            future_kumo,
+            chikou_span,
+        }
+    }
+
+    fn push(&mut self, high: T, low: T, close: T) -> IchimokuOutput<T> {
+        let output = self.lines(high, low, close);
+        self.count += 1;
+
+        self.tenkan_sen.push(high, low);
+        self.kijun_sen.push(high, low);
+        self.senkou_span_b.push(high, low);
+        if let Some(kumo) = output.future_kumo {
+            self.kumos.push_back(kumo);
+            if self.kumos.len() > self.kijun_sen_length as usize + 1 {
+                self.kumos.pop_front();
             }
         }
-        (high.unwrap() + low.unwrap()) / T::from_u32(2).unwrap()
+        output
+    }
+
+    fn amend(&mut self, high: T, low: T, close: T) -> IchimokuOutput<T> {
+        if self.count == 0 {
+            return self.push(high, low, close);
+        }
+        // Forget the latest period, the kumo it dropped is not needed to draw it again
+        if self.count >= self.senkou_span_b_length as usize {
+            self.kumos.pop_back();
+        }
+        self.tenkan_sen.pop();
+        self.kijun_sen.pop();
+        self.senkou_span_b.pop();
+        self.count -= 1;
+        self.push(high, low, close)
+    }
+}
+
+impl<T> Next<T, Raw> for Ichimoku<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    type Output = IchimokuOutput<T>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        self.push(input, input, input)
     }
 }
 
 impl<'a, U, T> Next<&'a U, T> for Ichimoku<T>
 where
     U: Close<T> + High<T> + Low<T>,
-    T: Copy + Clone + Default + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
 {
-    type Output = ();
+    type Output = IchimokuOutput<T>;
 
     fn next(&mut self, input: &'a U) -> Self::Output {
-        self.nb_elemts += 1;
-        if self.nb_elemts > (self.senkou_span_b_length) {
-            drop(self.data.pop_front());
-            self.data.push_back(IchimokuOutput::default());
-        }
-        if self.nb_elemts < (self.senkou_span_b_length) {
-            let refer = &mut self.data[self.nb_elemts - 1];
-            refer.close = Some(input.close());
-            refer.high = Some(input.high());
-            refer.low = Some(input.low());
-        } else {
-            // Write common OHLC values
-            let refer = &mut self.data[self.senkou_span_b_length - 1];
-            refer.close = Some(input.close());
-            refer.high = Some(input.high());
-            refer.low = Some(input.low());
-
-            // Calc system
-            let tenkan = self.get_average(self.tenkan_sen_length);
-            let kijun = self.get_average(self.kijun_sen_length);
-            let senkou_span_a = (tenkan + kijun) / T::from_u32(2).unwrap();
-            let senkou_span_b = self.get_average(self.senkou_span_b_length);
-
-            // Draw tenkan sen & kijun sen
-            let refer = &mut self.data[self.senkou_span_b_length - 1];
-            refer.tenkan_sen = Some(tenkan);
-            refer.kijun_sen = Some(kijun);
-
-            // Draw chikou span (dated)
-            let refer = &mut self.data[self.senkou_span_b_length - self.kijun_sen_length - 1];
-            refer.chikou_span = Some(input.close());
-
-            // Draw senkou span a & b & colorize kumo (future)
-            let refer = &mut self.data[self.senkou_span_b_length + self.kijun_sen_length - 1];
-            refer.senkou_span_a = Some(senkou_span_a);
-            refer.senkou_span_b = Some(senkou_span_b);
-            refer.kumo_color = if senkou_span_a > senkou_span_b {
-                Some(KumoColor::Green)
-            } else {
-                Some(KumoColor::Red)
-            };
-        }
+        self.push(input.high(), input.low(), input.close())
+    }
+}
+
+impl<T> UpdateLast<T, Raw> for Ichimoku<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        self.amend(input, input, input)
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for Ichimoku<T>
+where
+    U: Close<T> + High<T> + Low<T>,
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.amend(input.high(), input.low(), input.close())
+    }
+}
+
+impl<T> Peek<T, Raw> for Ichimoku<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        self.lines(input, input, input)
     }
 }
 
-impl<T> Reset for Ichimoku<T>
+impl<'a, U, T> Peek<&'a U, T> for Ichimoku<T>
 where
-    T: Clone + Default,
+    U: Close<T> + High<T> + Low<T>,
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
 {
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.lines(input.high(), input.low(), input.close())
+    }
+}
+
+impl<T> Reset for Ichimoku<T> {
     fn reset(&mut self) {
-        for i in 0..(self.kijun_sen_length + self.senkou_span_b_length) {
-            self.data[i] = IchimokuOutput::default();
-        }
-        self.nb_elemts = 0;
+        self.count = 0;
+        self.tenkan_sen.reset();
+        self.kijun_sen.reset();
+        self.senkou_span_b.reset();
+        self.kumos.clear();
+    }
+}
+
+impl<T> Warmup for Ichimoku<T> {
+    fn warmup_period(&self) -> usize {
+        (self.senkou_span_b_length + self.kijun_sen_length) as usize
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl<T> Default for Ichimoku<T> {
+    fn default() -> Self {
+        Self::new(9, 26, 52).unwrap()
+    }
+}
+
+impl<T> fmt::Display for Ichimoku<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ICHIMOKU({}, {}, {})",
+            self.tenkan_sen_length, self.kijun_sen_length, self.senkou_span_b_length
+        )
+    }
+}
+
+impl<T> FromStr for Ichimoku<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "ICHIMOKU", 3)?;
+        Self::new(
+            parse_param(s, params[0])?,
+            parse_param(s, params[1])?,
+            parse_param(s, params[2])?,
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_helper::Bar;
+    use crate::test_helper::*;
+
+    test_indicator!(Ichimoku);
+
+    fn kumo(senkou_span_a: f64, senkou_span_b: f64) -> Option<Kumo<f64>> {
+        Some(Kumo::new(senkou_span_a, senkou_span_b))
+    }
 
     #[test]
-    fn test_reset() {
-        let mut ich = Ichimoku::<f64>::new(2, 4, 8);
-        let ohlc = Bar::new().open(20.0).close(20.0).high(20.0).low(20.0);
-        for _i in 0..8 {
-            ich.next(&ohlc);
-        }
-        ich.reset();
-        for i in 0..12 {
-            assert_eq!(ich.data[i], IchimokuOutput::default());
-        }
+    fn test_new() {
+        assert!(Ichimoku::<f64>::new(0, 4, 8).is_err());
+        assert!(Ichimoku::<f64>::new(4, 4, 8).is_err());
+        assert!(Ichimoku::<f64>::new(2, 4, 4).is_err());
+        assert!(Ichimoku::<f64>::new(2, 4, 3).is_err());
+        assert!(Ichimoku::<f64>::new(1, 2, 3).is_ok());
     }
 
     #[test]
-    fn test_ichimoku_advanced() {
-        let mut ich = Ichimoku::<f64>::new(2, 4, 8);
+    fn test_next() {
+        let mut ich = Ichimoku::<f64>::new(2, 4, 8).unwrap();
+        let flat = Bar::new().close(20).high(20).low(20);
 
-        // Set all row with simple values
-        let ohlc = Bar::new().open(20.0).close(20.0).high(20.0).low(20.0);
-        for _i in 0..8 {
-            ich.next(&ohlc);
-        }
-        for i in 0..3 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 3..4 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    chikou_span: Some(20.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 4..7 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 7..8 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    tenkan_sen: Some(20.0),
-                    kijun_sen: Some(20.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 8..11 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 11..12 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    senkou_span_a: Some(20.0),
-                    senkou_span_b: Some(20.0),
-                    kumo_color: Some(KumoColor::Red),
-                    ..Default::default()
-                }
-            );
+        assert_eq!(
+            ich.next(&flat),
+            IchimokuOutput {
+                tenkan_sen: None,
+                kijun_sen: None,
+                kumo: None,
+                future_kumo: None,
+                chikou_span: None,
+            }
+        );
+        let output = ich.next(&flat);
+        assert_eq!(output.tenkan_sen, Some(20.0));
+        assert_eq!(output.kijun_sen, None);
+        for _ in 2..4 {
+            ich.next(&flat);
+        }
+        let output = ich.next(&flat);
+        assert_eq!(output.kijun_sen, Some(20.0));
+        assert_eq!(output.chikou_span, Some(20.0));
+        assert_eq!(output.future_kumo, None);
+        for _ in 5..7 {
+            ich.next(&flat);
         }
 
-        // Add an UP variation
-        let ohlc = Bar::new().open(20.0).close(30.0).high(30.0).low(20.0);
-        ich.next(&ohlc);
-        for i in 0..2 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 2..3 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    chikou_span: Some(20.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 3..4 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    chikou_span: Some(30.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 4..6 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 6..7 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    tenkan_sen: Some(20.0),
-                    kijun_sen: Some(20.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 7..8 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(30.0),
-                    high: Some(30.0),
-                    low: Some(20.0),
-                    tenkan_sen: Some(25.0),
-                    kijun_sen: Some(25.0),
-                    ..Default::default()
-                }
-            );
+        let output = ich.next(&flat);
+        assert_eq!(output.future_kumo, kumo(20.0, 20.0));
+        assert_eq!(output.kumo, None);
+
+        // an up move
+        let output = ich.next(&Bar::new().close(30).high(30).low(20));
+        assert_eq!(output.tenkan_sen, Some(25.0));
+        assert_eq!(output.kijun_sen, Some(25.0));
+        assert_eq!(output.future_kumo, kumo(25.0, 25.0));
+        assert_eq!(output.chikou_span, Some(30.0));
+
+        let flat = Bar::new().close(30).high(30).low(30);
+        let output = ich.next(&flat);
+        assert_eq!(output.tenkan_sen, Some(25.0));
+        assert_eq!(output.future_kumo, kumo(25.0, 25.0));
+        let output = ich.next(&flat);
+        assert_eq!(output.tenkan_sen, Some(30.0));
+        assert_eq!(output.future_kumo, kumo(27.5, 25.0));
+        assert_eq!(output.future_kumo.unwrap().color, KumoColor::Green);
+
+        // the cloud projected 4 periods ago
+        assert!(!ich.is_ready());
+        let output = ich.next(&flat);
+        assert!(ich.is_ready());
+        assert_eq!(output.kumo, kumo(20.0, 20.0));
+        assert_eq!(output.kumo.unwrap().color, KumoColor::Red);
+        assert_eq!(output.future_kumo, kumo(27.5, 25.0));
+
+        // another up move
+        let output = ich.next(&Bar::new().close(40).high(45).low(25));
+        assert_eq!(output.tenkan_sen, Some(35.0));
+        assert_eq!(output.kijun_sen, Some(35.0));
+        assert_eq!(output.kumo, kumo(25.0, 25.0));
+        assert_eq!(output.future_kumo, kumo(35.0, 32.5));
+        assert_eq!(output.chikou_span, Some(40.0));
+
+        let output = ich.next(&flat);
+        assert_eq!(output.kumo, kumo(25.0, 25.0));
+        let output = ich.next(&flat);
+        assert_eq!(output.kumo, kumo(27.5, 25.0));
+    }
+
+    #[test]
+    fn test_kumo_position() {
+        let kumo = Kumo::new(20.0, 25.0);
+        assert_eq!((kumo.top(), kumo.bottom()), (25.0, 20.0));
+        assert_eq!(kumo.position(26.0), CloudPosition::Above);
+        assert_eq!(kumo.position(25.0), CloudPosition::Inside);
+        assert_eq!(kumo.position(20.0), CloudPosition::Inside);
+        assert_eq!(kumo.position(19.0), CloudPosition::Below);
+    }
+
+    #[test]
+    fn test_next_raw() {
+        let mut ich = Ichimoku::<f64>::new(2, 4, 8).unwrap();
+        let mut expected = Ichimoku::<f64>::new(2, 4, 8).unwrap();
+
+        for i in 0..20 {
+            let price = 20.0 + (i % 5) as f64;
+            let bar = Bar::new().close(price).high(price).low(price);
+            assert_eq!(ich.next(price), expected.next(&bar));
         }
-        for i in 8..10 {
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use crate::DataItem;
+        use rust_decimal::Decimal;
+
+        let mut ich = Ichimoku::<Decimal>::new(2, 4, 8).unwrap();
+        let mut expected = Ichimoku::<f64>::new(2, 4, 8).unwrap();
+        for i in 0..16 {
+            let price = 20 + (i % 5);
+            let item = DataItem::builder()
+                .open(Decimal::from(price))
+                .high(Decimal::from(price + 1))
+                .low(Decimal::from(price - 1))
+                .close(Decimal::from(price))
+                .volume(Decimal::ONE)
+                .build()
+                .unwrap();
+            let bar = Bar::new()
+                .close(price as f64)
+                .high((price + 1) as f64)
+                .low((price - 1) as f64);
+
+            let output = ich.next(&item);
+            let float_output = expected.next(&bar);
+            let to_string = |v: Option<f64>| v.map(|v| v.to_string());
             assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    ..Default::default()
-                }
+                output.tenkan_sen.map(|v| v.normalize().to_string()),
+                to_string(float_output.tenkan_sen)
             );
-        }
-        for i in 10..11 {
             assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    senkou_span_a: Some(20.0),
-                    senkou_span_b: Some(20.0),
-                    kumo_color: Some(KumoColor::Red),
-                    ..Default::default()
-                }
+                output.kijun_sen.map(|v| v.normalize().to_string()),
+                to_string(float_output.kijun_sen)
             );
-        }
-        for i in 11..12 {
             assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    senkou_span_a: Some(25.0),
-                    senkou_span_b: Some(25.0),
-                    kumo_color: Some(KumoColor::Red),
-                    ..Default::default()
-                }
+                output.kumo.map(|k| k.senkou_span_b.normalize().to_string()),
+                to_string(float_output.kumo.map(|k| k.senkou_span_b))
             );
         }
+    }
 
-        // Continue threee times with default values
-        let ohlc = Bar::new().open(30.0).close(30.0).high(30.0).low(30.0);
-        for _i in 0..3 {
+    #[test]
+    fn test_reset() {
+        let mut ich = Ichimoku::<f64>::new(2, 4, 8).unwrap();
+        let ohlc = Bar::new().close(20.0).high(20.0).low(20.0);
+        let first = ich.next(&ohlc);
+        for _i in 0..12 {
             ich.next(&ohlc);
         }
-        for i in 0..3 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    chikou_span: Some(30.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 3..4 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    tenkan_sen: Some(20.0),
-                    kijun_sen: Some(20.0),
-                    chikou_span: Some(30.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 4..5 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(30.0),
-                    high: Some(30.0),
-                    low: Some(20.0),
-                    tenkan_sen: Some(25.0),
-                    kijun_sen: Some(25.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 5..6 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(30.0),
-                    high: Some(30.0),
-                    low: Some(30.0),
-                    tenkan_sen: Some(25.0),
-                    kijun_sen: Some(25.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 6..7 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(30.0),
-                    high: Some(30.0),
-                    low: Some(30.0),
-                    tenkan_sen: Some(30.0),
-                    kijun_sen: Some(25.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 7..8 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(30.0),
-                    high: Some(30.0),
-                    low: Some(30.0),
-                    tenkan_sen: Some(30.0),
-                    kijun_sen: Some(25.0),
-                    senkou_span_a: Some(20.0),
-                    senkou_span_b: Some(20.0),
-                    kumo_color: Some(KumoColor::Red),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 8..10 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    senkou_span_a: Some(25.0),
-                    senkou_span_b: Some(25.0),
-                    kumo_color: Some(KumoColor::Red),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 10..12 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    senkou_span_a: Some(27.5),
-                    senkou_span_b: Some(25.0),
-                    kumo_color: Some(KumoColor::Green),
-                    ..Default::default()
-                }
-            );
-        }
 
-        // Add an other UP
-        let ohlc = Bar::new().open(30.0).close(40.0).high(45.0).low(25.0);
-        ich.next(&ohlc);
-        for i in 0..2 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    chikou_span: Some(30.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 2..3 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(20.0),
-                    high: Some(20.0),
-                    low: Some(20.0),
-                    tenkan_sen: Some(20.0),
-                    kijun_sen: Some(20.0),
-                    chikou_span: Some(30.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 3..4 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(30.0),
-                    high: Some(30.0),
-                    low: Some(20.0),
-                    tenkan_sen: Some(25.0),
-                    kijun_sen: Some(25.0),
-                    chikou_span: Some(40.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 4..5 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(30.0),
-                    high: Some(30.0),
-                    low: Some(30.0),
-                    tenkan_sen: Some(25.0),
-                    kijun_sen: Some(25.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 5..6 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(30.0),
-                    high: Some(30.0),
-                    low: Some(30.0),
-                    tenkan_sen: Some(30.0),
-                    kijun_sen: Some(25.0),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 6..7 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(30.0),
-                    high: Some(30.0),
-                    low: Some(30.0),
-                    tenkan_sen: Some(30.0),
-                    kijun_sen: Some(25.0),
-                    senkou_span_a: Some(20.0),
-                    senkou_span_b: Some(20.0),
-                    kumo_color: Some(KumoColor::Red),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 7..8 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    close: Some(40.0),
-                    high: Some(45.0),
-                    low: Some(25.0),
-                    tenkan_sen: Some(35.0),
-                    kijun_sen: Some(35.0),
-                    senkou_span_a: Some(25.0),
-                    senkou_span_b: Some(25.0),
-                    kumo_color: Some(KumoColor::Red),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 8..9 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    senkou_span_a: Some(25.0),
-                    senkou_span_b: Some(25.0),
-                    kumo_color: Some(KumoColor::Red),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 9..11 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    senkou_span_a: Some(27.5),
-                    senkou_span_b: Some(25.0),
-                    kumo_color: Some(KumoColor::Green),
-                    ..Default::default()
-                }
-            );
-        }
-        for i in 11..12 {
-            assert_eq!(
-                ich.data[i],
-                IchimokuOutput {
-                    senkou_span_a: Some(35.0),
-                    senkou_span_b: Some(32.5),
-                    kumo_color: Some(KumoColor::Green),
-                    ..Default::default()
-                }
-            );
-        }
+        ich.reset();
+        assert_eq!(ich.next(&ohlc), first);
+    }
+
+    #[test]
+    fn test_default() {
+        let ich = Ichimoku::<f64>::default();
+        assert_eq!(ich.warmup_period(), 78);
+    }
+
+    #[test]
+    fn test_display() {
+        let ich = Ichimoku::<f64>::new(2, 4, 8).unwrap();
+        assert_eq!(format!("{}", ich), "ICHIMOKU(2, 4, 8)");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let ich: Ichimoku<f64> = "ICHIMOKU(2, 4, 8)".parse().unwrap();
+        assert_eq!(ich.kijun_sen_length(), 4);
+
+        assert!("ICHIMOKU(2, 4)".parse::<Ichimoku<f64>>().is_err());
+        assert!("ICHIMOKU(4, 2, 8)".parse::<Ichimoku<f64>>().is_err());
     }
 }
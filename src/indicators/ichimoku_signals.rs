@@ -0,0 +1,583 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Div};
+use core::str::FromStr;
+
+use num_traits::cast::FromPrimitive;
+
+use crate::errors::*;
+use crate::helpers::{parse_param, parse_params};
+use crate::indicators::{CloudPosition, Ichimoku, IchimokuOutput, KumoColor};
+use crate::{Close, High, Low, Next, Peek, Raw, Reset, UpdateLast, Warmup};
+
+/// Trading signals of an [Ichimoku](struct.Ichimoku.html), detected every period.
+///
+/// * _TK cross_ - the Tenkan-sen crosses the Kijun-sen, bullish when it crosses upwards.
+/// * _Kumo breakout_ - the close leaves the cloud upwards (bullish) or downwards (bearish).
+/// * _Kumo twist_ - the Senkou spans of the projected cloud exchange positions, bullish when
+///   it turns green.
+/// * _Chikou cross_ - the Chikou span crosses the close it is plotted against, i.e. the close
+///   crosses the close of `kijun_sen_length` periods ago, bullish when it crosses upwards.
+///
+/// The strength of a signal tells where it happens relative to the current cloud: a bullish
+/// signal above the cloud, or a bearish one below it, is strong, and a signal in the cloud is
+/// neutral. TK crosses are located by the Kijun-sen, other signals by the close, so a Kumo
+/// breakout is always strong.
+///
+/// A line crosses another one once it is strictly on the other side, so touching the line and
+/// coming back is not a cross. Signals are emitted once the current cloud is known, i.e. after
+/// the warm-up period of the Ichimoku.
+///
+/// # Parameters
+///
+/// Same as [Ichimoku](struct.Ichimoku.html).
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{
+///     IchimokuSignal, IchimokuSignalKind, IchimokuSignals, SignalDirection, SignalStrength,
+/// };
+/// use ta::Next;
+///
+/// let mut ichimoku = IchimokuSignals::new(2, 4, 8).unwrap();
+/// for _ in 0..12 {
+///     ichimoku.next(20.0);
+/// }
+/// ichimoku.next(19.0);
+///
+/// let output = ichimoku.next(22.0);
+/// assert!(output.signals.contains(&IchimokuSignal {
+///     kind: IchimokuSignalKind::KumoBreakout,
+///     direction: SignalDirection::Bullish,
+///     strength: SignalStrength::Strong,
+/// }));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IchimokuSignals<T> {
+    ichimoku: Ichimoku<T>,
+    // Closes of the last kijun_sen_length + 1 periods
+    closes: VecDeque<T>,
+    state: SignalState,
+    // State before the latest input, restored by `update_last`
+    saved: SignalState,
+}
+
+/// Output of [IchimokuSignals](struct.IchimokuSignals.html) for a period.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IchimokuSignalsOutput<T> {
+    pub lines: IchimokuOutput<T>,
+    /// Position of the close relative to the current cloud.
+    pub position: Option<CloudPosition>,
+    /// Signals of the period, usually none.
+    pub signals: Vec<IchimokuSignal>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IchimokuSignal {
+    pub kind: IchimokuSignalKind,
+    pub direction: SignalDirection,
+    pub strength: SignalStrength,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IchimokuSignalKind {
+    TkCross,
+    KumoBreakout,
+    KumoTwist,
+    ChikouCross,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignalDirection {
+    Bullish,
+    Bearish,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignalStrength {
+    /// Bullish above the cloud, or bearish below it
+    Strong,
+    /// In the cloud
+    Neutral,
+    /// Bullish below the cloud, or bearish above it
+    Weak,
+}
+
+impl SignalStrength {
+    fn new(direction: SignalDirection, position: CloudPosition) -> Self {
+        match (direction, position) {
+            (SignalDirection::Bullish, CloudPosition::Above)
+            | (SignalDirection::Bearish, CloudPosition::Below) => SignalStrength::Strong,
+            (_, CloudPosition::Inside) => SignalStrength::Neutral,
+            _ => SignalStrength::Weak,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct SignalState {
+    // Whether the Tenkan-sen is above the Kijun-sen
+    tenkan_above: Option<bool>,
+    // Whether the close is above the close of kijun_sen_length periods ago
+    chikou_above: Option<bool>,
+    position: Option<CloudPosition>,
+    future_color: Option<KumoColor>,
+}
+
+// Whether `a` is above `b`, a tie keeps the previous side
+fn above<T: PartialOrd>(a: T, b: T, previous: Option<bool>) -> Option<bool> {
+    if a > b {
+        Some(true)
+    } else if a < b {
+        Some(false)
+    } else {
+        previous
+    }
+}
+
+fn cross(previous: Option<bool>, current: Option<bool>) -> Option<SignalDirection> {
+    match (previous, current) {
+        (Some(false), Some(true)) => Some(SignalDirection::Bullish),
+        (Some(true), Some(false)) => Some(SignalDirection::Bearish),
+        _ => None,
+    }
+}
+
+impl<T> IchimokuSignals<T> {
+    pub fn new(
+        tenkan_sen_length: u32,
+        kijun_sen_length: u32,
+        senkou_span_b_length: u32,
+    ) -> Result<Self> {
+        let ichimoku = Ichimoku::new(tenkan_sen_length, kijun_sen_length, senkou_span_b_length)
+            .map_err(|err| match err {
+                Error::InvalidParameter {
+                    name,
+                    value,
+                    constraint,
+                    ..
+                } => Error::InvalidParameter {
+                    indicator: "ICHIMOKU_SIGNALS",
+                    name,
+                    value,
+                    constraint,
+                },
+                err => err,
+            })?;
+        Ok(Self {
+            ichimoku,
+            closes: VecDeque::with_capacity(kijun_sen_length as usize + 1),
+            state: SignalState::default(),
+            saved: SignalState::default(),
+        })
+    }
+
+    /// Returns the underlying Ichimoku.
+    pub fn ichimoku(&self) -> &Ichimoku<T> {
+        &self.ichimoku
+    }
+}
+
+impl<T> IchimokuSignals<T>
+where
+    T: Copy + PartialOrd,
+{
+    // Signals of the lines of an input following the periods seen so far
+    fn evaluate(
+        &self,
+        lines: IchimokuOutput<T>,
+        close: T,
+    ) -> (SignalState, IchimokuSignalsOutput<T>) {
+        let previous = self.state;
+        let past_close = self
+            .closes
+            .len()
+            .checked_sub(self.ichimoku.kijun_sen_length() as usize)
+            .and_then(|index| self.closes.get(index));
+
+        let state = SignalState {
+            tenkan_above: match (lines.tenkan_sen, lines.kijun_sen) {
+                (Some(tenkan_sen), Some(kijun_sen)) => {
+                    above(tenkan_sen, kijun_sen, previous.tenkan_above)
+                }
+                _ => None,
+            },
+            chikou_above: past_close.and_then(|&past| above(close, past, previous.chikou_above)),
+            position: lines.kumo.map(|kumo| kumo.position(close)),
+            future_color: lines.future_kumo.map(|kumo| kumo.color),
+        };
+
+        let mut signals = Vec::new();
+        if let (Some(kumo), Some(position)) = (lines.kumo, state.position) {
+            let mut emit = |kind, direction, position| {
+                signals.push(IchimokuSignal {
+                    kind,
+                    direction,
+                    strength: SignalStrength::new(direction, position),
+                })
+            };
+
+            if let (Some(direction), Some(kijun_sen)) = (
+                cross(previous.tenkan_above, state.tenkan_above),
+                lines.kijun_sen,
+            ) {
+                emit(
+                    IchimokuSignalKind::TkCross,
+                    direction,
+                    kumo.position(kijun_sen),
+                );
+            }
+            match (previous.position, position) {
+                (Some(from), CloudPosition::Above) if from != CloudPosition::Above => emit(
+                    IchimokuSignalKind::KumoBreakout,
+                    SignalDirection::Bullish,
+                    position,
+                ),
+                (Some(from), CloudPosition::Below) if from != CloudPosition::Below => emit(
+                    IchimokuSignalKind::KumoBreakout,
+                    SignalDirection::Bearish,
+                    position,
+                ),
+                _ => {}
+            }
+            match (previous.future_color, state.future_color) {
+                (Some(KumoColor::Red), Some(KumoColor::Green)) => emit(
+                    IchimokuSignalKind::KumoTwist,
+                    SignalDirection::Bullish,
+                    position,
+                ),
+                (Some(KumoColor::Green), Some(KumoColor::Red)) => emit(
+                    IchimokuSignalKind::KumoTwist,
+                    SignalDirection::Bearish,
+                    position,
+                ),
+                _ => {}
+            }
+            if let Some(direction) = cross(previous.chikou_above, state.chikou_above) {
+                emit(IchimokuSignalKind::ChikouCross, direction, position);
+            }
+        }
+
+        let output = IchimokuSignalsOutput {
+            lines,
+            position: state.position,
+            signals,
+        };
+        (state, output)
+    }
+
+    fn push(&mut self, lines: IchimokuOutput<T>, close: T) -> IchimokuSignalsOutput<T> {
+        let (state, output) = self.evaluate(lines, close);
+        self.saved = self.state;
+        self.state = state;
+        self.closes.push_back(close);
+        if self.closes.len() > self.ichimoku.kijun_sen_length() as usize + 1 {
+            self.closes.pop_front();
+        }
+        output
+    }
+
+    fn amend(&mut self, lines: IchimokuOutput<T>, close: T) -> IchimokuSignalsOutput<T> {
+        if self.closes.pop_back().is_some() {
+            self.state = self.saved;
+        }
+        self.push(lines, close)
+    }
+}
+
+impl<T> Next<T, Raw> for IchimokuSignals<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    type Output = IchimokuSignalsOutput<T>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let lines = self.ichimoku.next(input);
+        self.push(lines, input)
+    }
+}
+
+impl<'a, U, T> Next<&'a U, T> for IchimokuSignals<T>
+where
+    U: Close<T> + High<T> + Low<T>,
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    type Output = IchimokuSignalsOutput<T>;
+
+    fn next(&mut self, input: &'a U) -> Self::Output {
+        let lines = self.ichimoku.next(input);
+        self.push(lines, input.close())
+    }
+}
+
+impl<T> UpdateLast<T, Raw> for IchimokuSignals<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        let lines = self.ichimoku.update_last(input);
+        self.amend(lines, input)
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for IchimokuSignals<T>
+where
+    U: Close<T> + High<T> + Low<T>,
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        let lines = self.ichimoku.update_last(input);
+        self.amend(lines, input.close())
+    }
+}
+
+impl<T> Peek<T, Raw> for IchimokuSignals<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        self.evaluate(self.ichimoku.peek(input), input).1
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for IchimokuSignals<T>
+where
+    U: Close<T> + High<T> + Low<T>,
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.evaluate(self.ichimoku.peek(input), input.close()).1
+    }
+}
+
+impl<T> Reset for IchimokuSignals<T> {
+    fn reset(&mut self) {
+        self.ichimoku.reset();
+        self.closes.clear();
+        self.state = SignalState::default();
+        self.saved = SignalState::default();
+    }
+}
+
+impl<T> Warmup for IchimokuSignals<T> {
+    fn warmup_period(&self) -> usize {
+        self.ichimoku.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ichimoku.is_ready()
+    }
+}
+
+impl<T> Default for IchimokuSignals<T> {
+    fn default() -> Self {
+        Self::new(9, 26, 52).unwrap()
+    }
+}
+
+impl<T> fmt::Display for IchimokuSignals<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ICHIMOKU_SIGNALS({}, {}, {})",
+            self.ichimoku.tenkan_sen_length(),
+            self.ichimoku.kijun_sen_length(),
+            self.ichimoku.senkou_span_b_length()
+        )
+    }
+}
+
+impl<T> FromStr for IchimokuSignals<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "ICHIMOKU_SIGNALS", 3)?;
+        Self::new(
+            parse_param(s, params[0])?,
+            parse_param(s, params[1])?,
+            parse_param(s, params[2])?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use IchimokuSignalKind::*;
+    use SignalDirection::*;
+    use SignalStrength::*;
+
+    test_indicator!(IchimokuSignals);
+
+    fn signal(
+        kind: IchimokuSignalKind,
+        direction: SignalDirection,
+        strength: SignalStrength,
+    ) -> IchimokuSignal {
+        IchimokuSignal {
+            kind,
+            direction,
+            strength,
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(IchimokuSignals::<f64>::new(0, 4, 8).is_err());
+        assert!(IchimokuSignals::<f64>::new(2, 2, 8).is_err());
+        assert!(IchimokuSignals::<f64>::new(2, 4, 4).is_err());
+        assert!(IchimokuSignals::<f64>::new(2, 4, 8).is_ok());
+
+        match IchimokuSignals::<f64>::new(0, 4, 8) {
+            Err(Error::InvalidParameter { indicator, .. }) => {
+                assert_eq!(indicator, "ICHIMOKU_SIGNALS")
+            }
+            _ => panic!("expected an invalid parameter"),
+        }
+    }
+
+    #[test]
+    fn test_strength() {
+        assert_eq!(SignalStrength::new(Bullish, CloudPosition::Above), Strong);
+        assert_eq!(SignalStrength::new(Bullish, CloudPosition::Inside), Neutral);
+        assert_eq!(SignalStrength::new(Bullish, CloudPosition::Below), Weak);
+        assert_eq!(SignalStrength::new(Bearish, CloudPosition::Above), Weak);
+        assert_eq!(SignalStrength::new(Bearish, CloudPosition::Inside), Neutral);
+        assert_eq!(SignalStrength::new(Bearish, CloudPosition::Below), Strong);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ich = IchimokuSignals::<f64>::new(2, 4, 8).unwrap();
+        let prices = [
+            20.0, 20.0, 20.0, 20.0, 20.0, 20.0, 20.0, 20.0, 18.0, 16.0, 15.0, 14.0, 13.0, 14.0,
+            16.0, 19.0, 22.0, 25.0, 27.0, 28.0, 27.0, 24.0, 21.0, 18.0, 15.0, 13.0,
+        ];
+        let outputs: Vec<_> = prices.iter().map(|&price| ich.next(price)).collect();
+
+        // no signal before the cloud of the current period is known
+        assert!(outputs[..11].iter().all(|o| o.position.is_none()));
+        assert_eq!(outputs[11].position, Some(CloudPosition::Below));
+
+        // the Tenkan-sen crosses the Kijun-sen upwards below the cloud
+        assert_eq!(
+            outputs[14].signals,
+            vec![
+                signal(TkCross, Bullish, Weak),
+                signal(ChikouCross, Bullish, Weak)
+            ]
+        );
+        assert_eq!(
+            outputs[15].signals,
+            vec![
+                signal(KumoBreakout, Bullish, Strong),
+                signal(KumoTwist, Bullish, Strong)
+            ]
+        );
+        assert_eq!(
+            outputs[21].signals,
+            vec![
+                signal(TkCross, Bearish, Weak),
+                signal(ChikouCross, Bearish, Weak)
+            ]
+        );
+        assert_eq!(outputs[22].position, Some(CloudPosition::Inside));
+        assert_eq!(
+            outputs[22].signals,
+            vec![signal(KumoTwist, Bearish, Neutral)]
+        );
+        assert_eq!(
+            outputs[23].signals,
+            vec![signal(KumoBreakout, Bearish, Strong)]
+        );
+
+        let with_signals = [14, 15, 21, 22, 23];
+        for (i, output) in outputs.iter().enumerate() {
+            assert_eq!(output.signals.is_empty(), !with_signals.contains(&i));
+        }
+    }
+
+    #[test]
+    fn test_next_tie() {
+        let mut ich = IchimokuSignals::<f64>::new(2, 4, 8).unwrap();
+        for _ in 0..12 {
+            ich.next(20.0);
+        }
+
+        // the edges belong to the cloud, so leaving it again from an edge is a breakout
+        assert_eq!(ich.next(21.0).position, Some(CloudPosition::Above));
+        assert_eq!(ich.next(20.0).position, Some(CloudPosition::Inside));
+        let output = ich.next(21.0);
+        assert_eq!(output.position, Some(CloudPosition::Above));
+        assert_eq!(output.signals, vec![signal(KumoBreakout, Bullish, Strong)]);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut ich = IchimokuSignals::<f64>::new(2, 4, 8).unwrap();
+        let mut expected = Ichimoku::<f64>::new(2, 4, 8).unwrap();
+
+        for i in 0..20 {
+            let price = 20.0 + (i % 5) as f64;
+            let bar = Bar::new().close(price).high(price + 1.0).low(price - 2.0);
+            let output = ich.next(&bar);
+            let lines = expected.next(&bar);
+            assert_eq!(output.lines, lines);
+            assert_eq!(output.position, lines.kumo.map(|kumo| kumo.position(price)));
+        }
+    }
+
+    #[test]
+    fn test_update_last_and_peek() {
+        let prices: Vec<f64> = (0..40)
+            .map(|i| 20.0 + ((i * 7) % 11) as f64 - (i / 10) as f64 * 2.0)
+            .collect();
+        let mut ich = IchimokuSignals::<f64>::new(2, 4, 8).unwrap();
+        let mut expected = IchimokuSignals::<f64>::new(2, 4, 8).unwrap();
+
+        let mut signals = 0;
+        for &price in &prices {
+            ich.peek(price + 1.0);
+            let output = expected.next(price);
+            signals += output.signals.len();
+            assert_eq!(ich.peek(price), output);
+
+            ich.next(price + 5.0);
+            ich.update_last(price - 5.0);
+            assert_eq!(ich.update_last(price), output);
+        }
+        assert!(signals > 0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ich = IchimokuSignals::<f64>::new(2, 4, 8).unwrap();
+        let outputs: Vec<_> = (0..20).map(|i| ich.next(20.0 - i as f64)).collect();
+
+        ich.reset();
+        for (i, output) in outputs.iter().enumerate() {
+            assert_eq!(&ich.next(20.0 - i as f64), output);
+        }
+    }
+
+    #[test]
+    fn test_default() {
+        IchimokuSignals::<f64>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ich = IchimokuSignals::<f64>::new(2, 4, 8).unwrap();
+        assert_eq!(format!("{}", ich), "ICHIMOKU_SIGNALS(2, 4, 8)");
+    }
+}
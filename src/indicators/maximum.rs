@@ -1,10 +1,16 @@
-use std::fmt;
+use core::fmt;
+use core::str::FromStr;
 
 use crate::errors::*;
-use crate::{High, Next, Reset};
+use crate::helpers::{parse_param, parse_params};
+use crate::rolling::{Extremum, RollingExtremum};
+use crate::{High, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// Returns the highest value in a given time frame.
 ///
+/// It runs in amortized constant time per input, whatever the size of the time frame: only the
+/// inputs which may still become the highest value are kept.
+///
 /// # Parameters
 ///
 /// * _n_ - size of the time frame (integer greater than 0). Default value is 14.
@@ -23,10 +29,9 @@ use crate::{High, Next, Reset};
 /// assert_eq!(max.next(8.0), 8.0);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Maximum<T> {
-    vec: Vec<Option<T>>,
-    max_index: usize,
-    cur_index: usize,
+    window: RollingExtremum<T>,
 }
 
 impl<T> Maximum<T>
@@ -37,59 +42,23 @@ where
         let n = n as usize;
 
         if n == 0 {
-            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+            return Err(Error::invalid_parameter("MAX", "n", n, "greater than 0"));
         }
 
-        let indicator = Self {
-            vec: vec![None; n],
-            max_index: 0,
-            cur_index: 0,
-        };
-        Ok(indicator)
-    }
-
-    fn find_max_index(&self) -> Option<usize> {
-        let mut max_value: Option<T> = None;
-        let mut max_index: Option<usize> = None;
-
-        for (i, val) in self.vec.iter().enumerate() {
-            if let Some(value) = val {
-                match max_index {
-                    Some(_) => {
-                        if *value > max_value.expect("cannot happened") {
-                            max_index = Some(i);
-                            max_value = *val;
-                        }
-                    }
-                    None => {
-                        max_index = Some(i);
-                        max_value = *val;
-                    }
-                }
-            }
-        }
-        max_index
+        Ok(Self {
+            window: RollingExtremum::new(Extremum::Max, n),
+        })
     }
 }
 
-impl<T> Next<T, !> for Maximum<T>
+impl<T> Next<T, Raw> for Maximum<T>
 where
     T: Copy + PartialOrd,
 {
     type Output = T;
 
     fn next(&mut self, input: T) -> Self::Output {
-        self.cur_index = (self.cur_index + 1) % self.vec.len();
-        self.vec[self.cur_index] = Some(input);
-
-        if let Some(max_value) = self.vec[self.max_index] {
-            if input > max_value {
-                self.max_index = self.cur_index;
-                return self.vec[self.max_index].expect("Cannot happened");
-            }
-        }
-        self.max_index = self.find_max_index().expect("Cannot happened");
-        self.vec[self.max_index].expect("Cannot happened")
+        self.window.push(input)
     }
 }
 
@@ -105,11 +74,57 @@ where
     }
 }
 
+impl<T> UpdateLast<T, Raw> for Maximum<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        self.window.update_last(input)
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for Maximum<T>
+where
+    U: High<T>,
+    T: Copy + PartialOrd,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.high())
+    }
+}
+
+impl<T> Peek<T, Raw> for Maximum<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        self.window.peek(input)
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for Maximum<T>
+where
+    U: High<T>,
+    T: Copy + PartialOrd,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.high())
+    }
+}
+
 impl<T> Reset for Maximum<T> {
     fn reset(&mut self) {
-        for elmt in self.vec.iter_mut() {
-            *elmt = None;
-        }
+        self.window.reset();
+    }
+}
+
+impl<T> Warmup for Maximum<T> {
+    fn warmup_period(&self) -> usize {
+        self.window.length()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
     }
 }
 
@@ -124,7 +139,20 @@ where
 
 impl<T> fmt::Display for Maximum<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "MAX({})", self.vec.len())
+        write!(f, "MAX({})", self.window.length())
+    }
+}
+
+impl<T> FromStr for Maximum<T>
+where
+    T: Copy + PartialOrd,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "MAX", 1)?;
+        let n = parse_param(s, params[0])?;
+        Self::new(n)
     }
 }
 
@@ -170,6 +198,31 @@ mod tests {
         assert_eq!(max.next(&bar(2.0)), 3.5);
     }
 
+    #[test]
+    fn test_next_fixed() {
+        use crate::{DataItem, Fixed};
+
+        fn bar(high: i64) -> DataItem<Fixed<2>> {
+            let high = Fixed::from_ticks(high);
+            DataItem::builder()
+                .open(high)
+                .high(high)
+                .low(high)
+                .close(high)
+                .volume(Fixed::from_ticks(0))
+                .build()
+                .unwrap()
+        }
+
+        let mut max = Maximum::<Fixed<2>>::new(2).unwrap();
+
+        assert_eq!(max.next(Fixed::from_ticks(110)), Fixed::from_ticks(110));
+        assert_eq!(max.next(Fixed::from_ticks(-400)), Fixed::from_ticks(110));
+        assert_eq!(max.next(&bar(350)), Fixed::from_ticks(350));
+        assert_eq!(max.next(&bar(200)), Fixed::from_ticks(350));
+        assert_eq!(max.next(&bar(100)), Fixed::from_ticks(200));
+    }
+
     #[test]
     fn test_reset() {
         let mut max = Maximum::<f64>::new(100).unwrap();
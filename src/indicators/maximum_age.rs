@@ -0,0 +1,280 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::errors::*;
+use crate::helpers::{parse_param, parse_params};
+use crate::rolling::{Extremum, RollingExtremum};
+use crate::{High, Next, Peek, Raw, Reset, UpdateLast, Warmup};
+
+/// Returns the highest value in a given time frame, with the number of periods since it.
+///
+/// This is what Aroon up, breakout age filters or Donchian channel exits are built on. The most
+/// recent period wins on ties, so the age is 0 when the latest input equals the highest one.
+///
+/// # Parameters
+///
+/// * _n_ - size of the time frame (integer greater than 0). Default value is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{Extreme, MaximumAge};
+/// use ta::Next;
+///
+/// let mut max = MaximumAge::<f64>::new(3).unwrap();
+/// assert_eq!(max.next(7.0), Extreme { value: 7.0, age: 0, index: 0 });
+/// assert_eq!(max.next(5.0), Extreme { value: 7.0, age: 1, index: 0 });
+/// assert_eq!(max.next(4.0), Extreme { value: 7.0, age: 2, index: 0 });
+/// assert_eq!(max.next(4.0), Extreme { value: 5.0, age: 2, index: 1 });
+///
+/// // Aroon up over 2 periods looks at the last 3 ones
+/// let aroon_up = 100.0 * (2 - max.next(6.0).age) as f64 / 2.0;
+/// assert_eq!(aroon_up, 100.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaximumAge<T> {
+    window: RollingExtremum<T>,
+}
+
+/// Highest or lowest value of a time frame, output of [MaximumAge](struct.MaximumAge.html) and
+/// [MinimumAge](struct.MinimumAge.html).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Extreme<T> {
+    pub value: T,
+    /// Number of periods since the extreme, 0 for the latest input.
+    pub age: usize,
+    /// Period of the extreme, counted from the first input since the last reset.
+    pub index: usize,
+}
+
+impl<T> Extreme<T> {
+    // Extreme at `index`, the latest input being the one at `last`
+    pub(super) fn new((index, value): (usize, T), last: usize) -> Self {
+        Self {
+            value,
+            age: last - index,
+            index,
+        }
+    }
+}
+
+impl<T> MaximumAge<T>
+where
+    T: Copy + PartialOrd,
+{
+    pub fn new(n: u32) -> Result<Self> {
+        let n = n as usize;
+
+        if n == 0 {
+            return Err(Error::invalid_parameter(
+                "MAX_AGE",
+                "n",
+                n,
+                "greater than 0",
+            ));
+        }
+
+        Ok(Self {
+            window: RollingExtremum::new(Extremum::Max, n),
+        })
+    }
+
+    // Extreme of the window, only once an input was pushed
+    fn output(&self) -> Extreme<T> {
+        Extreme::new(
+            self.window
+                .extreme()
+                .expect("window is non-empty after an input"),
+            self.window.count() - 1,
+        )
+    }
+}
+
+impl<T> Next<T, Raw> for MaximumAge<T>
+where
+    T: Copy + PartialOrd,
+{
+    type Output = Extreme<T>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        self.window.push(input);
+        self.output()
+    }
+}
+
+impl<'a, U, T> Next<&'a U, T> for MaximumAge<T>
+where
+    U: High<T>,
+    T: Copy + PartialOrd,
+{
+    type Output = Extreme<T>;
+
+    fn next(&mut self, input: &'a U) -> Self::Output {
+        self.next(input.high())
+    }
+}
+
+impl<T> UpdateLast<T, Raw> for MaximumAge<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        self.window.update_last(input);
+        self.output()
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for MaximumAge<T>
+where
+    U: High<T>,
+    T: Copy + PartialOrd,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.high())
+    }
+}
+
+impl<T> Peek<T, Raw> for MaximumAge<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        Extreme::new(self.window.peek_extreme(input), self.window.count())
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for MaximumAge<T>
+where
+    U: High<T>,
+    T: Copy + PartialOrd,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.high())
+    }
+}
+
+impl<T> Reset for MaximumAge<T> {
+    fn reset(&mut self) {
+        self.window.reset();
+    }
+}
+
+impl<T> Warmup for MaximumAge<T> {
+    fn warmup_period(&self) -> usize {
+        self.window.length()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
+    }
+}
+
+impl<T> Default for MaximumAge<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl<T> fmt::Display for MaximumAge<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MAX_AGE({})", self.window.length())
+    }
+}
+
+impl<T> FromStr for MaximumAge<T>
+where
+    T: Copy + PartialOrd,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "MAX_AGE", 1)?;
+        let n = parse_param(s, params[0])?;
+        Self::new(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::Maximum;
+    use crate::test_helper::*;
+
+    test_indicator!(MaximumAge);
+
+    fn extreme(value: f64, age: usize, index: usize) -> Extreme<f64> {
+        Extreme { value, age, index }
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(MaximumAge::<f64>::new(0).is_err());
+        assert!(MaximumAge::<f64>::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut max = MaximumAge::<f64>::new(3).unwrap();
+
+        assert_eq!(max.next(4.0), extreme(4.0, 0, 0));
+        assert_eq!(max.next(1.2), extreme(4.0, 1, 0));
+        assert_eq!(max.next(5.0), extreme(5.0, 0, 2));
+        assert_eq!(max.next(3.0), extreme(5.0, 1, 2));
+        assert_eq!(max.next(5.0), extreme(5.0, 0, 4));
+        assert_eq!(max.next(0.0), extreme(5.0, 1, 4));
+        assert_eq!(max.next(-1.0), extreme(5.0, 2, 4));
+        assert_eq!(max.next(-2.0), extreme(0.0, 2, 5));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        fn bar(high: f64) -> Bar {
+            Bar::new().high(high)
+        }
+
+        let mut max = MaximumAge::<f64>::new(2).unwrap();
+
+        assert_eq!(max.next(&bar(1.1)), extreme(1.1, 0, 0));
+        assert_eq!(max.next(&bar(4.0)), extreme(4.0, 0, 1));
+        assert_eq!(max.next(&bar(3.5)), extreme(4.0, 1, 1));
+        assert_eq!(max.next(&bar(2.0)), extreme(3.5, 1, 2));
+    }
+
+    #[test]
+    fn test_same_value_as_maximum() {
+        let mut max = Maximum::<f64>::new(4).unwrap();
+        let mut max_age = MaximumAge::<f64>::new(4).unwrap();
+
+        for i in 0..40 {
+            let input = ((i * 7) % 11) as f64;
+            assert_eq!(max_age.next(input).value, max.next(input));
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut max = MaximumAge::<f64>::new(100).unwrap();
+        max.next(4.0);
+        max.next(10.0);
+        assert_eq!(max.next(4.0), extreme(10.0, 1, 1));
+
+        max.reset();
+        assert_eq!(max.next(4.0), extreme(4.0, 0, 0));
+    }
+
+    #[test]
+    fn test_default() {
+        MaximumAge::<f64>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let indicator = MaximumAge::<f64>::new(7).unwrap();
+        assert_eq!(format!("{}", indicator), "MAX_AGE(7)");
+    }
+}
@@ -1,10 +1,16 @@
-use std::fmt;
+use core::fmt;
+use core::str::FromStr;
 
 use crate::errors::*;
-use crate::{Low, Next, Reset};
+use crate::helpers::{parse_param, parse_params};
+use crate::rolling::{Extremum, RollingExtremum};
+use crate::{Low, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// Returns the lowest value in a given time frame.
 ///
+/// It runs in amortized constant time per input, whatever the size of the time frame: only the
+/// inputs which may still become the lowest value are kept.
+///
 /// # Parameters
 ///
 /// * _n_ - size of the time frame (integer greater than 0). Default value is 14.
@@ -22,10 +28,9 @@ use crate::{Low, Next, Reset};
 /// assert_eq!(min.next(13.0), 11.0);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Minimum<T> {
-    vec: Vec<Option<T>>,
-    min_index: usize,
-    cur_index: usize,
+    window: RollingExtremum<T>,
 }
 
 impl<T> Minimum<T>
@@ -35,61 +40,24 @@ where
     pub fn new(n: u32) -> Result<Self> {
         let n = n as usize;
 
-        if n <= 0 {
-            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        if n == 0 {
+            return Err(Error::invalid_parameter("MIN", "n", n, "greater than 0"));
         }
 
-        let indicator = Self {
-            vec: vec![None; n],
-            min_index: 0,
-            cur_index: 0,
-        };
-
-        Ok(indicator)
-    }
-
-    fn find_min_index(&self) -> Option<usize> {
-        let mut min_value: Option<T> = None;
-        let mut min_index: Option<usize> = None;
-
-        for (i, val) in self.vec.iter().enumerate() {
-            if let Some(value) = val {
-                match min_index {
-                    Some(_) => {
-                        if *value < min_value.expect("cannot happened") {
-                            min_index = Some(i);
-                            min_value = *val;
-                        }
-                    }
-                    None => {
-                        min_index = Some(i);
-                        min_value = *val;
-                    }
-                }
-            }
-        }
-        min_index
+        Ok(Self {
+            window: RollingExtremum::new(Extremum::Min, n),
+        })
     }
 }
 
-impl<T> Next<T, !> for Minimum<T>
+impl<T> Next<T, Raw> for Minimum<T>
 where
     T: Copy + PartialOrd,
 {
     type Output = T;
 
     fn next(&mut self, input: T) -> Self::Output {
-        self.cur_index = (self.cur_index + 1) % self.vec.len();
-        self.vec[self.cur_index] = Some(input);
-
-        if let Some(min_value) = self.vec[self.min_index] {
-            if input < min_value {
-                self.min_index = self.cur_index;
-                return self.vec[self.min_index].expect("Cannot happened");
-            }
-        }
-        self.min_index = self.find_min_index().expect("Cannot happened");
-        self.vec[self.min_index].expect("Cannot happened")
+        self.window.push(input)
     }
 }
 
@@ -105,11 +73,57 @@ where
     }
 }
 
+impl<T> UpdateLast<T, Raw> for Minimum<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        self.window.update_last(input)
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for Minimum<T>
+where
+    U: Low<T>,
+    T: Copy + PartialOrd,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.low())
+    }
+}
+
+impl<T> Peek<T, Raw> for Minimum<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        self.window.peek(input)
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for Minimum<T>
+where
+    U: Low<T>,
+    T: Copy + PartialOrd,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.low())
+    }
+}
+
 impl<T> Reset for Minimum<T> {
     fn reset(&mut self) {
-        for elmt in self.vec.iter_mut() {
-            *elmt = None;
-        }
+        self.window.reset();
+    }
+}
+
+impl<T> Warmup for Minimum<T> {
+    fn warmup_period(&self) -> usize {
+        self.window.length()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
     }
 }
 
@@ -124,7 +138,20 @@ where
 
 impl<T> fmt::Display for Minimum<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "MIN({})", self.vec.len())
+        write!(f, "MIN({})", self.window.length())
+    }
+}
+
+impl<T> FromStr for Minimum<T>
+where
+    T: Copy + PartialOrd,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "MIN", 1)?;
+        let n = parse_param(s, params[0])?;
+        Self::new(n)
     }
 }
 
@@ -171,6 +198,31 @@ mod tests {
         assert_eq!(min.next(&bar(5.0)), 1.2);
     }
 
+    #[test]
+    fn test_next_fixed() {
+        use crate::{DataItem, Fixed};
+
+        fn bar(low: i64) -> DataItem<Fixed<2>> {
+            let low = Fixed::from_ticks(low);
+            DataItem::builder()
+                .open(low)
+                .high(low)
+                .low(low)
+                .close(low)
+                .volume(Fixed::from_ticks(0))
+                .build()
+                .unwrap()
+        }
+
+        let mut min = Minimum::<Fixed<2>>::new(2).unwrap();
+
+        assert_eq!(min.next(Fixed::from_ticks(110)), Fixed::from_ticks(110));
+        assert_eq!(min.next(Fixed::from_ticks(-400)), Fixed::from_ticks(-400));
+        assert_eq!(min.next(&bar(350)), Fixed::from_ticks(-400));
+        assert_eq!(min.next(&bar(200)), Fixed::from_ticks(200));
+        assert_eq!(min.next(&bar(300)), Fixed::from_ticks(200));
+    }
+
     #[test]
     fn test_reset() {
         let mut min = Minimum::<f64>::new(10).unwrap();
@@ -0,0 +1,258 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::errors::*;
+use crate::helpers::{parse_param, parse_params};
+use crate::indicators::Extreme;
+use crate::rolling::{Extremum, RollingExtremum};
+use crate::{Low, Next, Peek, Raw, Reset, UpdateLast, Warmup};
+
+/// Returns the lowest value in a given time frame, with the number of periods since it.
+///
+/// This is what Aroon down, breakdown age filters or Donchian channel exits are built on. The most
+/// recent period wins on ties, so the age is 0 when the latest input equals the lowest one.
+///
+/// # Parameters
+///
+/// * _n_ - size of the time frame (integer greater than 0). Default value is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{Extreme, MinimumAge};
+/// use ta::Next;
+///
+/// let mut min = MinimumAge::<f64>::new(3).unwrap();
+/// assert_eq!(min.next(3.0), Extreme { value: 3.0, age: 0, index: 0 });
+/// assert_eq!(min.next(5.0), Extreme { value: 3.0, age: 1, index: 0 });
+/// assert_eq!(min.next(6.0), Extreme { value: 3.0, age: 2, index: 0 });
+/// assert_eq!(min.next(6.0), Extreme { value: 5.0, age: 2, index: 1 });
+///
+/// // Aroon down over 2 periods looks at the last 3 ones
+/// let aroon_down = 100.0 * (2 - min.next(4.0).age) as f64 / 2.0;
+/// assert_eq!(aroon_down, 100.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MinimumAge<T> {
+    window: RollingExtremum<T>,
+}
+
+impl<T> MinimumAge<T>
+where
+    T: Copy + PartialOrd,
+{
+    pub fn new(n: u32) -> Result<Self> {
+        let n = n as usize;
+
+        if n == 0 {
+            return Err(Error::invalid_parameter(
+                "MIN_AGE",
+                "n",
+                n,
+                "greater than 0",
+            ));
+        }
+
+        Ok(Self {
+            window: RollingExtremum::new(Extremum::Min, n),
+        })
+    }
+
+    // Extreme of the window, only once an input was pushed
+    fn output(&self) -> Extreme<T> {
+        Extreme::new(
+            self.window
+                .extreme()
+                .expect("window is non-empty after an input"),
+            self.window.count() - 1,
+        )
+    }
+}
+
+impl<T> Next<T, Raw> for MinimumAge<T>
+where
+    T: Copy + PartialOrd,
+{
+    type Output = Extreme<T>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        self.window.push(input);
+        self.output()
+    }
+}
+
+impl<'a, U, T> Next<&'a U, T> for MinimumAge<T>
+where
+    U: Low<T>,
+    T: Copy + PartialOrd,
+{
+    type Output = Extreme<T>;
+
+    fn next(&mut self, input: &'a U) -> Self::Output {
+        self.next(input.low())
+    }
+}
+
+impl<T> UpdateLast<T, Raw> for MinimumAge<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        self.window.update_last(input);
+        self.output()
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for MinimumAge<T>
+where
+    U: Low<T>,
+    T: Copy + PartialOrd,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.low())
+    }
+}
+
+impl<T> Peek<T, Raw> for MinimumAge<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        Extreme::new(self.window.peek_extreme(input), self.window.count())
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for MinimumAge<T>
+where
+    U: Low<T>,
+    T: Copy + PartialOrd,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.low())
+    }
+}
+
+impl<T> Reset for MinimumAge<T> {
+    fn reset(&mut self) {
+        self.window.reset();
+    }
+}
+
+impl<T> Warmup for MinimumAge<T> {
+    fn warmup_period(&self) -> usize {
+        self.window.length()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
+    }
+}
+
+impl<T> Default for MinimumAge<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl<T> fmt::Display for MinimumAge<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MIN_AGE({})", self.window.length())
+    }
+}
+
+impl<T> FromStr for MinimumAge<T>
+where
+    T: Copy + PartialOrd,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "MIN_AGE", 1)?;
+        let n = parse_param(s, params[0])?;
+        Self::new(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::Minimum;
+    use crate::test_helper::*;
+
+    test_indicator!(MinimumAge);
+
+    fn extreme(value: f64, age: usize, index: usize) -> Extreme<f64> {
+        Extreme { value, age, index }
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(MinimumAge::<f64>::new(0).is_err());
+        assert!(MinimumAge::<f64>::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut min = MinimumAge::<f64>::new(3).unwrap();
+
+        assert_eq!(min.next(4.0), extreme(4.0, 0, 0));
+        assert_eq!(min.next(5.2), extreme(4.0, 1, 0));
+        assert_eq!(min.next(3.0), extreme(3.0, 0, 2));
+        assert_eq!(min.next(5.0), extreme(3.0, 1, 2));
+        assert_eq!(min.next(3.0), extreme(3.0, 0, 4));
+        assert_eq!(min.next(6.0), extreme(3.0, 1, 4));
+        assert_eq!(min.next(7.0), extreme(3.0, 2, 4));
+        assert_eq!(min.next(8.0), extreme(6.0, 2, 5));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        fn bar(low: f64) -> Bar {
+            Bar::new().low(low)
+        }
+
+        let mut min = MinimumAge::<f64>::new(2).unwrap();
+
+        assert_eq!(min.next(&bar(1.1)), extreme(1.1, 0, 0));
+        assert_eq!(min.next(&bar(0.5)), extreme(0.5, 0, 1));
+        assert_eq!(min.next(&bar(0.8)), extreme(0.5, 1, 1));
+        assert_eq!(min.next(&bar(2.0)), extreme(0.8, 1, 2));
+    }
+
+    #[test]
+    fn test_same_value_as_minimum() {
+        let mut min = Minimum::<f64>::new(4).unwrap();
+        let mut min_age = MinimumAge::<f64>::new(4).unwrap();
+
+        for i in 0..40 {
+            let input = ((i * 7) % 11) as f64;
+            assert_eq!(min_age.next(input).value, min.next(input));
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut min = MinimumAge::<f64>::new(100).unwrap();
+        min.next(4.0);
+        min.next(1.0);
+        assert_eq!(min.next(4.0), extreme(1.0, 1, 1));
+
+        min.reset();
+        assert_eq!(min.next(4.0), extreme(4.0, 0, 0));
+    }
+
+    #[test]
+    fn test_default() {
+        MinimumAge::<f64>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let indicator = MinimumAge::<f64>::new(7).unwrap();
+        assert_eq!(format!("{}", indicator), "MIN_AGE(7)");
+    }
+}
@@ -8,7 +8,7 @@ mod smoothed_or_modified_moving_average;
 pub use self::smoothed_or_modified_moving_average::SmoothedOrModifiedMovingAverage;
 
 mod standard_deviation;
-pub use self::standard_deviation::StandardDeviation;
+pub use self::standard_deviation::{DeviationType, StandardDeviation, UpdateMode};
 
 mod relative_strength_index;
 pub use self::relative_strength_index::RelativeStrengthIndex;
@@ -22,6 +22,12 @@ pub use self::minimum::Minimum;
 mod maximum;
 pub use self::maximum::Maximum;
 
+mod minimum_age;
+pub use self::minimum_age::MinimumAge;
+
+mod maximum_age;
+pub use self::maximum_age::{Extreme, MaximumAge};
+
 mod fast_stochastic;
 pub use self::fast_stochastic::FastStochastic;
 
@@ -35,7 +41,9 @@ mod average_true_range;
 pub use self::average_true_range::AverageTrueRange;
 
 mod moving_average_convergence_divergence;
-pub use self::moving_average_convergence_divergence::MovingAverageConvergenceDivergence;
+pub use self::moving_average_convergence_divergence::{
+    MacdOutput, MovingAverageConvergenceDivergence, MovingAverageType,
+};
 
 mod efficiency_ratio;
 pub use self::efficiency_ratio::EfficiencyRatio;
@@ -52,8 +60,20 @@ pub use self::money_flow_index::MoneyFlowIndex;
 mod on_balance_volume;
 pub use self::on_balance_volume::OnBalanceVolume;
 
+mod volume_weighted_average_price;
+pub use self::volume_weighted_average_price::VolumeWeightedAveragePrice;
+
 mod heikin_ashi;
 pub use self::heikin_ashi::{HeikinAshi, HeikinAshiCandle, HeikinAshiColor};
 
 mod ichimoku;
-pub use self::ichimoku::{Ichimoku, IchimokuOutput};
+pub use self::ichimoku::{CloudPosition, Ichimoku, IchimokuOutput, Kumo, KumoColor};
+
+mod ichimoku_signals;
+pub use self::ichimoku_signals::{
+    IchimokuSignal, IchimokuSignalKind, IchimokuSignals, IchimokuSignalsOutput, SignalDirection,
+    SignalStrength,
+};
+
+mod any_indicator;
+pub use self::any_indicator::{AnyIndicator, AnyOutput};
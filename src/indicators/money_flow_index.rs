@@ -1,11 +1,13 @@
-use std::collections::VecDeque;
-use std::fmt;
-use std::ops::{AddAssign, Div, Mul, SubAssign};
+use alloc::collections::VecDeque;
+use core::fmt;
+use core::ops::{AddAssign, Div, Mul, SubAssign};
+use core::str::FromStr;
 
 use num_traits::{FromPrimitive, Signed, Zero};
 
 use crate::errors::*;
-use crate::{Close, High, Low, Next, Reset, Volume};
+use crate::helpers::{parse_param, parse_params};
+use crate::{Close, High, Low, Next, Peek, Reset, UpdateLast, Volume, Warmup};
 
 /// Money Flow Index (MFI).
 ///
@@ -55,13 +57,19 @@ use crate::{Close, High, Low, Next, Reset, Volume};
 /// * [Money Flow Index, stockcharts](https://stockcharts.com/school/doku.php?id=chart_school:technical_indicators:money_flow_index_mfi)
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MoneyFlowIndex<T> {
     n: u32,
     money_flows: VecDeque<T>,
     prev_typical_price: T,
     total_positive_money_flow: T,
     total_absolute_money_flow: T,
-    is_new: bool,
+    count: usize,
+    // State before the latest input, restored by `update_last`
+    saved_typical_price: T,
+    saved_positive_money_flow: T,
+    saved_absolute_money_flow: T,
+    evicted_money_flow: Option<T>,
 }
 
 impl<T> MoneyFlowIndex<T>
@@ -70,15 +78,19 @@ where
 {
     pub fn new(n: u32) -> Result<Self> {
         match n {
-            0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
+            0 => Err(Error::invalid_parameter("MFI", "n", n, "greater than 0")),
             _ => {
                 let indicator = Self {
-                    n: n,
+                    n,
                     money_flows: VecDeque::with_capacity(n as usize + 1),
                     prev_typical_price: T::zero(),
                     total_positive_money_flow: T::zero(),
                     total_absolute_money_flow: T::zero(),
-                    is_new: true,
+                    count: 0,
+                    saved_typical_price: T::zero(),
+                    saved_positive_money_flow: T::zero(),
+                    saved_absolute_money_flow: T::zero(),
+                    evicted_money_flow: None,
                 };
                 Ok(indicator)
             }
@@ -86,9 +98,8 @@ where
     }
 }
 
-impl<'a, U, T> Next<&'a U, T> for MoneyFlowIndex<T>
+impl<T> MoneyFlowIndex<T>
 where
-    U: High<T> + Low<T> + Close<T> + Volume<T>,
     T: Copy
         + Zero
         + Signed
@@ -99,51 +110,176 @@ where
         + SubAssign
         + PartialOrd,
 {
-    type Output = T;
+    fn typical_price<U>(input: &U) -> T
+    where
+        U: High<T> + Low<T> + Close<T>,
+    {
+        (input.high() + input.low() + input.close()) / T::from_u32(3).expect("constant fits in T")
+    }
 
-    //impl<'a, T: High + Low + Close + Volume> Next<&'a T> for MoneyFlowIndex {
-    //    type Output = f64;
+    // Without any volume in the window, there is no pressure either way
+    fn index(total_positive_money_flow: T, total_absolute_money_flow: T) -> T {
+        if total_absolute_money_flow.is_zero() {
+            T::from_u32(50).expect("constant fits in T")
+        } else {
+            (total_positive_money_flow / total_absolute_money_flow)
+                * T::from_u32(100).expect("constant fits in T")
+        }
+    }
 
-    fn next(&mut self, input: &'a U) -> T {
-        let typical_price =
-            (input.high() + input.low() + input.close()) / T::from_u32(3).expect("Woot ?");
+    // Returns the signed money flow of input, and the totals once it is pushed in the window.
+    fn add_money_flow<U>(&self, typical_price: T, input: &U) -> (T, T, T)
+    where
+        U: Volume<T>,
+    {
+        let mut total_positive_money_flow = self.total_positive_money_flow;
+        let mut total_absolute_money_flow = self.total_absolute_money_flow;
+        let money_flow = typical_price * input.volume();
+
+        let signed_money_flow = if typical_price >= self.prev_typical_price {
+            total_positive_money_flow += money_flow;
+            money_flow
+        } else {
+            -money_flow
+        };
+
+        total_absolute_money_flow += money_flow;
+
+        if self.money_flows.len() == (self.n as usize) {
+            let old_signed_money_flow = self.money_flows[0];
+            if old_signed_money_flow > T::zero() {
+                total_positive_money_flow -= old_signed_money_flow;
+                total_absolute_money_flow -= old_signed_money_flow;
+            } else {
+                // it is actually subtraction, because old_signed_money_flow is negative
+                total_absolute_money_flow += old_signed_money_flow;
+            }
+        }
+
+        (
+            signed_money_flow,
+            total_positive_money_flow,
+            total_absolute_money_flow,
+        )
+    }
+
+    fn money_flow_index<U>(&mut self, input: &U) -> T
+    where
+        U: High<T> + Low<T> + Close<T> + Volume<T>,
+    {
+        let typical_price = Self::typical_price(input);
+
+        self.saved_typical_price = self.prev_typical_price;
+        self.saved_positive_money_flow = self.total_positive_money_flow;
+        self.saved_absolute_money_flow = self.total_absolute_money_flow;
+        self.evicted_money_flow = None;
 
-        if self.is_new {
+        if self.count == 1 {
             // money flow is 0, because without having previous typical_price
             // it is not possible to determine is it positive or negative.
             self.money_flows.push_back(T::zero());
             self.prev_typical_price = typical_price;
-            self.is_new = false;
-            return T::from_u32(50).expect("Woot ?");
+            T::from_u32(50).expect("constant fits in T")
         } else {
-            let money_flow = typical_price * input.volume();
-
-            let signed_money_flow = if typical_price >= self.prev_typical_price {
-                self.total_positive_money_flow += money_flow;
-                money_flow
-            } else {
-                -money_flow
-            };
-
-            self.total_absolute_money_flow += money_flow;
+            let (signed_money_flow, total_positive_money_flow, total_absolute_money_flow) =
+                self.add_money_flow(typical_price, input);
 
             if self.money_flows.len() == (self.n as usize) {
-                let old_signed_money_flow = self.money_flows.pop_front().unwrap();
-                if old_signed_money_flow > T::zero() {
-                    self.total_positive_money_flow -= old_signed_money_flow;
-                    self.total_absolute_money_flow -= old_signed_money_flow;
-                } else {
-                    // it is actually subtraction, because old_signed_money_flow is negative
-                    self.total_absolute_money_flow += old_signed_money_flow;
-                }
+                self.evicted_money_flow = self.money_flows.pop_front();
             }
 
             self.money_flows.push_back(signed_money_flow);
             self.prev_typical_price = typical_price;
+            self.total_positive_money_flow = total_positive_money_flow;
+            self.total_absolute_money_flow = total_absolute_money_flow;
+
+            Self::index(
+                self.total_positive_money_flow,
+                self.total_absolute_money_flow,
+            )
+        }
+    }
+}
+
+impl<'a, U, T> Next<&'a U, T> for MoneyFlowIndex<T>
+where
+    U: High<T> + Low<T> + Close<T> + Volume<T>,
+    T: Copy
+        + Zero
+        + Signed
+        + FromPrimitive
+        + Div<Output = T>
+        + Mul<Output = T>
+        + AddAssign
+        + SubAssign
+        + PartialOrd,
+{
+    type Output = T;
+
+    //impl<'a, T: High + Low + Close + Volume> Next<&'a T> for MoneyFlowIndex {
+    //    type Output = f64;
+
+    fn next(&mut self, input: &'a U) -> T {
+        if self.count < self.warmup_period() {
+            self.count += 1;
+        }
+        self.money_flow_index(input)
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for MoneyFlowIndex<T>
+where
+    U: High<T> + Low<T> + Close<T> + Volume<T>,
+    T: Copy
+        + Zero
+        + Signed
+        + FromPrimitive
+        + Div<Output = T>
+        + Mul<Output = T>
+        + AddAssign
+        + SubAssign
+        + PartialOrd,
+{
+    fn peek(&self, input: &'a U) -> T {
+        if self.count == 0 {
+            return T::from_u32(50).expect("constant fits in T");
+        }
+
+        let typical_price = Self::typical_price(input);
+        let (_, total_positive_money_flow, total_absolute_money_flow) =
+            self.add_money_flow(typical_price, input);
+
+        Self::index(total_positive_money_flow, total_absolute_money_flow)
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for MoneyFlowIndex<T>
+where
+    U: High<T> + Low<T> + Close<T> + Volume<T>,
+    T: Copy
+        + Zero
+        + Signed
+        + FromPrimitive
+        + Div<Output = T>
+        + Mul<Output = T>
+        + AddAssign
+        + SubAssign
+        + PartialOrd,
+{
+    fn update_last(&mut self, input: &'a U) -> T {
+        if self.count == 0 {
+            return self.next(input);
+        }
 
-            (self.total_positive_money_flow / self.total_absolute_money_flow)
-                * T::from_u32(100).expect("Woot ?")
+        self.money_flows.pop_back();
+        if let Some(money_flow) = self.evicted_money_flow {
+            self.money_flows.push_front(money_flow);
         }
+        self.prev_typical_price = self.saved_typical_price;
+        self.total_positive_money_flow = self.saved_positive_money_flow;
+        self.total_absolute_money_flow = self.saved_absolute_money_flow;
+
+        self.money_flow_index(input)
     }
 }
 
@@ -170,6 +306,19 @@ impl<T> fmt::Display for MoneyFlowIndex<T> {
     }
 }
 
+impl<T> FromStr for MoneyFlowIndex<T>
+where
+    T: Zero,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "MFI", 1)?;
+        let n = parse_param(s, params[0])?;
+        Self::new(n)
+    }
+}
+
 impl<T> Reset for MoneyFlowIndex<T>
 where
     T: Zero,
@@ -179,7 +328,22 @@ where
         self.prev_typical_price = T::zero();
         self.total_positive_money_flow = T::zero();
         self.total_absolute_money_flow = T::zero();
-        self.is_new = true;
+        self.count = 0;
+        self.saved_typical_price = T::zero();
+        self.saved_positive_money_flow = T::zero();
+        self.saved_absolute_money_flow = T::zero();
+        self.evicted_money_flow = None;
+    }
+}
+
+impl<T> Warmup for MoneyFlowIndex<T> {
+    fn warmup_period(&self) -> usize {
+        // The first input only gives the previous typical price to compare with
+        self.n as usize + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count == self.warmup_period()
     }
 }
 
@@ -187,6 +351,7 @@ where
 mod tests {
     use super::*;
     use crate::test_helper::*;
+    use crate::Compute;
 
     #[test]
     fn test_new() {
@@ -255,6 +420,182 @@ mod tests {
         assert_eq!(round(mfi.next(&bar6)), 44.444);
     }
 
+    #[test]
+    fn test_zero_volume() {
+        let mut mfi = MoneyFlowIndex::new(3).unwrap();
+        let bar = Bar::new().high(3).low(1).close(2).volume(0.0);
+        assert_eq!(mfi.next(&bar), 50.0);
+        assert_eq!(mfi.peek(&bar), 50.0);
+        assert_eq!(mfi.next(&bar), 50.0);
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_zero_volume_decimal() {
+        use crate::DataItem;
+        use rust_decimal::Decimal;
+
+        let bar = DataItem::builder()
+            .open(Decimal::from(2))
+            .high(Decimal::from(3))
+            .low(Decimal::from(1))
+            .close(Decimal::from(2))
+            .volume(Decimal::ZERO)
+            .build()
+            .unwrap();
+
+        let mut mfi = MoneyFlowIndex::<Decimal>::new(3).unwrap();
+        for _ in 0..5 {
+            assert_eq!(mfi.peek(&bar), Decimal::from(50));
+            assert_eq!(mfi.next(&bar), Decimal::from(50));
+        }
+    }
+
+    #[test]
+    fn test_zero_volume_fixed() {
+        use crate::{DataItem, Fixed};
+
+        let bar = DataItem::builder()
+            .open(Fixed::<2>::from_ticks(200))
+            .high(Fixed::from_ticks(300))
+            .low(Fixed::from_ticks(100))
+            .close(Fixed::from_ticks(200))
+            .volume(Fixed::from_ticks(0))
+            .build()
+            .unwrap();
+
+        let mut mfi = MoneyFlowIndex::<Fixed<2>>::new(3).unwrap();
+        for _ in 0..5 {
+            assert_eq!(mfi.peek(&bar), Fixed::from_ticks(5000));
+            assert_eq!(mfi.next(&bar), Fixed::from_ticks(5000));
+        }
+    }
+
+    #[test]
+    fn test_compute() {
+        let bars = vec![
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(1000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(2000.0),
+            Bar::new().open(8).high(9).low(7).close(8).volume(3000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(4000.0),
+            Bar::new().open(4).high(5).low(3).close(4.5).volume(5000.0),
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(6000.0),
+        ];
+
+        let mut mfi = MoneyFlowIndex::<f64>::new(3).unwrap();
+        let expected: Vec<_> = bars.iter().map(|bar| mfi.next(bar)).collect();
+
+        mfi.reset();
+        assert_eq!(mfi.compute(&bars), expected);
+    }
+
+    #[test]
+    fn test_update_last() {
+        let bars = [
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(1000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(2000.0),
+            Bar::new().open(8).high(9).low(7).close(8).volume(3000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(4000.0),
+            Bar::new().open(4).high(5).low(3).close(4.5).volume(5000.0),
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(6000.0),
+        ];
+
+        let mut mfi = MoneyFlowIndex::<f64>::new(3).unwrap();
+        let expected: Vec<_> = bars.iter().map(|bar| mfi.next(bar)).collect();
+
+        mfi.reset();
+        for (i, bar) in bars.iter().enumerate() {
+            mfi.next(&bars[(i + 3) % bars.len()]);
+            assert_eq!(mfi.update_last(bar), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_peek() {
+        let bars = [
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(1000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(2000.0),
+            Bar::new().open(8).high(9).low(7).close(8).volume(3000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(4000.0),
+            Bar::new().open(4).high(5).low(3).close(4.5).volume(5000.0),
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(6000.0),
+        ];
+
+        let mut mfi = MoneyFlowIndex::<f64>::new(3).unwrap();
+        let mut expected = MoneyFlowIndex::<f64>::new(3).unwrap();
+
+        for (i, bar) in bars.iter().enumerate() {
+            mfi.peek(&bars[(i + 3) % bars.len()]);
+            let output = expected.next(bar);
+            assert_eq!(mfi.peek(bar), output);
+            assert_eq!(mfi.next(bar), output);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let bars = [
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(1000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(2000.0),
+            Bar::new().open(8).high(9).low(7).close(8).volume(3000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(4000.0),
+            Bar::new().open(4).high(5).low(3).close(4.5).volume(5000.0),
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(6000.0),
+        ];
+
+        let mut mfi = MoneyFlowIndex::<f64>::new(3).unwrap();
+        for bar in &bars[..4] {
+            mfi.next(bar);
+        }
+
+        let json = serde_json::to_string(&mfi).unwrap();
+        let mut restored: MoneyFlowIndex<f64> = serde_json::from_str(&json).unwrap();
+        for bar in &bars[4..] {
+            assert_eq!(restored.next(bar), mfi.next(bar));
+        }
+    }
+
     #[test]
     fn test_default() {
         MoneyFlowIndex::<f64>::default();
@@ -265,4 +606,13 @@ mod tests {
         let mfi = MoneyFlowIndex::<f64>::new(10).unwrap();
         assert_eq!(format!("{}", mfi), "MFI(10)");
     }
+
+    #[test]
+    fn test_from_str() {
+        let mfi: MoneyFlowIndex<f64> = "MFI(10)".parse().unwrap();
+        assert_eq!(format!("{}", mfi), "MFI(10)");
+
+        assert!("MFI(0)".parse::<MoneyFlowIndex<f64>>().is_err());
+        assert!("MFI()".parse::<MoneyFlowIndex<f64>>().is_err());
+    }
 }
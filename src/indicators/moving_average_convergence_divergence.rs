@@ -1,11 +1,16 @@
-use std::fmt;
-use std::ops::{Add, Div, Mul, Sub};
+use alloc::string::ToString;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Sub};
+use core::str::FromStr;
 
 use num_traits::{FromPrimitive, One, Zero};
 
 use crate::errors::*;
+use crate::helpers::{parse_param, parse_params};
 use crate::indicators::ExponentialMovingAverage as Ema;
-use crate::{Close, Next, Reset};
+use crate::indicators::SimpleMovingAverage as Sma;
+use crate::indicators::SmoothedOrModifiedMovingAverage as Smma;
+use crate::{Close, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// Moving average converge divergence (MACD).
 ///
@@ -21,6 +26,9 @@ use crate::{Close, Next, Reset};
 /// moving average (EMA), and a "slow" (longer period) EMA of the price series.
 /// The average series is an EMA of the MACD series itself.
 ///
+/// Each of the fast, slow and signal lines may use another
+/// [MovingAverageType](enum.MovingAverageType.html) instead of the EMA.
+///
 /// # Formula
 ///
 /// # Parameters
@@ -32,7 +40,7 @@ use crate::{Close, Next, Reset};
 /// # Example
 ///
 /// ```
-/// use ta::indicators::MovingAverageConvergenceDivergence as Macd;
+/// use ta::indicators::{MacdOutput, MovingAverageConvergenceDivergence as Macd};
 /// use ta::Next;
 ///
 /// let mut macd = Macd::<f64>::new(3, 6, 4).unwrap();
@@ -44,78 +52,399 @@ use crate::{Close, Next, Reset};
 /// assert_eq!(round(macd.next(6.7)), (1.15, 0.83, 0.32));
 /// assert_eq!(round(macd.next(6.5)), (0.94, 0.87, 0.07));
 ///
-/// fn round(nums: (f64, f64, f64)) -> (f64, f64, f64) {
-///     let n0 = (nums.0 * 100.0).round() / 100.0;
-///     let n1 = (nums.1 * 100.0).round() / 100.0;
-///     let n2 = (nums.2 * 100.0).round() / 100.0;
-///     (n0, n1, n2)
+/// fn round(out: MacdOutput<f64>) -> (f64, f64, f64) {
+///     let macd = (out.macd * 100.0).round() / 100.0;
+///     let signal = (out.signal * 100.0).round() / 100.0;
+///     let histogram = (out.histogram * 100.0).round() / 100.0;
+///     (macd, signal, histogram)
 /// }
 /// ```
+///
+/// With a simple moving average as the signal line:
+///
+/// ```
+/// use ta::indicators::{MovingAverageConvergenceDivergence as Macd, MovingAverageType};
+/// use ta::Next;
+///
+/// use MovingAverageType::{Ema, Sma};
+///
+/// let mut macd = Macd::<f64>::with_ma_types(12, 26, 9, Ema, Ema, Sma).unwrap();
+/// assert_eq!(macd.to_string(), "MACD(12, 26, 9, EMA, EMA, SMA)");
+///
+/// let out = macd.next(10.0);
+/// assert_eq!(out.histogram, out.macd - out.signal);
+/// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MovingAverageConvergenceDivergence<T> {
-    fast_ema: Ema<T>,
-    slow_ema: Ema<T>,
-    signal_ema: Ema<T>,
+    fast_ma: MovingAverage<T>,
+    slow_ma: MovingAverage<T>,
+    signal_ma: MovingAverage<T>,
+    count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MacdOutput<T> {
+    pub macd: T,
+    pub signal: T,
+    pub histogram: T,
+}
+
+/// Type of the moving averages of a
+/// [MovingAverageConvergenceDivergence](struct.MovingAverageConvergenceDivergence.html).
+///
+/// It is displayed and parsed as `EMA`, `SMA` or `SMMA`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MovingAverageType {
+    /// [ExponentialMovingAverage](struct.ExponentialMovingAverage.html)
+    #[default]
+    Ema,
+    /// [SimpleMovingAverage](struct.SimpleMovingAverage.html)
+    Sma,
+    /// [SmoothedOrModifiedMovingAverage](struct.SmoothedOrModifiedMovingAverage.html)
+    Smma,
+}
+
+impl fmt::Display for MovingAverageType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MovingAverageType::Ema => write!(f, "EMA"),
+            MovingAverageType::Sma => write!(f, "SMA"),
+            MovingAverageType::Smma => write!(f, "SMMA"),
+        }
+    }
+}
+
+impl FromStr for MovingAverageType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "EMA" => Ok(MovingAverageType::Ema),
+            "SMA" => Ok(MovingAverageType::Sma),
+            "SMMA" => Ok(MovingAverageType::Smma),
+            spec => Err(Error::InvalidSpec {
+                spec: spec.to_string(),
+            }),
+        }
+    }
+}
+
+// Moving average of the selected type
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum MovingAverage<T> {
+    Ema(Ema<T>),
+    Sma(Sma<T>),
+    Smma(Smma<T>),
+}
+
+impl<T> MovingAverage<T>
+where
+    T: Clone + Zero + One + Div<Output = T> + FromPrimitive,
+{
+    fn new(ma_type: MovingAverageType, length: u32) -> Result<Self> {
+        Ok(match ma_type {
+            MovingAverageType::Ema => MovingAverage::Ema(Ema::new(length)?),
+            MovingAverageType::Sma => MovingAverage::Sma(Sma::new(length)?),
+            MovingAverageType::Smma => MovingAverage::Smma(Smma::new(length)?),
+        })
+    }
+}
+
+impl<T> MovingAverage<T> {
+    fn ma_type(&self) -> MovingAverageType {
+        match *self {
+            MovingAverage::Ema(_) => MovingAverageType::Ema,
+            MovingAverage::Sma(_) => MovingAverageType::Sma,
+            MovingAverage::Smma(_) => MovingAverageType::Smma,
+        }
+    }
+
+    fn length(&self) -> u32 {
+        match *self {
+            MovingAverage::Ema(ref ma) => ma.length(),
+            MovingAverage::Sma(ref ma) => ma.length(),
+            MovingAverage::Smma(ref ma) => ma.length(),
+        }
+    }
+
+    fn warmup_period(&self) -> usize {
+        match *self {
+            MovingAverage::Ema(ref ma) => ma.warmup_period(),
+            MovingAverage::Sma(ref ma) => ma.warmup_period(),
+            MovingAverage::Smma(ref ma) => ma.warmup_period(),
+        }
+    }
+}
+
+impl<T> MovingAverage<T>
+where
+    T: Copy
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + FromPrimitive,
+{
+    fn next(&mut self, input: T) -> T {
+        match *self {
+            MovingAverage::Ema(ref mut ma) => ma.next(input),
+            MovingAverage::Sma(ref mut ma) => ma.next(input),
+            MovingAverage::Smma(ref mut ma) => ma.next(input),
+        }
+    }
+
+    fn update_last(&mut self, input: T) -> T {
+        match *self {
+            MovingAverage::Ema(ref mut ma) => ma.update_last(input),
+            MovingAverage::Sma(ref mut ma) => ma.update_last(input),
+            MovingAverage::Smma(ref mut ma) => ma.update_last(input),
+        }
+    }
+
+    fn peek(&self, input: T) -> T {
+        match *self {
+            MovingAverage::Ema(ref ma) => ma.peek(input),
+            MovingAverage::Sma(ref ma) => ma.peek(input),
+            MovingAverage::Smma(ref ma) => ma.peek(input),
+        }
+    }
+}
+
+impl<T> MovingAverage<T>
+where
+    T: Zero,
+{
+    fn reset(&mut self) {
+        match *self {
+            MovingAverage::Ema(ref mut ma) => ma.reset(),
+            MovingAverage::Sma(ref mut ma) => ma.reset(),
+            MovingAverage::Smma(ref mut ma) => ma.reset(),
+        }
+    }
 }
 
 impl<T> MovingAverageConvergenceDivergence<T>
 where
-    T: Zero + One + Div<Output = T> + FromPrimitive,
+    T: Clone + Zero + One + Div<Output = T> + FromPrimitive,
 {
     pub fn new(fast_length: u32, slow_length: u32, signal_length: u32) -> Result<Self> {
+        Self::with_ma_types(
+            fast_length,
+            slow_length,
+            signal_length,
+            MovingAverageType::Ema,
+            MovingAverageType::Ema,
+            MovingAverageType::Ema,
+        )
+    }
+
+    /// Creates a MACD whose fast, slow and signal lines are moving averages of type `fast_ma`,
+    /// `slow_ma` and `signal_ma`.
+    pub fn with_ma_types(
+        fast_length: u32,
+        slow_length: u32,
+        signal_length: u32,
+        fast_ma: MovingAverageType,
+        slow_ma: MovingAverageType,
+        signal_ma: MovingAverageType,
+    ) -> Result<Self> {
+        let lengths = [
+            ("fast_length", fast_length),
+            ("slow_length", slow_length),
+            ("signal_length", signal_length),
+        ];
+        for &(name, length) in lengths.iter() {
+            if length == 0 {
+                return Err(Error::invalid_parameter(
+                    "MACD",
+                    name,
+                    length,
+                    "greater than 0",
+                ));
+            }
+        }
         let indicator = Self {
-            fast_ema: Ema::<T>::new(fast_length)?,
-            slow_ema: Ema::<T>::new(slow_length)?,
-            signal_ema: Ema::<T>::new(signal_length)?,
+            fast_ma: MovingAverage::new(fast_ma, fast_length)?,
+            slow_ma: MovingAverage::new(slow_ma, slow_length)?,
+            signal_ma: MovingAverage::new(signal_ma, signal_length)?,
+            count: 0,
         };
         Ok(indicator)
     }
 }
 
-impl<T> Next<T, !> for MovingAverageConvergenceDivergence<T>
+impl<T> Next<T, Raw> for MovingAverageConvergenceDivergence<T>
 where
-    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    T: Copy
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + FromPrimitive,
 {
-    type Output = (T, T, T);
+    type Output = MacdOutput<T>;
 
     fn next(&mut self, input: T) -> Self::Output {
-        let fast_val = self.fast_ema.next(input);
-        let slow_val = self.slow_ema.next(input);
+        let fast_val = self.fast_ma.next(input);
+        let slow_val = self.slow_ma.next(input);
 
         let macd = fast_val - slow_val;
-        let signal = self.signal_ema.next(macd);
+        let signal = self.signal_ma.next(macd);
         let histogram = macd - signal;
 
-        (macd, signal, histogram)
+        if self.count < self.warmup_period() {
+            self.count += 1;
+        }
+
+        MacdOutput {
+            macd,
+            signal,
+            histogram,
+        }
     }
 }
 
 impl<'a, U, T> Next<&'a U, T> for MovingAverageConvergenceDivergence<T>
 where
     U: Close<T>,
-    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    T: Copy
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + FromPrimitive,
 {
-    type Output = (T, T, T);
+    type Output = MacdOutput<T>;
 
     fn next(&mut self, input: &'a U) -> Self::Output {
         self.next(input.close())
     }
 }
 
+impl<T> UpdateLast<T, Raw> for MovingAverageConvergenceDivergence<T>
+where
+    T: Copy
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + FromPrimitive,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        if self.count == 0 {
+            return self.next(input);
+        }
+        let fast_val = self.fast_ma.update_last(input);
+        let slow_val = self.slow_ma.update_last(input);
+
+        let macd = fast_val - slow_val;
+        let signal = self.signal_ma.update_last(macd);
+        let histogram = macd - signal;
+
+        MacdOutput {
+            macd,
+            signal,
+            histogram,
+        }
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for MovingAverageConvergenceDivergence<T>
+where
+    U: Close<T>,
+    T: Copy
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + FromPrimitive,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.close())
+    }
+}
+
+impl<T> Peek<T, Raw> for MovingAverageConvergenceDivergence<T>
+where
+    T: Copy
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + FromPrimitive,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        let fast_val = self.fast_ma.peek(input);
+        let slow_val = self.slow_ma.peek(input);
+
+        let macd = fast_val - slow_val;
+        let signal = self.signal_ma.peek(macd);
+        let histogram = macd - signal;
+
+        MacdOutput {
+            macd,
+            signal,
+            histogram,
+        }
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for MovingAverageConvergenceDivergence<T>
+where
+    U: Close<T>,
+    T: Copy
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + FromPrimitive,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.close())
+    }
+}
+
 impl<T> Reset for MovingAverageConvergenceDivergence<T>
 where
     T: Zero,
 {
     fn reset(&mut self) {
-        self.fast_ema.reset();
-        self.slow_ema.reset();
-        self.signal_ema.reset();
+        self.fast_ma.reset();
+        self.slow_ma.reset();
+        self.signal_ma.reset();
+        self.count = 0;
+    }
+}
+
+impl<T> Warmup for MovingAverageConvergenceDivergence<T> {
+    fn warmup_period(&self) -> usize {
+        // The signal line is meaningful once it has smoothed enough meaningful MACD values
+        let macd_period = self
+            .fast_ma
+            .warmup_period()
+            .max(self.slow_ma.warmup_period());
+        macd_period + self.signal_ma.warmup_period() - 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count == self.warmup_period()
     }
 }
 
 impl<T> Default for MovingAverageConvergenceDivergence<T>
 where
-    T: Zero + One + Div<Output = T> + FromPrimitive,
+    T: Clone + Zero + One + Div<Output = T> + FromPrimitive,
 {
     fn default() -> Self {
         Self::new(12, 26, 9).unwrap()
@@ -126,10 +455,57 @@ impl<T> fmt::Display for MovingAverageConvergenceDivergence<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "MACD({}, {}, {})",
-            self.fast_ema.length(),
-            self.slow_ema.length(),
-            self.signal_ema.length()
+            "MACD({}, {}, {}",
+            self.fast_ma.length(),
+            self.slow_ma.length(),
+            self.signal_ma.length()
+        )?;
+        // EMAs are implied
+        let ma_types = [
+            self.fast_ma.ma_type(),
+            self.slow_ma.ma_type(),
+            self.signal_ma.ma_type(),
+        ];
+        if ma_types
+            .iter()
+            .any(|&ma_type| ma_type != MovingAverageType::Ema)
+        {
+            write!(f, ", {}, {}, {}", ma_types[0], ma_types[1], ma_types[2])?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<T> FromStr for MovingAverageConvergenceDivergence<T>
+where
+    T: Clone + Zero + One + Div<Output = T> + FromPrimitive,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "MACD", 3).or_else(|_| parse_params(s, "MACD", 6))?;
+        let fast_length = parse_param(s, params[0])?;
+        let slow_length = parse_param(s, params[1])?;
+        let signal_length = parse_param(s, params[2])?;
+        let (fast_ma, slow_ma, signal_ma) = match params.len() {
+            6 => (
+                parse_param(s, params[3])?,
+                parse_param(s, params[4])?,
+                parse_param(s, params[5])?,
+            ),
+            _ => (
+                MovingAverageType::Ema,
+                MovingAverageType::Ema,
+                MovingAverageType::Ema,
+            ),
+        };
+        Self::with_ma_types(
+            fast_length,
+            slow_length,
+            signal_length,
+            fast_ma,
+            slow_ma,
+            signal_ma,
         )
     }
 }
@@ -142,10 +518,10 @@ mod tests {
 
     test_indicator!(Macd);
 
-    fn round(nums: (f64, f64, f64)) -> (f64, f64, f64) {
-        let n0 = (nums.0 * 100.0).round() / 100.0;
-        let n1 = (nums.1 * 100.0).round() / 100.0;
-        let n2 = (nums.2 * 100.0).round() / 100.0;
+    fn round(out: MacdOutput<f64>) -> (f64, f64, f64) {
+        let n0 = (out.macd * 100.0).round() / 100.0;
+        let n1 = (out.signal * 100.0).round() / 100.0;
+        let n2 = (out.histogram * 100.0).round() / 100.0;
         (n0, n1, n2)
     }
 
@@ -155,6 +531,17 @@ mod tests {
         assert!(Macd::<f64>::new(1, 0, 1).is_err());
         assert!(Macd::<f64>::new(1, 1, 0).is_err());
         assert!(Macd::<f64>::new(1, 1, 1).is_ok());
+
+        match Macd::<f64>::new(3, 0, 4) {
+            Err(Error::InvalidParameter {
+                indicator, name, ..
+            }) => assert_eq!((indicator, name), ("MACD", "slow_length")),
+            _ => panic!("expected an invalid parameter"),
+        }
+
+        let sma = MovingAverageType::Sma;
+        assert!(Macd::<f64>::with_ma_types(3, 6, 0, sma, sma, sma).is_err());
+        assert!(Macd::<f64>::with_ma_types(3, 6, 4, sma, sma, sma).is_ok());
     }
 
     #[test]
@@ -169,6 +556,48 @@ mod tests {
         assert_eq!(round(macd.next(6.5)), (0.94, 0.87, 0.07));
     }
 
+    #[test]
+    fn test_ma_types() {
+        let values = [2.0, 3.0, 4.2, 7.0, 6.7, 6.5, 5.0, 8.1];
+
+        let ma_types = [
+            MovingAverageType::Ema,
+            MovingAverageType::Sma,
+            MovingAverageType::Smma,
+        ];
+
+        for &fast_ma in &ma_types {
+            for &slow_ma in &ma_types {
+                for &signal_ma in &ma_types {
+                    let mut macd =
+                        Macd::with_ma_types(3, 6, 4, fast_ma, slow_ma, signal_ma).unwrap();
+                    let mut fast = MovingAverage::new(fast_ma, 3).unwrap();
+                    let mut slow = MovingAverage::new(slow_ma, 6).unwrap();
+                    let mut signal = MovingAverage::new(signal_ma, 4).unwrap();
+
+                    for &value in &values {
+                        let line = fast.next(value) - slow.next(value);
+                        let average = signal.next(line);
+                        let expected = MacdOutput {
+                            macd: line,
+                            signal: average,
+                            histogram: line - average,
+                        };
+                        assert_eq!(macd.next(value), expected);
+                    }
+                }
+            }
+        }
+
+        // SMAs average the inputs received so far, until they have received `length` of them
+        let sma = MovingAverageType::Sma;
+        let mut macd = Macd::<f64>::with_ma_types(3, 6, 4, sma, sma, sma).unwrap();
+        assert_eq!(round(macd.next(2.0)), (0.0, 0.0, 0.0));
+        assert_eq!(round(macd.next(3.0)), (0.0, 0.0, 0.0));
+        assert_eq!(round(macd.next(4.2)), (0.0, 0.0, 0.0));
+        assert_eq!(round(macd.next(7.0)), (0.68, 0.17, 0.51));
+    }
+
     #[test]
     fn test_reset() {
         let mut macd = Macd::<f64>::new(3, 6, 4).unwrap();
@@ -182,6 +611,14 @@ mod tests {
         assert_eq!(round(macd.next(3.0)), (0.21, 0.09, 0.13));
     }
 
+    #[test]
+    fn test_warmup() {
+        assert_eq!(Macd::<f64>::new(3, 6, 4).unwrap().warmup_period(), 9);
+        assert_eq!(Macd::<f64>::new(12, 26, 9).unwrap().warmup_period(), 34);
+        // fast and slow lines may be swapped
+        assert_eq!(Macd::<f64>::new(26, 12, 9).unwrap().warmup_period(), 34);
+    }
+
     #[test]
     fn test_default() {
         Macd::<f64>::default();
@@ -191,5 +628,24 @@ mod tests {
     fn test_display() {
         let indicator = Macd::<f64>::new(13, 30, 10).unwrap();
         assert_eq!(format!("{}", indicator), "MACD(13, 30, 10)");
+
+        let (ema, smma) = (MovingAverageType::Ema, MovingAverageType::Smma);
+        let indicator = Macd::<f64>::with_ma_types(13, 30, 10, smma, ema, ema).unwrap();
+        assert_eq!(format!("{}", indicator), "MACD(13, 30, 10, SMMA, EMA, EMA)");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let macd: Macd<f64> = "MACD(12, 26, 9, SMA, EMA, SMMA)".parse().unwrap();
+        assert_eq!(format!("{}", macd), "MACD(12, 26, 9, SMA, EMA, SMMA)");
+
+        let macd: Macd<f64> = "MACD(12, 26, 9, EMA, EMA, EMA)".parse().unwrap();
+        assert_eq!(format!("{}", macd), "MACD(12, 26, 9)");
+
+        assert!("MACD(12, 26, 9, EMA)".parse::<Macd<f64>>().is_err());
+        assert!("MACD(12, 26, 9, EMA, SMA)".parse::<Macd<f64>>().is_err());
+        assert!("MACD(12, 26, 9, WMA, EMA, SMA)"
+            .parse::<Macd<f64>>()
+            .is_err());
     }
 }
@@ -1,9 +1,12 @@
-use std::fmt;
-use std::ops::{Add, Sub};
+use core::fmt;
+use core::ops::{Add, Sub};
+use core::str::FromStr;
 
 use num_traits::Zero;
 
-use crate::{Close, Next, Reset, Volume};
+use crate::errors::*;
+use crate::helpers::parse_params;
+use crate::{Close, Next, Peek, Reset, UpdateLast, Volume, Warmup};
 
 /// On Balance Volume (OBV).
 ///
@@ -60,9 +63,14 @@ use crate::{Close, Next, Reset, Volume};
 /// * [On Balance Volume, stockcharts](https://stockcharts.com/school/doku.php?id=chart_school:technical_indicators:on_balance_volume_obv)
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OnBalanceVolume<T> {
     obv: T,
     prev_close: T,
+    is_new: bool,
+    // State before the latest input, restored by `update_last`
+    saved_obv: T,
+    saved_close: T,
 }
 
 impl<T> OnBalanceVolume<T>
@@ -73,6 +81,9 @@ where
         Self {
             obv: T::zero(),
             prev_close: T::zero(),
+            is_new: true,
+            saved_obv: T::zero(),
+            saved_close: T::zero(),
         }
     }
 }
@@ -85,13 +96,43 @@ where
     type Output = T;
 
     fn next(&mut self, input: &'a U) -> T {
+        self.saved_obv = self.obv;
+        self.saved_close = self.prev_close;
+
+        self.obv = self.peek(input);
+        self.prev_close = input.close();
+        self.is_new = false;
+        self.obv
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for OnBalanceVolume<T>
+where
+    U: Close<T> + Volume<T>,
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    fn peek(&self, input: &'a U) -> T {
         if input.close() > self.prev_close {
-            self.obv = self.obv + input.volume();
+            self.obv + input.volume()
         } else if input.close() < self.prev_close {
-            self.obv = self.obv - input.volume();
+            self.obv - input.volume()
+        } else {
+            self.obv
         }
-        self.prev_close = input.close();
-        self.obv
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for OnBalanceVolume<T>
+where
+    U: Close<T> + Volume<T>,
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    fn update_last(&mut self, input: &'a U) -> T {
+        if !self.is_new {
+            self.obv = self.saved_obv;
+            self.prev_close = self.saved_close;
+        }
+        self.next(input)
     }
 }
 
@@ -110,6 +151,18 @@ impl<T> fmt::Display for OnBalanceVolume<T> {
     }
 }
 
+impl<T> FromStr for OnBalanceVolume<T>
+where
+    T: Zero,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_params(s, "OBV", 0)?;
+        Ok(Self::new())
+    }
+}
+
 impl<T> Reset for OnBalanceVolume<T>
 where
     T: Zero,
@@ -117,6 +170,19 @@ where
     fn reset(&mut self) {
         self.obv = T::zero();
         self.prev_close = T::zero();
+        self.is_new = true;
+        self.saved_obv = T::zero();
+        self.saved_close = T::zero();
+    }
+}
+
+impl<T> Warmup for OnBalanceVolume<T> {
+    fn warmup_period(&self) -> usize {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        !self.is_new
     }
 }
 
@@ -124,6 +190,7 @@ where
 mod tests {
     use super::*;
     use crate::test_helper::*;
+    use crate::Compute;
 
     #[test]
     fn test_next_bar() {
@@ -146,6 +213,29 @@ mod tests {
         assert_eq!(obv.next(&bar4), -3000.0);
     }
 
+    #[test]
+    fn test_next_fixed() {
+        use crate::{DataItem, Fixed};
+
+        fn bar(close: i64, volume: i64) -> DataItem<Fixed<2>> {
+            let close = Fixed::from_ticks(close);
+            DataItem::builder()
+                .open(close)
+                .high(close)
+                .low(close)
+                .close(close)
+                .volume(Fixed::from_ticks(volume))
+                .build()
+                .unwrap()
+        }
+
+        let mut obv = OnBalanceVolume::<Fixed<2>>::new();
+        assert_eq!(obv.next(&bar(150, 100_000)), Fixed::from_ticks(100_000));
+        assert_eq!(obv.next(&bar(500, 500_000)), Fixed::from_ticks(600_000));
+        assert_eq!(obv.next(&bar(400, 900_000)), Fixed::from_ticks(-300_000));
+        assert_eq!(obv.next(&bar(400, 400_000)), Fixed::from_ticks(-300_000));
+    }
+
     #[test]
     fn test_reset() {
         let mut obv = OnBalanceVolume::<f64>::new();
@@ -165,6 +255,131 @@ mod tests {
         assert_eq!(obv.next(&bar3), 6000.0);
     }
 
+    #[test]
+    fn test_compute() {
+        let bars = vec![
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(1000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(2000.0),
+            Bar::new().open(8).high(9).low(7).close(8).volume(3000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(4000.0),
+            Bar::new().open(4).high(5).low(3).close(4.5).volume(5000.0),
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(6000.0),
+        ];
+
+        let mut obv = OnBalanceVolume::<f64>::new();
+        let expected: Vec<_> = bars.iter().map(|bar| obv.next(bar)).collect();
+
+        obv.reset();
+        assert_eq!(obv.compute(&bars), expected);
+    }
+
+    #[test]
+    fn test_update_last() {
+        let bars = [
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(1000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(2000.0),
+            Bar::new().open(8).high(9).low(7).close(8).volume(3000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(4000.0),
+            Bar::new().open(4).high(5).low(3).close(4.5).volume(5000.0),
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(6000.0),
+        ];
+
+        let mut obv = OnBalanceVolume::<f64>::new();
+        let expected: Vec<_> = bars.iter().map(|bar| obv.next(bar)).collect();
+
+        obv.reset();
+        for (i, bar) in bars.iter().enumerate() {
+            obv.next(&bars[(i + 3) % bars.len()]);
+            assert_eq!(obv.update_last(bar), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_peek() {
+        let bars = [
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(1000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(2000.0),
+            Bar::new().open(8).high(9).low(7).close(8).volume(3000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(4000.0),
+            Bar::new().open(4).high(5).low(3).close(4.5).volume(5000.0),
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(6000.0),
+        ];
+
+        let mut obv = OnBalanceVolume::<f64>::new();
+        let mut expected = OnBalanceVolume::<f64>::new();
+
+        for (i, bar) in bars.iter().enumerate() {
+            obv.peek(&bars[(i + 3) % bars.len()]);
+            let output = expected.next(bar);
+            assert_eq!(obv.peek(bar), output);
+            assert_eq!(obv.next(bar), output);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let bars = [
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(1000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(2000.0),
+            Bar::new().open(8).high(9).low(7).close(8).volume(3000.0),
+            Bar::new().open(4).high(5).low(3).close(4).volume(4000.0),
+            Bar::new().open(4).high(5).low(3).close(4.5).volume(5000.0),
+            Bar::new()
+                .open(1.5)
+                .high(2)
+                .low(1)
+                .close(1.5)
+                .volume(6000.0),
+        ];
+
+        let mut obv = OnBalanceVolume::<f64>::new();
+        for bar in &bars[..4] {
+            obv.next(bar);
+        }
+
+        let json = serde_json::to_string(&obv).unwrap();
+        let mut restored: OnBalanceVolume<f64> = serde_json::from_str(&json).unwrap();
+        for bar in &bars[4..] {
+            assert_eq!(restored.next(bar), obv.next(bar));
+        }
+    }
+
     #[test]
     fn test_default() {
         OnBalanceVolume::<f64>::default();
@@ -175,4 +390,13 @@ mod tests {
         let obv = OnBalanceVolume::<f64>::new();
         assert_eq!(format!("{}", obv), "OBV");
     }
+
+    #[test]
+    fn test_from_str() {
+        let obv: OnBalanceVolume<f64> = "OBV".parse().unwrap();
+        assert_eq!(format!("{}", obv), "OBV");
+
+        assert!("OBV()".parse::<OnBalanceVolume<f64>>().is_ok());
+        assert!("OBV(2)".parse::<OnBalanceVolume<f64>>().is_err());
+    }
 }
@@ -1,11 +1,13 @@
-use std::collections::VecDeque;
-use std::fmt;
-use std::ops::{Div, Mul, Sub};
+use alloc::collections::VecDeque;
+use core::fmt;
+use core::ops::{Div, Mul, Sub};
+use core::str::FromStr;
 
 use num_traits::{FromPrimitive, Zero};
 
 use crate::errors::*;
-use crate::traits::{Close, Next, Reset};
+use crate::helpers::{parse_param, parse_params};
+use crate::traits::{Close, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// Rate of Change (ROC)
 ///
@@ -41,19 +43,27 @@ use crate::traits::{Close, Next, Reset};
 /// * [Rate of Change, Wikipedia](https://en.wikipedia.org/wiki/Momentum_(technical_analysis))
 ///
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RateOfChange<T> {
     length: u32,
     prices: VecDeque<T>,
+    count: usize,
 }
 
 impl<T> RateOfChange<T> {
     pub fn new(length: u32) -> Result<Self> {
         match length {
-            0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
+            0 => Err(Error::invalid_parameter(
+                "ROC",
+                "length",
+                length,
+                "greater than 0",
+            )),
             _ => {
                 let indicator = Self {
-                    length: length,
+                    length,
                     prices: VecDeque::with_capacity(length as usize + 1),
+                    count: 0,
                 };
                 Ok(indicator)
             }
@@ -61,40 +71,92 @@ impl<T> RateOfChange<T> {
     }
 }
 
-impl<T> Next<T, !> for RateOfChange<T>
+impl<T> Next<T, Raw> for RateOfChange<T>
 where
     T: Copy + Zero + FromPrimitive + Mul<Output = T> + Div<Output = T> + Sub<Output = T>,
 {
     type Output = T;
 
     fn next(&mut self, input: T) -> Self::Output {
-        self.prices.push_back(input);
+        if self.count < self.warmup_period() {
+            self.count += 1;
+        }
 
-        if self.prices.len() == 1 {
-            return T::zero();
+        // The latest input is kept until the next one comes
+        if self.prices.len() > (self.length as usize) {
+            self.prices.pop_front();
         }
+        let output = self.peek(input);
+        self.prices.push_back(input);
+        output
+    }
+}
 
+impl<'a, U, T> Next<&'a U, T> for RateOfChange<T>
+where
+    U: Close<T>,
+    T: Copy + Zero + FromPrimitive + Mul<Output = T> + Div<Output = T> + Sub<Output = T>,
+{
+    type Output = T;
+
+    fn next(&mut self, input: &'a U) -> T {
+        self.next(input.close())
+    }
+}
+
+impl<T> Peek<T, Raw> for RateOfChange<T>
+where
+    T: Copy + Zero + FromPrimitive + Mul<Output = T> + Div<Output = T> + Sub<Output = T>,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        // The first price is pushed out by the input once there are enough of them
         let initial_price = if self.prices.len() > (self.length as usize) {
-            // unwrap is safe, because the check above.
-            // At this moment there must be at least 2 items in self.prices
-            self.prices.pop_front().unwrap()
+            self.prices.get(1)
         } else {
-            self.prices[0]
+            self.prices.front()
         };
 
-        (input - initial_price) / initial_price * T::from_u32(100).expect("Woot ?")
+        match initial_price {
+            Some(&initial_price) if !initial_price.is_zero() => {
+                (input - initial_price) / initial_price
+                    * T::from_u32(100).expect("constant fits in T")
+            }
+            _ => T::zero(),
+        }
     }
 }
 
-impl<'a, U, T> Next<&'a U, T> for RateOfChange<T>
+impl<'a, U, T> Peek<&'a U, T> for RateOfChange<T>
 where
     U: Close<T>,
     T: Copy + Zero + FromPrimitive + Mul<Output = T> + Div<Output = T> + Sub<Output = T>,
 {
-    type Output = T;
+    fn peek(&self, input: &'a U) -> T {
+        self.peek(input.close())
+    }
+}
 
-    fn next(&mut self, input: &'a U) -> T {
-        self.next(input.close())
+impl<T> UpdateLast<T, Raw> for RateOfChange<T>
+where
+    T: Copy + Zero + FromPrimitive + Mul<Output = T> + Div<Output = T> + Sub<Output = T>,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        if self.prices.pop_back().is_none() {
+            return self.next(input);
+        }
+        let output = self.peek(input);
+        self.prices.push_back(input);
+        output
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for RateOfChange<T>
+where
+    U: Close<T>,
+    T: Copy + Zero + FromPrimitive + Mul<Output = T> + Div<Output = T> + Sub<Output = T>,
+{
+    fn update_last(&mut self, input: &'a U) -> T {
+        self.update_last(input.close())
     }
 }
 
@@ -110,9 +172,31 @@ impl<T> fmt::Display for RateOfChange<T> {
     }
 }
 
+impl<T> FromStr for RateOfChange<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "ROC", 1)?;
+        let length = parse_param(s, params[0])?;
+        Self::new(length)
+    }
+}
+
 impl<T> Reset for RateOfChange<T> {
     fn reset(&mut self) {
         self.prices.clear();
+        self.count = 0;
+    }
+}
+
+impl<T> Warmup for RateOfChange<T> {
+    fn warmup_period(&self) -> usize {
+        // The first input only gives the price to compare with
+        self.length as usize + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count == self.warmup_period()
     }
 }
 
@@ -1,12 +1,14 @@
-use std::fmt;
-use std::ops::{Add, Div, Mul, Sub};
+use core::fmt;
+use core::ops::{Add, Div, Mul, Sub};
+use core::str::FromStr;
 
 use num_traits::{cast::FromPrimitive, One, Zero};
 
 use crate::errors::*;
+use crate::helpers::{parse_param, parse_params};
 use crate::indicators::ExponentialMovingAverage as Ema;
 
-use crate::{Close, Next, Reset};
+use crate::{Close, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// The relative strength index (RSI).
 ///
@@ -70,12 +72,15 @@ use crate::{Close, Next, Reset};
 /// * [Relative strength index (Wikipedia)](https://en.wikipedia.org/wiki/Relative_strength_index)
 /// * [RSI (Investopedia)](http://www.investopedia.com/terms/r/rsi.asp)
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RelativeStrengthIndex<T> {
     n: u32,
     up_ema_indicator: Ema<T>,
     down_ema_indicator: Ema<T>,
-    prev_val: T,
-    is_new: bool,
+    // value before the latest input, if any
+    prev_val: Option<T>,
+    last_val: Option<T>,
+    count: usize,
 }
 
 impl<T> RelativeStrengthIndex<T>
@@ -83,18 +88,22 @@ where
     T: Copy + Zero + One + Div<Output = T> + FromPrimitive,
 {
     pub fn new(n: u32) -> Result<Self> {
+        if n == 0 {
+            return Err(Error::invalid_parameter("RSI", "n", n, "greater than 0"));
+        }
         let rsi = Self {
-            n: n,
+            n,
             up_ema_indicator: Ema::new(n)?,
             down_ema_indicator: Ema::new(n)?,
-            prev_val: T::zero(),
-            is_new: true,
+            prev_val: None,
+            last_val: None,
+            count: 0,
         };
         Ok(rsi)
     }
 }
 
-impl<T> Next<T, !> for RelativeStrengthIndex<T>
+impl<T> RelativeStrengthIndex<T>
 where
     T: Copy
         + Zero
@@ -106,32 +115,65 @@ where
         + FromPrimitive
         + PartialOrd,
 {
-    type Output = T;
+    // RSI = 100 * (hausse moyenne / (hausse moyenne - baisse moyenne))
+    // Eq RSI = 100 – (100 / (1 + (hausse moyenne / baisse moyenne))).
+    fn rsi(up_ema: T, down_ema: T) -> T {
+        let total = up_ema + down_ema;
+        // The averages of exact numbers can decay to zero
+        if total.is_zero() {
+            return T::from_u32(50).expect("constant fits in T");
+        }
+        T::from_u32(100).expect("constant fits in T") * up_ema / total
+    }
 
-    fn next(&mut self, input: T) -> Self::Output {
+    fn gains(previous: Option<T>, input: T) -> (T, T) {
         let mut up = T::zero();
         let mut down = T::zero();
 
-        if self.is_new {
-            self.is_new = false;
-            // Initialize with some small seed numbers to avoid division by zero
-            up = T::from_f64(0.1).expect("Woot ?");
-            down = T::from_f64(0.1).expect("Woot ?");
-        } else {
-            if input > self.prev_val {
-                up = input - self.prev_val;
-            } else if input < self.prev_val {
-                down = self.prev_val - input;
+        match previous {
+            None => {
+                // Initialize with some small seed numbers to avoid division by zero
+                up = T::one() / T::from_u32(10).expect("constant fits in T");
+                down = T::one() / T::from_u32(10).expect("constant fits in T");
+            }
+            Some(previous) => {
+                if input > previous {
+                    up = input - previous;
+                } else if input < previous {
+                    down = previous - input;
+                }
             }
         }
+        (up, down)
+    }
+}
+
+impl<T> Next<T, Raw> for RelativeStrengthIndex<T>
+where
+    T: Copy
+        + Zero
+        + One
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + FromPrimitive
+        + PartialOrd,
+{
+    type Output = T;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        if self.count < self.warmup_period() {
+            self.count += 1;
+        }
+        self.prev_val = self.last_val;
+        self.last_val = Some(input);
 
-        self.prev_val = input;
+        let (up, down) = Self::gains(self.prev_val, input);
         let up_ema = self.up_ema_indicator.next(up);
         let down_ema = self.down_ema_indicator.next(down);
 
-        // RSI = 100 * (hausse moyenne / (hausse moyenne - baisse moyenne))
-        // Eq RSI = 100 – (100 / (1 + (hausse moyenne / baisse moyenne))).
-        T::from_u32(100).unwrap() * up_ema / (up_ema + down_ema)
+        Self::rsi(up_ema, down_ema)
     }
 }
 
@@ -155,18 +197,111 @@ where
     }
 }
 
+impl<T> UpdateLast<T, Raw> for RelativeStrengthIndex<T>
+where
+    T: Copy
+        + Zero
+        + One
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + FromPrimitive
+        + PartialOrd,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        if self.count == 0 {
+            return self.next(input);
+        }
+        self.last_val = Some(input);
+
+        let (up, down) = Self::gains(self.prev_val, input);
+        let up_ema = self.up_ema_indicator.update_last(up);
+        let down_ema = self.down_ema_indicator.update_last(down);
+        Self::rsi(up_ema, down_ema)
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for RelativeStrengthIndex<T>
+where
+    U: Close<T>,
+    T: Copy
+        + Zero
+        + One
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + FromPrimitive
+        + PartialOrd,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.close())
+    }
+}
+
+impl<T> Peek<T, Raw> for RelativeStrengthIndex<T>
+where
+    T: Copy
+        + Zero
+        + One
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + FromPrimitive
+        + PartialOrd,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        let (up, down) = Self::gains(self.last_val, input);
+        let up_ema = self.up_ema_indicator.peek(up);
+        let down_ema = self.down_ema_indicator.peek(down);
+        Self::rsi(up_ema, down_ema)
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for RelativeStrengthIndex<T>
+where
+    U: Close<T>,
+    T: Copy
+        + Zero
+        + One
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + FromPrimitive
+        + PartialOrd,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.close())
+    }
+}
+
 impl<T> Reset for RelativeStrengthIndex<T>
 where
     T: Zero,
 {
     fn reset(&mut self) {
-        self.is_new = true;
-        self.prev_val = T::zero();
+        self.count = 0;
+        self.prev_val = None;
+        self.last_val = None;
         self.up_ema_indicator.reset();
         self.down_ema_indicator.reset();
     }
 }
 
+impl<T> Warmup for RelativeStrengthIndex<T> {
+    fn warmup_period(&self) -> usize {
+        // The first input only gives the previous value to compare with
+        self.up_ema_indicator.warmup_period() + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count == self.warmup_period()
+    }
+}
+
 impl<T> Default for RelativeStrengthIndex<T>
 where
     T: Copy + Zero + One + Div<Output = T> + FromPrimitive,
@@ -182,6 +317,19 @@ impl<T> fmt::Display for RelativeStrengthIndex<T> {
     }
 }
 
+impl<T> FromStr for RelativeStrengthIndex<T>
+where
+    T: Copy + Zero + One + Div<Output = T> + FromPrimitive,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "RSI", 1)?;
+        let n = parse_param(s, params[0])?;
+        Self::new(n)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -204,6 +352,23 @@ mod tests {
         assert_eq!(rsi.next(9.5).round(), 16.0);
     }
 
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use rust_decimal::Decimal;
+
+        let mut rsi = RelativeStrengthIndex::<Decimal>::new(3).unwrap();
+        assert_eq!(rsi.next(Decimal::from(10)), Decimal::from(50));
+        assert_eq!(rsi.next(Decimal::new(105, 1)).round(), Decimal::from(86));
+        assert_eq!(rsi.next(Decimal::from(10)).round(), Decimal::from(35));
+
+        // the averages of a flat price decay to zero
+        let mut rsi = RelativeStrengthIndex::<Decimal>::new(3).unwrap();
+        for _ in 0..200 {
+            assert!(rsi.next(Decimal::from(10)) <= Decimal::from(50));
+        }
+    }
+
     #[test]
     fn test_reset() {
         let mut rsi = RelativeStrengthIndex::<f64>::new(3).unwrap();
@@ -215,6 +380,19 @@ mod tests {
         assert_eq!(rsi.next(10.5).round(), 86.0);
     }
 
+    #[test]
+    fn test_warmup() {
+        let mut rsi = RelativeStrengthIndex::<f64>::new(3).unwrap();
+        assert_eq!(rsi.warmup_period(), 4);
+
+        for _ in 0..3 {
+            rsi.next(10.0);
+            assert!(!rsi.is_ready());
+        }
+        rsi.next(10.5);
+        assert!(rsi.is_ready());
+    }
+
     #[test]
     fn test_default() {
         RelativeStrengthIndex::<f64>::default();
@@ -1,12 +1,14 @@
-use std::fmt;
-use std::ops::{Add, Div, Mul, Sub};
+use core::fmt;
+use core::ops::{Add, Div, Mul, Sub};
+use core::str::FromStr;
 
 use num_traits::{cast::FromPrimitive, One, Zero};
 
 use crate::errors::*;
+use crate::helpers::{parse_param, parse_params};
 use crate::indicators::SmoothedOrModifiedMovingAverage as Smma;
 
-use crate::{Close, Next, Reset};
+use crate::{Close, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// The relative strength index (RSI).
 ///
@@ -70,12 +72,15 @@ use crate::{Close, Next, Reset};
 /// * [Relative strength index (Wikipedia)](https://en.wikipedia.org/wiki/Relative_strength_index)
 /// * [RSI (Investopedia)](http://www.investopedia.com/terms/r/rsi.asp)
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RelativeStrengthIndexSmma<T> {
     n: u32,
     up_smma_indicator: Smma<T>,
     down_smma_indicator: Smma<T>,
-    prev_val: T,
-    is_new: bool,
+    // value before the latest input, if any
+    prev_val: Option<T>,
+    last_val: Option<T>,
+    count: usize,
 }
 
 impl<T> RelativeStrengthIndexSmma<T>
@@ -83,18 +88,27 @@ where
     T: Copy + Zero + One + Div<Output = T> + FromPrimitive,
 {
     pub fn new(n: u32) -> Result<Self> {
+        if n == 0 {
+            return Err(Error::invalid_parameter(
+                "RSI_SMMA",
+                "n",
+                n,
+                "greater than 0",
+            ));
+        }
         let rsi = Self {
-            n: n,
+            n,
             up_smma_indicator: Smma::new(n)?,
             down_smma_indicator: Smma::new(n)?,
-            prev_val: T::zero(),
-            is_new: true,
+            prev_val: None,
+            last_val: None,
+            count: 0,
         };
         Ok(rsi)
     }
 }
 
-impl<T> Next<T, !> for RelativeStrengthIndexSmma<T>
+impl<T> RelativeStrengthIndexSmma<T>
 where
     T: Copy
         + Zero
@@ -106,32 +120,65 @@ where
         + FromPrimitive
         + PartialOrd,
 {
-    type Output = T;
+    // RSI = 100 * (hausse moyenne / (hausse moyenne - baisse moyenne))
+    // Eq RSI = 100 – (100 / (1 + (hausse moyenne / baisse moyenne))).
+    fn rsi(up_ema: T, down_ema: T) -> T {
+        let total = up_ema + down_ema;
+        // The averages of exact numbers can decay to zero
+        if total.is_zero() {
+            return T::from_u32(50).expect("constant fits in T");
+        }
+        T::from_u32(100).expect("constant fits in T") * up_ema / total
+    }
 
-    fn next(&mut self, input: T) -> Self::Output {
+    fn gains(previous: Option<T>, input: T) -> (T, T) {
         let mut up = T::zero();
         let mut down = T::zero();
 
-        if self.is_new {
-            self.is_new = false;
-            // Initialize with some small seed numbers to avoid division by zero
-            up = T::from_f64(0.000000001).unwrap();
-            down = T::from_f64(0.00000001).unwrap();
-        } else {
-            if input > self.prev_val {
-                up = input - self.prev_val;
-            } else if input < self.prev_val {
-                down = self.prev_val - input;
+        match previous {
+            None => {
+                // Initialize with some small seed numbers to avoid division by zero
+                up = T::one() / T::from_u32(1_000_000_000).expect("constant fits in T");
+                down = T::one() / T::from_u32(100_000_000).expect("constant fits in T");
+            }
+            Some(previous) => {
+                if input > previous {
+                    up = input - previous;
+                } else if input < previous {
+                    down = previous - input;
+                }
             }
         }
+        (up, down)
+    }
+}
 
-        self.prev_val = input;
+impl<T> Next<T, Raw> for RelativeStrengthIndexSmma<T>
+where
+    T: Copy
+        + Zero
+        + One
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + FromPrimitive
+        + PartialOrd,
+{
+    type Output = T;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        if self.count < self.warmup_period() {
+            self.count += 1;
+        }
+        self.prev_val = self.last_val;
+        self.last_val = Some(input);
+
+        let (up, down) = Self::gains(self.prev_val, input);
         let up_ema = self.up_smma_indicator.next(up);
         let down_ema = self.down_smma_indicator.next(down);
 
-        // RSI = 100 * (hausse moyenne / (hausse moyenne - baisse moyenne))
-        // Eq RSI = 100 – (100 / (1 + (hausse moyenne / baisse moyenne))).
-        T::from_u32(100).unwrap() * up_ema / (up_ema + down_ema)
+        Self::rsi(up_ema, down_ema)
     }
 }
 
@@ -155,18 +202,111 @@ where
     }
 }
 
+impl<T> UpdateLast<T, Raw> for RelativeStrengthIndexSmma<T>
+where
+    T: Copy
+        + Zero
+        + One
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + FromPrimitive
+        + PartialOrd,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        if self.count == 0 {
+            return self.next(input);
+        }
+        self.last_val = Some(input);
+
+        let (up, down) = Self::gains(self.prev_val, input);
+        let up_ema = self.up_smma_indicator.update_last(up);
+        let down_ema = self.down_smma_indicator.update_last(down);
+        Self::rsi(up_ema, down_ema)
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for RelativeStrengthIndexSmma<T>
+where
+    U: Close<T>,
+    T: Copy
+        + Zero
+        + One
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + FromPrimitive
+        + PartialOrd,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.close())
+    }
+}
+
+impl<T> Peek<T, Raw> for RelativeStrengthIndexSmma<T>
+where
+    T: Copy
+        + Zero
+        + One
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + FromPrimitive
+        + PartialOrd,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        let (up, down) = Self::gains(self.last_val, input);
+        let up_ema = self.up_smma_indicator.peek(up);
+        let down_ema = self.down_smma_indicator.peek(down);
+        Self::rsi(up_ema, down_ema)
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for RelativeStrengthIndexSmma<T>
+where
+    U: Close<T>,
+    T: Copy
+        + Zero
+        + One
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + FromPrimitive
+        + PartialOrd,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.close())
+    }
+}
+
 impl<T> Reset for RelativeStrengthIndexSmma<T>
 where
     T: Zero,
 {
     fn reset(&mut self) {
-        self.is_new = true;
-        self.prev_val = T::zero();
+        self.count = 0;
+        self.prev_val = None;
+        self.last_val = None;
         self.up_smma_indicator.reset();
         self.down_smma_indicator.reset();
     }
 }
 
+impl<T> Warmup for RelativeStrengthIndexSmma<T> {
+    fn warmup_period(&self) -> usize {
+        // The first input only gives the previous value to compare with
+        self.up_smma_indicator.warmup_period() + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count == self.warmup_period()
+    }
+}
+
 impl<T> Default for RelativeStrengthIndexSmma<T>
 where
     T: Copy + Zero + One + Div<Output = T> + FromPrimitive,
@@ -178,51 +318,115 @@ where
 
 impl<T> fmt::Display for RelativeStrengthIndexSmma<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "RSI({})", self.n)
+        write!(f, "RSI_SMMA({})", self.n)
+    }
+}
+
+impl<T> FromStr for RelativeStrengthIndexSmma<T>
+where
+    T: Copy + Zero + One + Div<Output = T> + FromPrimitive,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "RSI_SMMA", 1)?;
+        let n = parse_param(s, params[0])?;
+        Self::new(n)
     }
 }
 
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use crate::test_helper::*;
-
-//     test_indicator!(RelativeStrengthIndexSmma);
-
-//     #[test]
-//     fn test_new() {
-//         assert!(RelativeStrengthIndexSmma::<f64>::new(0).is_err());
-//         assert!(RelativeStrengthIndexSmma::<f64>::new(1).is_ok());
-//     }
-
-//     #[test]
-//     fn test_next() {
-//         let mut rsi = RelativeStrengthIndexSmma::<f64>::new(3).unwrap();
-//         assert_eq!(rsi.next(10.0), 50.0);
-//         assert_eq!(rsi.next(10.5).round(), 86.0);
-//         assert_eq!(rsi.next(10.0).round(), 35.0);
-//         assert_eq!(rsi.next(9.5).round(), 16.0);
-//     }
-
-//     #[test]
-//     fn test_reset() {
-//         let mut rsi = RelativeStrengthIndexSmma::<f64>::new(3).unwrap();
-//         assert_eq!(rsi.next(10.0), 50.0);
-//         assert_eq!(rsi.next(10.5).round(), 86.0);
-
-//         rsi.reset();
-//         assert_eq!(rsi.next(10.0).round(), 50.0);
-//         assert_eq!(rsi.next(10.5).round(), 86.0);
-//     }
-
-//     #[test]
-//     fn test_default() {
-//         RelativeStrengthIndexSmma::<f64>::default();
-//     }
-
-//     #[test]
-//     fn test_display() {
-//         let rsi = RelativeStrengthIndexSmma::<f64>::new(16).unwrap();
-//         assert_eq!(format!("{}", rsi), "RSI(16)");
-//     }
-// }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RelativeStrengthIndexSmma);
+
+    #[test]
+    fn test_new() {
+        assert!(RelativeStrengthIndexSmma::<f64>::new(0).is_err());
+        assert!(RelativeStrengthIndexSmma::<f64>::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut rsi = RelativeStrengthIndexSmma::<f64>::new(3).unwrap();
+        // the seeds lean down, until the first move outweighs them
+        assert_eq!(round(rsi.next(10.0)), 9.091);
+        assert_eq!(round(rsi.next(10.5)), 100.0);
+        assert_eq!(round(rsi.next(10.0)), 40.0);
+        assert_eq!(round(rsi.next(9.5)), 21.053);
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use rust_decimal::Decimal;
+
+        let mut rsi = RelativeStrengthIndexSmma::<Decimal>::new(3).unwrap();
+        assert_eq!(
+            rsi.next(Decimal::from(10)).round_dp(3),
+            Decimal::new(9091, 3)
+        );
+        assert_eq!(
+            rsi.next(Decimal::new(105, 1)).round_dp(3),
+            Decimal::from(100)
+        );
+        assert_eq!(rsi.next(Decimal::from(10)).round_dp(3), Decimal::from(40));
+        assert_eq!(
+            rsi.next(Decimal::new(95, 1)).round_dp(3),
+            Decimal::new(21053, 3)
+        );
+    }
+
+    // exact averages of a flat price decay to zero, leaving no move either way
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_flat_decimal() {
+        use rust_decimal::Decimal;
+
+        let mut rsi = RelativeStrengthIndexSmma::<Decimal>::new(3).unwrap();
+        let mut output = rsi.next(Decimal::from(10));
+        for _ in 0..200 {
+            output = rsi.next(Decimal::from(10));
+        }
+        assert_eq!(output, Decimal::from(50));
+        assert_eq!(rsi.peek(Decimal::from(10)), Decimal::from(50));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rsi = RelativeStrengthIndexSmma::<f64>::new(3).unwrap();
+        assert_eq!(round(rsi.next(10.0)), 9.091);
+        assert_eq!(round(rsi.next(10.5)), 100.0);
+
+        rsi.reset();
+        assert_eq!(round(rsi.next(10.0)), 9.091);
+        assert_eq!(round(rsi.next(10.5)), 100.0);
+    }
+
+    // the macro takes its snapshot long after the warm-up, this one still holds the seeds
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_seeded() {
+        let mut rsi = RelativeStrengthIndexSmma::<f64>::new(3).unwrap();
+        rsi.next(10.0);
+
+        let json = serde_json::to_string(&rsi).unwrap();
+        let mut restored: RelativeStrengthIndexSmma<f64> = serde_json::from_str(&json).unwrap();
+        for &v in &[10.5, 10.0, 9.5, 9.5] {
+            assert_eq!(restored.next(v), rsi.next(v));
+        }
+    }
+
+    #[test]
+    fn test_default() {
+        RelativeStrengthIndexSmma::<f64>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let rsi = RelativeStrengthIndexSmma::<f64>::new(16).unwrap();
+        assert_eq!(format!("{}", rsi), "RSI_SMMA(16)");
+    }
+}
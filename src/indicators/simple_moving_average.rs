@@ -1,10 +1,14 @@
-use std::fmt;
-use std::ops::{Add, Div, Sub};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Div, Sub};
+use core::str::FromStr;
 
 use num_traits::{cast::FromPrimitive, Zero};
 
 use crate::errors::*;
-use crate::{Close, Next, Reset};
+use crate::helpers::{parse_param, parse_params};
+use crate::{Close, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// Simple moving average (SMA).
 ///
@@ -40,11 +44,14 @@ use crate::{Close, Next, Reset};
 /// * [Simple Moving Average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average#Simple_moving_average)
 ///
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SimpleMovingAverage<T> {
     n: u32,
     index: usize,
     count: u32,
     sum: T,
+    // sum without the latest input
+    rest: T,
     vec: Vec<T>,
 }
 
@@ -54,13 +61,14 @@ where
 {
     pub fn new(n: u32) -> Result<Self> {
         match n {
-            0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
+            0 => Err(Error::invalid_parameter("SMA", "n", n, "greater than 0")),
             _ => {
                 let indicator = Self {
-                    n: n,
+                    n,
                     index: 0,
                     count: 0,
                     sum: T::zero(),
+                    rest: T::zero(),
                     vec: vec![T::zero(); n as usize],
                 };
                 Ok(indicator)
@@ -69,7 +77,13 @@ where
     }
 }
 
-impl<T> Next<T, !> for SimpleMovingAverage<T>
+impl<T> SimpleMovingAverage<T> {
+    pub fn length(&self) -> u32 {
+        self.n
+    }
+}
+
+impl<T> Next<T, Raw> for SimpleMovingAverage<T>
 where
     T: Copy + Add<Output = T> + Div<Output = T> + Sub<Output = T> + FromPrimitive,
 {
@@ -85,7 +99,8 @@ where
             self.count += 1;
         }
 
-        self.sum = self.sum - old_val + input;
+        self.rest = self.sum - old_val;
+        self.sum = self.rest + input;
         self.sum / T::from_u32(self.count).expect("Woot ?")
     }
 }
@@ -102,6 +117,56 @@ where
     }
 }
 
+impl<T> UpdateLast<T, Raw> for SimpleMovingAverage<T>
+where
+    T: Copy + Add<Output = T> + Div<Output = T> + Sub<Output = T> + FromPrimitive,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        if self.count == 0 {
+            return self.next(input);
+        }
+
+        self.vec[self.index] = input;
+        self.sum = self.rest + input;
+        self.sum / T::from_u32(self.count).expect("window length fits in T")
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for SimpleMovingAverage<T>
+where
+    U: Close<T>,
+    T: Copy + Add<Output = T> + Div<Output = T> + Sub<Output = T> + FromPrimitive,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.close())
+    }
+}
+
+impl<T> Peek<T, Raw> for SimpleMovingAverage<T>
+where
+    T: Copy + Add<Output = T> + Div<Output = T> + Sub<Output = T> + FromPrimitive,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        let old_val = self.vec[(self.index + 1) % (self.n as usize)];
+        let count = if self.count < self.n {
+            self.count + 1
+        } else {
+            self.count
+        };
+        (self.sum - old_val + input) / T::from_u32(count).expect("window length fits in T")
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for SimpleMovingAverage<T>
+where
+    U: Close<T>,
+    T: Copy + Add<Output = T> + Div<Output = T> + Sub<Output = T> + FromPrimitive,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.close())
+    }
+}
+
 impl<T> Reset for SimpleMovingAverage<T>
 where
     T: Zero,
@@ -110,12 +175,23 @@ where
         self.index = 0;
         self.count = 0;
         self.sum = T::zero();
+        self.rest = T::zero();
         for i in 0..(self.n as usize) {
             self.vec[i] = T::zero();
         }
     }
 }
 
+impl<T> Warmup for SimpleMovingAverage<T> {
+    fn warmup_period(&self) -> usize {
+        self.n as usize
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count == self.n
+    }
+}
+
 impl<T> Default for SimpleMovingAverage<T>
 where
     T: Clone + Zero,
@@ -131,6 +207,19 @@ impl<T> fmt::Display for SimpleMovingAverage<T> {
     }
 }
 
+impl<T> FromStr for SimpleMovingAverage<T>
+where
+    T: Clone + Zero,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "SMA", 1)?;
+        let n = parse_param(s, params[0])?;
+        Self::new(n)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,6 +245,33 @@ mod tests {
         assert_eq!(sma.next(2.0), 5.0);
     }
 
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use rust_decimal::Decimal;
+
+        let mut sma = SimpleMovingAverage::<Decimal>::new(3).unwrap();
+        assert_eq!(sma.next(Decimal::new(10, 2)), Decimal::new(10, 2));
+        assert_eq!(sma.next(Decimal::new(20, 2)), Decimal::new(15, 2));
+        // no float drift: 0.1 + 0.2 + 0.3 is exactly 0.6
+        assert_eq!(sma.next(Decimal::new(30, 2)), Decimal::new(20, 2));
+    }
+
+    #[test]
+    fn test_next_fixed() {
+        use crate::Fixed;
+
+        let mut sma = SimpleMovingAverage::<Fixed<2>>::new(3).unwrap();
+        assert_eq!(sma.next(Fixed::from_ticks(1000)), Fixed::from_ticks(1000));
+        // 20.01 / 2 = 10.005 is rounded away from zero
+        assert_eq!(sma.next(Fixed::from_ticks(1001)), Fixed::from_ticks(1001));
+        // 30.02 / 3 = 10.0066...
+        assert_eq!(sma.next(Fixed::from_ticks(1001)), Fixed::from_ticks(1001));
+        // 30.01 / 3 = 10.0033..., the sum is exact so rounding does not accumulate
+        assert_eq!(sma.next(Fixed::from_ticks(999)), Fixed::from_ticks(1000));
+        assert_eq!(sma.next(Fixed::from_ticks(-999)), Fixed::from_ticks(334));
+    }
+
     #[test]
     fn test_next_with_bars() {
         fn bar(close: f64) -> Bar {
@@ -190,4 +306,14 @@ mod tests {
         let sma = SimpleMovingAverage::<f64>::new(5).unwrap();
         assert_eq!(format!("{}", sma), "SMA(5)");
     }
+
+    #[test]
+    fn test_from_str() {
+        let sma: SimpleMovingAverage<f64> = " SMA( 5 ) ".parse().unwrap();
+        assert_eq!(format!("{}", sma), "SMA(5)");
+
+        assert!("SMA(0)".parse::<SimpleMovingAverage<f64>>().is_err());
+        assert!("SMA(2.5)".parse::<SimpleMovingAverage<f64>>().is_err());
+        assert!("EMA(5)".parse::<SimpleMovingAverage<f64>>().is_err());
+    }
 }
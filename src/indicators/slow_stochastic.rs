@@ -1,11 +1,13 @@
-use std::fmt;
-use std::ops::{Add, Div, Mul, Sub};
+use core::fmt;
+use core::ops::{Add, Div, Mul, Sub};
+use core::str::FromStr;
 
 use num_traits::{cast::FromPrimitive, One, Zero};
 
-use crate::errors::Result;
+use crate::errors::{Error, Result};
+use crate::helpers::{parse_param, parse_params};
 use crate::indicators::{ExponentialMovingAverage, FastStochastic};
-use crate::{Close, High, Low, Next, Reset};
+use crate::{Close, High, Low, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// Slow stochastic oscillator.
 ///
@@ -14,7 +16,7 @@ use crate::{Close, High, Low, Next, Reset};
 /// # Parameters
 ///
 /// * _stochastic_n_ - number of periods for fast stochastic (integer greater than 0). Default is 14.
-/// *_ema_n_ - length for EMA (integer greater than 0). Default is 3.
+/// * _ema_n_ - length for EMA (integer greater than 0). Default is 3.
 ///
 /// # Example
 ///
@@ -30,9 +32,11 @@ use crate::{Close, High, Low, Next, Reset};
 /// assert_eq!(stoch.next(55.0).round(), 77.0);
 /// ```
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlowStochastic<T> {
     fast_stochastic: FastStochastic<T>,
     ema: ExponentialMovingAverage<T>,
+    count: usize,
 }
 
 impl<T> SlowStochastic<T>
@@ -40,15 +44,32 @@ where
     T: Copy + Zero + One + FromPrimitive + PartialOrd + Div<Output = T>,
 {
     pub fn new(stochastic_n: u32, ema_n: u32) -> Result<Self> {
+        if stochastic_n == 0 {
+            return Err(Error::invalid_parameter(
+                "SLOW_STOCH",
+                "stochastic_n",
+                stochastic_n,
+                "greater than 0",
+            ));
+        }
+        if ema_n == 0 {
+            return Err(Error::invalid_parameter(
+                "SLOW_STOCH",
+                "ema_n",
+                ema_n,
+                "greater than 0",
+            ));
+        }
         let indicator = Self {
             fast_stochastic: FastStochastic::<T>::new(stochastic_n)?,
             ema: ExponentialMovingAverage::<T>::new(ema_n)?,
+            count: 0,
         };
         Ok(indicator)
     }
 }
 
-impl<T> Next<T, !> for SlowStochastic<T>
+impl<T> Next<T, Raw> for SlowStochastic<T>
 where
     T: Copy
         + One
@@ -62,6 +83,7 @@ where
     type Output = T;
 
     fn next(&mut self, input: T) -> Self::Output {
+        self.count_input();
         self.ema.next(self.fast_stochastic.next(input))
     }
 }
@@ -81,10 +103,85 @@ where
     type Output = T;
 
     fn next(&mut self, input: &'a U) -> Self::Output {
+        self.count_input();
         self.ema.next(self.fast_stochastic.next(input))
     }
 }
 
+impl<T> UpdateLast<T, Raw> for SlowStochastic<T>
+where
+    T: Copy
+        + One
+        + PartialOrd
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Mul<Output = T>
+        + Sub<Output = T>,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        if self.count == 0 {
+            return self.next(input);
+        }
+        self.ema
+            .update_last(self.fast_stochastic.update_last(input))
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for SlowStochastic<T>
+where
+    U: High<T> + Low<T> + Close<T>,
+    T: Copy
+        + One
+        + PartialOrd
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Mul<Output = T>
+        + Sub<Output = T>,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        if self.count == 0 {
+            return self.next(input);
+        }
+        self.ema
+            .update_last(self.fast_stochastic.update_last(input))
+    }
+}
+
+impl<T> Peek<T, Raw> for SlowStochastic<T>
+where
+    T: Copy
+        + One
+        + PartialOrd
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Mul<Output = T>
+        + Sub<Output = T>,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        self.ema.peek(self.fast_stochastic.peek(input))
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for SlowStochastic<T>
+where
+    U: High<T> + Low<T> + Close<T>,
+    T: Copy
+        + One
+        + PartialOrd
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Mul<Output = T>
+        + Sub<Output = T>,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.ema.peek(self.fast_stochastic.peek(input))
+    }
+}
+
 impl<T> Reset for SlowStochastic<T>
 where
     T: Copy + Zero,
@@ -92,6 +189,26 @@ where
     fn reset(&mut self) {
         self.fast_stochastic.reset();
         self.ema.reset();
+        self.count = 0;
+    }
+}
+
+impl<T> SlowStochastic<T> {
+    fn count_input(&mut self) {
+        if self.count < self.warmup_period() {
+            self.count += 1;
+        }
+    }
+}
+
+impl<T> Warmup for SlowStochastic<T> {
+    fn warmup_period(&self) -> usize {
+        // The EMA is meaningful once it has smoothed enough meaningful stochastic values
+        self.fast_stochastic.warmup_period() + self.ema.warmup_period() - 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count == self.warmup_period()
     }
 }
 
@@ -118,6 +235,20 @@ where
     }
 }
 
+impl<T> FromStr for SlowStochastic<T>
+where
+    T: Copy + Zero + One + FromPrimitive + PartialOrd + Div<Output = T>,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "SLOW_STOCH", 2)?;
+        let stochastic_n = parse_param(s, params[0])?;
+        let ema_n = parse_param(s, params[1])?;
+        Self::new(stochastic_n, ema_n)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +304,19 @@ mod tests {
         assert_eq!(stoch.next(10.0), 50.0);
     }
 
+    #[test]
+    fn test_warmup() {
+        let mut stoch = SlowStochastic::<f64>::new(3, 2).unwrap();
+        assert_eq!(stoch.warmup_period(), 4);
+
+        for _ in 0..3 {
+            stoch.next(10.0);
+            assert!(!stoch.is_ready());
+        }
+        stoch.next(&Bar::new().high(11).low(9).close(10));
+        assert!(stoch.is_ready());
+    }
+
     #[test]
     fn test_default() {
         SlowStochastic::<f64>::default();
@@ -1,10 +1,12 @@
-use std::fmt;
-use std::ops::{Add, Div, Mul};
+use core::fmt;
+use core::ops::{Add, Div, Mul};
+use core::str::FromStr;
 
 use num_traits::cast::FromPrimitive;
 
 use crate::errors::*;
-use crate::{Close, Next, Reset};
+use crate::helpers::{parse_param, parse_params};
+use crate::{Close, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// view `<https://www.instaforex.eu/fr/forex_technical_indicators/moving_average>`
 /// view `<https://www.metatrader5.com/en/terminal/help/indicators/trend_indicators/ma>`
@@ -56,19 +58,30 @@ use crate::{Close, Next, Reset};
 ///
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SmoothedOrModifiedMovingAverage<T> {
     length: u32,
     current: Option<T>,
+    // value before the latest input, if any
+    previous: Option<T>,
+    count: u32,
 }
 
 impl<T> SmoothedOrModifiedMovingAverage<T> {
     pub fn new(length: u32) -> Result<Self> {
         match length {
-            0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
+            0 => Err(Error::invalid_parameter(
+                "SMMA",
+                "length",
+                length,
+                "greater than 0",
+            )),
             _ => {
                 let indicator = Self {
                     length,
                     current: None,
+                    previous: None,
+                    count: 0,
                 };
                 Ok(indicator)
             }
@@ -82,21 +95,36 @@ impl<T> SmoothedOrModifiedMovingAverage<T> {
     }
 }
 
-impl<T> Next<T, !> for SmoothedOrModifiedMovingAverage<T>
+impl<T> SmoothedOrModifiedMovingAverage<T>
+where
+    T: Copy + FromPrimitive + Add<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    fn average(&self, previous: Option<T>, input: T) -> T {
+        match previous {
+            None => input,
+            // SMMA (i) = (SMMA (i - 1) * (N - 1) + CLOSE (i)) / N
+            Some(previous) => {
+                (previous * T::from_u32(self.length - 1).unwrap() + input)
+                    / T::from_u32(self.length).unwrap()
+            }
+        }
+    }
+}
+
+impl<T> Next<T, Raw> for SmoothedOrModifiedMovingAverage<T>
 where
     T: Copy + FromPrimitive + Add<Output = T> + Div<Output = T> + Mul<Output = T>,
 {
     type Output = T;
 
     fn next(&mut self, input: T) -> Self::Output {
-        self.current = Some(if let None = self.current {
-            input
-        } else {
-            // SMMA (i) = (SMMA (i - 1) * (N - 1) + CLOSE (i)) / N
-            (self.current.unwrap() * T::from_u32(self.length - 1).unwrap() + input)
-                / T::from_u32(self.length).unwrap()
-        });
-        self.current.unwrap()
+        self.previous = self.current;
+        let current = self.average(self.previous, input);
+        self.current = Some(current);
+        if self.count < self.length {
+            self.count += 1;
+        }
+        current
     }
 }
 
@@ -112,9 +140,64 @@ where
     }
 }
 
+impl<T> UpdateLast<T, Raw> for SmoothedOrModifiedMovingAverage<T>
+where
+    T: Copy + FromPrimitive + Add<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        if self.current.is_none() {
+            return self.next(input);
+        }
+        let current = self.average(self.previous, input);
+        self.current = Some(current);
+        current
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for SmoothedOrModifiedMovingAverage<T>
+where
+    U: Close<T>,
+    T: Copy + FromPrimitive + Add<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.close())
+    }
+}
+
+impl<T> Peek<T, Raw> for SmoothedOrModifiedMovingAverage<T>
+where
+    T: Copy + FromPrimitive + Add<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        self.average(self.current, input)
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for SmoothedOrModifiedMovingAverage<T>
+where
+    U: Close<T>,
+    T: Copy + FromPrimitive + Add<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.close())
+    }
+}
+
 impl<T> Reset for SmoothedOrModifiedMovingAverage<T> {
     fn reset(&mut self) {
         self.current = None;
+        self.previous = None;
+        self.count = 0;
+    }
+}
+
+impl<T> Warmup for SmoothedOrModifiedMovingAverage<T> {
+    fn warmup_period(&self) -> usize {
+        self.length as usize
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count == self.length
     }
 }
 
@@ -130,52 +213,83 @@ impl<T> fmt::Display for SmoothedOrModifiedMovingAverage<T> {
     }
 }
 
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use crate::test_helper::*;
-
-//     test_indicator!(SmoothedOrModifiedMovingAverage);
-
-//     #[test]
-//     fn test_new() {
-//         assert!(SmoothedOrModifiedMovingAverage::<f64>::new(0).is_err());
-//         assert!(SmoothedOrModifiedMovingAverage::<f64>::new(1).is_ok());
-//     }
-
-//     #[test]
-//     fn test_next() {
-//         let mut ema = SmoothedOrModifiedMovingAverage::new(3).unwrap();
-
-//         assert_eq!(ema.next(2.0), 2.0);
-//         assert_eq!(ema.next(5.0), 3.5);
-//         assert_eq!(ema.next(1.0), 2.25);
-//         assert_eq!(ema.next(6.25), 4.25);
-
-//         let mut ema = SmoothedOrModifiedMovingAverage::new(3).unwrap();
-//         let bar1 = Bar::new().close(2);
-//         let bar2 = Bar::new().close(5);
-//         assert_eq!(ema.next(&bar1), 2.0);
-//         assert_eq!(ema.next(&bar2), 3.5);
-//     }
-
-//     #[test]
-//     fn test_reset() {
-//         let mut ema = SmoothedOrModifiedMovingAverage::new(5).unwrap();
-
-//         assert_eq!(ema.next(4.0), 4.0);
-//         ema.next(10.0);
-//         ema.next(15.0);
-//         ema.next(20.0);
-//         assert_ne!(ema.next(4.0), 4.0);
-
-//         ema.reset();
-//         assert_eq!(ema.next(4.0), 4.0);
-//     }
-
-//     #[test]
-//     fn test_display() {
-//         let ema = SmoothedOrModifiedMovingAverage::<f64>::new(7).unwrap();
-//         assert_eq!(format!("{}", ema), "EMA(7)");
-//     }
-// }
+impl<T> FromStr for SmoothedOrModifiedMovingAverage<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "SMMA", 1)?;
+        let length = parse_param(s, params[0])?;
+        Self::new(length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(SmoothedOrModifiedMovingAverage);
+
+    #[test]
+    fn test_new() {
+        assert!(SmoothedOrModifiedMovingAverage::<f64>::new(0).is_err());
+        assert!(SmoothedOrModifiedMovingAverage::<f64>::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut smma = SmoothedOrModifiedMovingAverage::new(3).unwrap();
+
+        assert_eq!(smma.next(2.0), 2.0);
+        assert_eq!(smma.next(5.0), 3.0);
+        assert_eq!(round(smma.next(1.0)), 2.333);
+        assert_eq!(round(smma.next(6.25)), 3.639);
+
+        let mut smma = SmoothedOrModifiedMovingAverage::new(3).unwrap();
+        let bar1 = Bar::new().close(2);
+        let bar2 = Bar::new().close(5);
+        assert_eq!(smma.next(&bar1), 2.0);
+        assert_eq!(smma.next(&bar2), 3.0);
+    }
+
+    #[test]
+    fn test_next_fixed() {
+        use crate::Fixed;
+
+        let mut smma = SmoothedOrModifiedMovingAverage::<Fixed<2>>::new(3).unwrap();
+        assert_eq!(smma.next(Fixed::from_ticks(1000)), Fixed::from_ticks(1000));
+        // 31.00 / 3 is rounded to the nearest tick
+        assert_eq!(smma.next(Fixed::from_ticks(1100)), Fixed::from_ticks(1033));
+        assert_eq!(smma.peek(Fixed::from_ticks(1200)), Fixed::from_ticks(1089));
+        assert_eq!(smma.next(Fixed::from_ticks(1200)), Fixed::from_ticks(1089));
+        assert_eq!(
+            smma.update_last(Fixed::from_ticks(900)),
+            Fixed::from_ticks(989)
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut smma = SmoothedOrModifiedMovingAverage::new(5).unwrap();
+
+        assert_eq!(smma.next(4.0), 4.0);
+        smma.next(10.0);
+        smma.next(15.0);
+        smma.next(20.0);
+        assert_ne!(smma.next(4.0), 4.0);
+
+        smma.reset();
+        assert_eq!(smma.next(4.0), 4.0);
+    }
+
+    #[test]
+    fn test_default() {
+        SmoothedOrModifiedMovingAverage::<f64>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let smma = SmoothedOrModifiedMovingAverage::<f64>::new(7).unwrap();
+        assert_eq!(format!("{}", smma), "SMMA(7)");
+    }
+}
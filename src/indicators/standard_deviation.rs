@@ -1,10 +1,15 @@
-use std::fmt;
-use std::ops::{Add, AddAssign, Div, Mul, Sub};
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, Mul, Sub};
+use core::str::FromStr;
 
-use num_traits::{cast::FromPrimitive, One, Zero};
+use num_traits::{cast::FromPrimitive, Zero};
 
 use crate::errors::*;
-use crate::{Close, Next, Reset};
+use crate::helpers::{parse_param, parse_params};
+use crate::{Close, Next, Peek, Raw, Reset, Sqrt, UpdateLast, Warmup};
 
 /// Standard deviation (SD).
 ///
@@ -23,16 +28,33 @@ use crate::{Close, Next, Reset};
 /// # Parameters
 ///
 /// * _n_ - number of periods (integer greater than 0)
+/// * _deviation_type_ - population (divided by N) or sample (divided by N - 1) standard
+///   deviation, see [DeviationType](enum.DeviationType.html). Default is population.
+/// * _update_mode_ - how the mean and the sum of squared deviations are kept up to date, see
+///   [UpdateMode](enum.UpdateMode.html). Default is incremental.
+///
+/// Besides the standard deviation returned by `next`, the mean, the variance and the z-score of
+/// the latest input are available.
 ///
 /// # Example
 ///
 /// ```
-/// use ta::indicators::StandardDeviation;
+/// use ta::indicators::{DeviationType, StandardDeviation, UpdateMode};
 /// use ta::Next;
 ///
 /// let mut sd = StandardDeviation::<f64>::new(3).unwrap();
 /// assert_eq!(sd.next(10.0), 0.0);
 /// assert_eq!(sd.next(20.0), 5.0);
+/// assert_eq!(sd.mean(), 15.0);
+/// assert_eq!(sd.variance(), 25.0);
+/// assert_eq!(sd.z_score(), 1.0);
+///
+/// let mut sd =
+///     StandardDeviation::<f64>::with_options(3, DeviationType::Sample, UpdateMode::Stable)
+///         .unwrap();
+/// assert_eq!(sd.next(10.0), 0.0);
+/// assert_eq!(sd.next(20.0), 50.0_f64.sqrt());
+/// assert_eq!(sd.variance(), 50.0);
 /// ```
 ///
 /// # Links
@@ -40,29 +62,128 @@ use crate::{Close, Next, Reset};
 /// * [Standard Deviation, Wikipedia](https://en.wikipedia.org/wiki/Standard_deviation)
 ///
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StandardDeviation<T> {
     n: u32,
+    deviation_type: DeviationType,
+    update_mode: UpdateMode,
     index: usize,
     count: u32,
     m: T,
     m2: T,
+    // m and m2 before the latest input
+    prev_m: T,
+    prev_m2: T,
+    // value pushed out of the window by the latest input, if it was full
+    evicted: Option<T>,
     vec: Vec<T>,
 }
 
+/// Divisor of the sum of squared deviations of a
+/// [StandardDeviation](struct.StandardDeviation.html).
+///
+/// It is displayed and parsed as `POPULATION` or `SAMPLE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeviationType {
+    /// Divided by N, the values are the whole population
+    #[default]
+    Population,
+    /// Divided by N - 1 (Bessel's correction), the values are a sample of the population. It is
+    /// what most charting platforms use.
+    Sample,
+}
+
+impl fmt::Display for DeviationType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DeviationType::Population => write!(f, "POPULATION"),
+            DeviationType::Sample => write!(f, "SAMPLE"),
+        }
+    }
+}
+
+impl FromStr for DeviationType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "POPULATION" => Ok(DeviationType::Population),
+            "SAMPLE" => Ok(DeviationType::Sample),
+            spec => Err(Error::InvalidSpec {
+                spec: spec.to_string(),
+            }),
+        }
+    }
+}
+
+/// How a [StandardDeviation](struct.StandardDeviation.html) keeps its mean and sum of squared
+/// deviations up to date.
+///
+/// It is displayed and parsed as `INCREMENTAL` or `STABLE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UpdateMode {
+    /// Updated with every input, in constant time. With floats, rounding errors pile up over
+    /// a long-running stream, above all when the deviation is small relative to the values.
+    #[default]
+    Incremental,
+    /// Updated with every input as well, but recomputed from the window every n inputs, which
+    /// bounds the rounding errors to those of the last n updates. It stays in amortized
+    /// constant time.
+    Stable,
+}
+
+impl fmt::Display for UpdateMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UpdateMode::Incremental => write!(f, "INCREMENTAL"),
+            UpdateMode::Stable => write!(f, "STABLE"),
+        }
+    }
+}
+
+impl FromStr for UpdateMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "INCREMENTAL" => Ok(UpdateMode::Incremental),
+            "STABLE" => Ok(UpdateMode::Stable),
+            spec => Err(Error::InvalidSpec {
+                spec: spec.to_string(),
+            }),
+        }
+    }
+}
+
 impl<T> StandardDeviation<T>
 where
     T: Copy + Zero,
 {
     pub fn new(n: u32) -> Result<Self> {
+        Self::with_options(n, DeviationType::Population, UpdateMode::Incremental)
+    }
+
+    pub fn with_options(
+        n: u32,
+        deviation_type: DeviationType,
+        update_mode: UpdateMode,
+    ) -> Result<Self> {
         match n {
-            0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
+            0 => Err(Error::invalid_parameter("SD", "n", n, "greater than 0")),
             _ => {
                 let std = StandardDeviation {
                     n,
+                    deviation_type,
+                    update_mode,
                     index: 0,
                     count: 0,
                     m: T::zero(),
                     m2: T::zero(),
+                    prev_m: T::zero(),
+                    prev_m2: T::zero(),
+                    evicted: None,
                     vec: vec![T::zero(); n as usize],
                 };
                 Ok(std)
@@ -70,52 +191,188 @@ where
         }
     }
 
-    pub(super) fn mean(&self) -> T {
+    /// Returns the mean of the last n values.
+    pub fn mean(&self) -> T {
         self.m
     }
 }
 
-/// Heron method: An+1 = 1/2 * (an + A/an)
-/// See http://villemin.gerard.free.fr/ThNbDemo/Heron.htm
-fn find_square_root<T>(seed: T, v: T, ttl: usize) -> T
-where
-    T: Copy + Zero + One + PartialEq + Div<Output = T> + FromPrimitive,
-{
-    if ttl == 0 {
-        seed
-    } else if seed == T::zero() {
-        eprintln!("division by zero ?");
-        T::zero()
-    } else {
-        find_square_root(
-            T::one() / T::from_u32(2).expect("Woot ?") * (seed + v / seed),
-            v,
-            ttl - 1,
-        )
+impl<T> StandardDeviation<T> {
+    pub fn length(&self) -> u32 {
+        self.n
+    }
+
+    pub fn deviation_type(&self) -> DeviationType {
+        self.deviation_type
     }
-}
 
-const TTL: usize = 32;
+    pub fn update_mode(&self) -> UpdateMode {
+        self.update_mode
+    }
+}
 
-fn sqrt<T>(v: T) -> T
+impl<T> StandardDeviation<T>
 where
-    T: Copy + Zero + One + PartialEq + Div<Output = T> + FromPrimitive,
+    T: Copy
+        + Zero
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + AddAssign
+        + PartialOrd
+        + Sqrt,
 {
-    find_square_root(v, v, TTL)
+    // Returns m and m2 once input is added to them, and evicted is removed if any.
+    fn add_to_moments(
+        &self,
+        mut m: T,
+        mut m2: T,
+        count: u32,
+        evicted: Option<T>,
+        input: T,
+    ) -> (T, T) {
+        match evicted {
+            None => {
+                let delta = input - m;
+                m += delta / T::from_u32(count).expect("window length fits in T");
+                let delta2 = input - m;
+                m2 += delta * delta2;
+            }
+            Some(old_val) => {
+                let delta = input - old_val;
+                let old_m = m;
+                m += delta / T::from_u32(self.n).expect("window length fits in T");
+                let delta2 = input - m + old_val - old_m;
+                m2 += delta * delta2;
+            }
+        }
+        (m, m2)
+    }
+
+    // Returns m and m2 of the full window once input is at slot, computed in two passes.
+    fn window_moments(&self, slot: usize, input: T) -> (T, T) {
+        let values = || {
+            self.vec
+                .iter()
+                .enumerate()
+                .map(move |(i, &value)| if i == slot { input } else { value })
+        };
+        let m = values().fold(T::zero(), |sum, value| sum + value)
+            / T::from_u32(self.n).expect("window length fits in T");
+        let m2 = values().fold(T::zero(), |m2, value| {
+            let delta = value - m;
+            m2 + delta * delta
+        });
+        (m, m2)
+    }
+
+    // Returns m and m2 once input is added at slot, and evicted is removed if any. In the stable
+    // mode they are recomputed from the window each time the input is at its first slot.
+    fn moments(
+        &self,
+        m: T,
+        m2: T,
+        count: u32,
+        evicted: Option<T>,
+        slot: usize,
+        input: T,
+    ) -> (T, T) {
+        if self.update_mode == UpdateMode::Stable && count == self.n && slot == 0 {
+            self.window_moments(slot, input)
+        } else {
+            self.add_to_moments(m, m2, count, evicted, input)
+        }
+    }
+
+    // Rounding errors can make the variance slightly negative, when all values are equal.
+    fn variance_of(&self, m2: T, count: u32) -> T {
+        let divisor = match self.deviation_type {
+            DeviationType::Population => count,
+            DeviationType::Sample => count.saturating_sub(1),
+        };
+        if divisor == 0 {
+            return T::zero();
+        }
+        let variance = m2 / T::from_u32(divisor).expect("window length fits in T");
+        if variance > T::zero() {
+            variance
+        } else {
+            T::zero()
+        }
+    }
+
+    fn deviation(&self, m2: T, count: u32) -> T {
+        let variance = self.variance_of(m2, count);
+        if variance > T::zero() {
+            variance.sqrt()
+        } else {
+            T::zero()
+        }
+    }
+
+    // Adds the latest input to m and m2 as they were before it.
+    fn update_moments(&mut self, input: T) -> T {
+        let (m, m2) = self.moments(
+            self.prev_m,
+            self.prev_m2,
+            self.count,
+            self.evicted,
+            self.index,
+            input,
+        );
+        self.m = m;
+        self.m2 = m2;
+
+        self.deviation(self.m2, self.count)
+    }
+
+    /// Returns the variance of the last n values, the square of the standard deviation.
+    pub fn variance(&self) -> T {
+        self.variance_of(self.m2, self.count)
+    }
+
+    /// Returns how many standard deviations the latest input is away from the mean, 0 when the
+    /// standard deviation is 0.
+    pub fn z_score(&self) -> T {
+        let deviation = self.deviation(self.m2, self.count);
+        if deviation > T::zero() {
+            (self.vec[self.index] - self.m) / deviation
+        } else {
+            T::zero()
+        }
+    }
+
+    // Returns the mean and the standard deviation next would give for input.
+    pub(super) fn peek_with_mean(&self, input: T) -> (T, T) {
+        let (count, evicted) = if self.count < self.n {
+            (self.count + 1, None)
+        } else {
+            (
+                self.count,
+                Some(self.vec[(self.index + 1) % (self.n as usize)]),
+            )
+        };
+        let slot = (self.index + 1) % (self.n as usize);
+        let (m, m2) = self.moments(self.m, self.m2, count, evicted, slot, input);
+
+        (m, self.deviation(m2, count))
+    }
 }
 
-impl<T> Next<T, !> for StandardDeviation<T>
+impl<T> Next<T, Raw> for StandardDeviation<T>
 where
     T: Copy
         + Zero
-        + One
         + FromPrimitive
         + Add<Output = T>
         + Div<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
         + AddAssign
-        + PartialEq,
+        + PartialOrd
+        + Sqrt,
 {
     type Output = T;
 
@@ -125,21 +382,16 @@ where
         let old_val = self.vec[self.index];
         self.vec[self.index] = input;
 
-        if self.count < self.n {
+        self.evicted = if self.count < self.n {
             self.count += 1;
-            let delta = input - self.m;
-            self.m += delta / T::from_u32(self.count).expect("Woot ?");
-            let delta2 = input - self.m;
-            self.m2 += delta * delta2;
+            None
         } else {
-            let delta = input - old_val;
-            let old_m = self.m;
-            self.m += delta / T::from_u32(self.n).expect("Woot ?");
-            let delta2 = input - self.m + old_val - old_m;
-            self.m2 += delta * delta2;
-        }
+            Some(old_val)
+        };
+        self.prev_m = self.m;
+        self.prev_m2 = self.m2;
 
-        sqrt(self.m2 / T::from_u32(self.count).expect("Woot ?"))
+        self.update_moments(input)
     }
 }
 
@@ -148,14 +400,14 @@ where
     U: Close<T>,
     T: Copy
         + Zero
-        + One
         + FromPrimitive
         + Add<Output = T>
         + Div<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
         + AddAssign
-        + PartialEq,
+        + PartialOrd
+        + Sqrt,
 {
     type Output = T;
 
@@ -164,6 +416,85 @@ where
     }
 }
 
+impl<T> UpdateLast<T, Raw> for StandardDeviation<T>
+where
+    T: Copy
+        + Zero
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + AddAssign
+        + PartialOrd
+        + Sqrt,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        if self.count == 0 {
+            return self.next(input);
+        }
+
+        self.vec[self.index] = input;
+        self.update_moments(input)
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for StandardDeviation<T>
+where
+    U: Close<T>,
+    T: Copy
+        + Zero
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + AddAssign
+        + PartialOrd
+        + Sqrt,
+{
+    fn update_last(&mut self, input: &'a U) -> Self::Output {
+        self.update_last(input.close())
+    }
+}
+
+impl<T> Peek<T, Raw> for StandardDeviation<T>
+where
+    T: Copy
+        + Zero
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + AddAssign
+        + PartialOrd
+        + Sqrt,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        self.peek_with_mean(input).1
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for StandardDeviation<T>
+where
+    U: Close<T>,
+    T: Copy
+        + Zero
+        + FromPrimitive
+        + Add<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + AddAssign
+        + PartialOrd
+        + Sqrt,
+{
+    fn peek(&self, input: &'a U) -> Self::Output {
+        self.peek(input.close())
+    }
+}
+
 impl<T> Reset for StandardDeviation<T>
 where
     T: Zero,
@@ -173,12 +504,25 @@ where
         self.count = 0;
         self.m = T::zero();
         self.m2 = T::zero();
+        self.prev_m = T::zero();
+        self.prev_m2 = T::zero();
+        self.evicted = None;
         for i in 0..(self.n as usize) {
             self.vec[i] = T::zero();
         }
     }
 }
 
+impl<T> Warmup for StandardDeviation<T> {
+    fn warmup_period(&self) -> usize {
+        self.n as usize
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count == self.n
+    }
+}
+
 impl<T> Default for StandardDeviation<T>
 where
     T: Copy + Zero,
@@ -190,7 +534,31 @@ where
 
 impl<T> fmt::Display for StandardDeviation<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "SD({})", self.n)
+        write!(f, "SD({}", self.n)?;
+        // The defaults are implied
+        if self.deviation_type != DeviationType::Population
+            || self.update_mode != UpdateMode::Incremental
+        {
+            write!(f, ", {}, {}", self.deviation_type, self.update_mode)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<T> FromStr for StandardDeviation<T>
+where
+    T: Copy + Zero,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "SD", 1).or_else(|_| parse_params(s, "SD", 3))?;
+        let n = parse_param(s, params[0])?;
+        let (deviation_type, update_mode) = match params.len() {
+            3 => (parse_param(s, params[1])?, parse_param(s, params[2])?),
+            _ => (DeviationType::Population, UpdateMode::Incremental),
+        };
+        Self::with_options(n, deviation_type, update_mode)
     }
 }
 
@@ -233,6 +601,151 @@ mod tests {
         assert_eq!(round(sd.next(&bar(100.0))), 35.355);
     }
 
+    #[test]
+    fn test_next_constant_values() {
+        let mut sd = StandardDeviation::<f64>::new(3).unwrap();
+        for &value in &[0.3, 0.1, 0.7, 0.7, 0.7, 0.7, 0.7, 0.7] {
+            sd.next(value);
+        }
+        // the variance may end up slightly negative because of rounding errors
+        let value = sd.next(0.7);
+        assert!((0.0..1e-6).contains(&value));
+    }
+
+    #[test]
+    fn test_next_sample() {
+        let mut sd = StandardDeviation::<f64>::with_options(
+            4,
+            DeviationType::Sample,
+            UpdateMode::Incremental,
+        )
+        .unwrap();
+        assert_eq!(sd.next(10.0), 0.0);
+        assert_eq!(round(sd.next(20.0)), 7.071);
+        assert_eq!(sd.next(30.0), 10.0);
+        assert_eq!(round(sd.next(20.0)), 8.165);
+        assert_eq!(round(sd.next(10.0)), 8.165);
+        assert_eq!(round(sd.next(100.0)), 40.825);
+    }
+
+    #[test]
+    fn test_mean_variance_z_score() {
+        let mut sd = StandardDeviation::<f64>::new(4).unwrap();
+        assert_eq!(sd.mean(), 0.0);
+        assert_eq!(sd.variance(), 0.0);
+        assert_eq!(sd.z_score(), 0.0);
+
+        sd.next(10.0);
+        assert_eq!(sd.mean(), 10.0);
+        // no deviation yet
+        assert_eq!(sd.z_score(), 0.0);
+
+        sd.next(20.0);
+        sd.next(30.0);
+        sd.next(20.0);
+        assert_eq!(sd.mean(), 20.0);
+        assert_eq!(sd.variance(), 50.0);
+        assert_eq!(sd.z_score(), 0.0);
+
+        sd.next(40.0);
+        assert_eq!(sd.mean(), 27.5);
+        assert_eq!(sd.variance(), 68.75);
+        assert_eq!(round(sd.z_score()), 1.508);
+
+        let mut sd =
+            StandardDeviation::<f64>::with_options(4, DeviationType::Sample, UpdateMode::Stable)
+                .unwrap();
+        sd.next(10.0);
+        assert_eq!(sd.variance(), 0.0);
+        for &value in &[20.0, 30.0, 20.0] {
+            sd.next(value);
+        }
+        assert_eq!(sd.mean(), 20.0);
+        assert_eq!(round(sd.variance()), 66.667);
+    }
+
+    // A long-running stream of large values with a small deviation, with some spikes
+    fn stream(i: usize) -> f64 {
+        let spike = if i % 1000 < 10 { 1e5 } else { 0.0 };
+        1e6 + ((i * 7919) % 101) as f64 * 0.01 + spike
+    }
+
+    #[test]
+    fn test_stable_mode() {
+        let n = 10;
+        let mut incremental = StandardDeviation::<f64>::new(n).unwrap();
+        let mut stable = StandardDeviation::<f64>::with_options(
+            n,
+            DeviationType::Population,
+            UpdateMode::Stable,
+        )
+        .unwrap();
+
+        let count = 100_000;
+        let (mut drifted, mut deviation) = (0.0, 0.0);
+        for i in 0..count {
+            drifted = incremental.next(stream(i));
+            deviation = stable.next(stream(i));
+        }
+
+        // computed in two passes over the last n values
+        let window: Vec<f64> = (count - n as usize..count).map(stream).collect();
+        let mean = window.iter().sum::<f64>() / n as f64;
+        let m2: f64 = window
+            .iter()
+            .map(|value| (value - mean) * (value - mean))
+            .sum();
+        let expected = (m2 / n as f64).sqrt();
+
+        assert!((deviation - expected).abs() < 1e-6);
+        assert!((drifted - expected).abs() > 1e-3);
+    }
+
+    #[test]
+    fn test_stable_update_last_and_peek() {
+        let values: Vec<f64> = (0..30).map(stream).collect();
+
+        for &deviation_type in &[DeviationType::Population, DeviationType::Sample] {
+            let mut sd =
+                StandardDeviation::<f64>::with_options(3, deviation_type, UpdateMode::Stable)
+                    .unwrap();
+            let mut expected =
+                StandardDeviation::<f64>::with_options(3, deviation_type, UpdateMode::Stable)
+                    .unwrap();
+
+            for &value in &values {
+                let output = expected.next(value);
+                assert_eq!(sd.peek(value), output);
+
+                sd.next(value + 5.0);
+                assert_eq!(sd.update_last(value), output);
+                assert_eq!(sd.z_score(), expected.z_score());
+            }
+        }
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_next_decimal() {
+        use rust_decimal::Decimal;
+
+        let mut sd = StandardDeviation::<Decimal>::new(4).unwrap();
+        assert_eq!(sd.next(Decimal::from(10)), Decimal::ZERO);
+        assert_eq!(sd.next(Decimal::from(20)), Decimal::from(5));
+        assert_eq!(
+            sd.next(Decimal::from(30)).round_dp(3),
+            Decimal::new(8165, 3)
+        );
+    }
+
+    #[test]
+    fn test_next_f32() {
+        let mut sd = StandardDeviation::<f32>::new(4).unwrap();
+        assert_eq!(sd.next(10.0), 0.0);
+        assert_eq!(sd.next(20.0), 5.0);
+        assert_eq!((sd.next(30.0) * 1000.0).round() / 1000.0, 8.165);
+    }
+
     #[test]
     fn test_reset() {
         let mut sd = StandardDeviation::<f64>::new(4).unwrap();
@@ -253,5 +766,26 @@ mod tests {
     fn test_display() {
         let sd = StandardDeviation::<f64>::new(5).unwrap();
         assert_eq!(format!("{}", sd), "SD(5)");
+
+        let sd =
+            StandardDeviation::<f64>::with_options(5, DeviationType::Sample, UpdateMode::Stable)
+                .unwrap();
+        assert_eq!(format!("{}", sd), "SD(5, SAMPLE, STABLE)");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let sd: StandardDeviation<f64> = "SD(20, SAMPLE, INCREMENTAL)".parse().unwrap();
+        assert_eq!(sd.length(), 20);
+        assert_eq!(sd.deviation_type(), DeviationType::Sample);
+        assert_eq!(sd.update_mode(), UpdateMode::Incremental);
+
+        let sd: StandardDeviation<f64> = "SD(20, POPULATION, INCREMENTAL)".parse().unwrap();
+        assert_eq!(format!("{}", sd), "SD(20)");
+
+        assert!("SD(20, SAMPLE)".parse::<StandardDeviation<f64>>().is_err());
+        assert!("SD(20, N-1, STABLE)"
+            .parse::<StandardDeviation<f64>>()
+            .is_err());
     }
 }
@@ -1,10 +1,12 @@
-use std::fmt;
-use std::ops::Sub;
+use core::fmt;
+use core::ops::Sub;
+use core::str::FromStr;
 
 use num_traits::{Signed, Zero};
 
-use crate::helpers::max3;
-use crate::{Close, High, Low, Next, Reset};
+use crate::errors::*;
+use crate::helpers::{max3, parse_params};
+use crate::{Close, High, Low, Next, Peek, Raw, Reset, UpdateLast, Warmup};
 
 /// The range of a day's trading is simply _high_ - _low_.
 /// The true range extends it to yesterday's closing price if it was outside of today's range.
@@ -51,13 +53,18 @@ use crate::{Close, High, Low, Next, Reset};
 /// }
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TrueRange<T> {
     prev_close: Option<T>,
+    close: Option<T>,
 }
 
 impl<T> TrueRange<T> {
     pub fn new() -> Self {
-        Self { prev_close: None }
+        Self {
+            prev_close: None,
+            close: None,
+        }
     }
 }
 
@@ -73,18 +80,25 @@ impl<T> fmt::Display for TrueRange<T> {
     }
 }
 
-impl<T> Next<T, !> for TrueRange<T>
+impl<T> FromStr for TrueRange<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_params(s, "TRUE_RANGE", 0)?;
+        Ok(Self::new())
+    }
+}
+
+impl<T> Next<T, Raw> for TrueRange<T>
 where
     T: Copy + Sub<Output = T> + PartialOrd + Zero + Signed,
 {
     type Output = T;
 
     fn next(&mut self, input: T) -> Self::Output {
-        let distance = match self.prev_close {
-            Some(prev) => (input - prev).abs(),
-            None => T::zero(),
-        };
-        self.prev_close = Some(input);
+        let distance = self.peek(input);
+        self.prev_close = self.close;
+        self.close = Some(input);
         distance
     }
 }
@@ -97,7 +111,32 @@ where
     type Output = T;
 
     fn next(&mut self, bar: &'a U) -> Self::Output {
-        let max_dist = match self.prev_close {
+        let max_dist = self.peek(bar);
+        self.prev_close = self.close;
+        self.close = Some(bar.close());
+        max_dist
+    }
+}
+
+impl<T> Peek<T, Raw> for TrueRange<T>
+where
+    T: Copy + Sub<Output = T> + PartialOrd + Zero + Signed,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        match self.close {
+            Some(prev) => (input - prev).abs(),
+            None => T::zero(),
+        }
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for TrueRange<T>
+where
+    U: High<T> + Low<T> + Close<T>,
+    T: Copy + Sub<Output = T> + PartialOrd + Signed,
+{
+    fn peek(&self, bar: &'a U) -> Self::Output {
+        match self.close {
             Some(prev_close) => {
                 let dist1 = bar.high() - bar.low();
                 let dist2 = (bar.high() - prev_close).abs();
@@ -105,15 +144,51 @@ where
                 max3(dist1, dist2, dist3)
             }
             None => bar.high() - bar.low(),
-        };
-        self.prev_close = Some(bar.close());
-        max_dist
+        }
+    }
+}
+
+impl<T> UpdateLast<T, Raw> for TrueRange<T>
+where
+    T: Copy + Sub<Output = T> + PartialOrd + Zero + Signed,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        if self.close.is_some() {
+            self.close = self.prev_close;
+        }
+        self.next(input)
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for TrueRange<T>
+where
+    U: High<T> + Low<T> + Close<T>,
+    T: Copy + Sub<Output = T> + PartialOrd + Signed,
+{
+    fn update_last(&mut self, bar: &'a U) -> Self::Output {
+        if self.close.is_some() {
+            self.close = self.prev_close;
+        }
+        self.next(bar)
     }
 }
 
 impl<T> Reset for TrueRange<T> {
     fn reset(&mut self) {
         self.prev_close = None;
+        self.close = None;
+    }
+}
+
+impl<T> Warmup for TrueRange<T> {
+    // The range of the first bar is already meaningful, but the first raw value has no previous
+    // close to be compared to, so both take a second input to be sure
+    fn warmup_period(&self) -> usize {
+        2
+    }
+
+    fn is_ready(&self) -> bool {
+        self.prev_close.is_some()
     }
 }
 
@@ -145,6 +220,33 @@ mod tests {
         assert_eq!(tr.next(&bar3), 4.5);
     }
 
+    #[test]
+    fn test_next_fixed() {
+        use crate::{DataItem, Fixed};
+
+        fn bar(high: i64, low: i64, close: i64) -> DataItem<Fixed<2>> {
+            DataItem::builder()
+                .open(Fixed::from_ticks(close))
+                .high(Fixed::from_ticks(high))
+                .low(Fixed::from_ticks(low))
+                .close(Fixed::from_ticks(close))
+                .volume(Fixed::from_ticks(0))
+                .build()
+                .unwrap()
+        }
+
+        let mut tr = TrueRange::<Fixed<2>>::new();
+        assert_eq!(tr.next(&bar(1000, 750, 900)), Fixed::from_ticks(250));
+        assert_eq!(tr.next(&bar(1100, 900, 950)), Fixed::from_ticks(200));
+        // gap down from the previous close
+        assert_eq!(tr.next(&bar(900, 500, 800)), Fixed::from_ticks(450));
+
+        let mut tr = TrueRange::<Fixed<2>>::new();
+        assert_eq!(tr.next(Fixed::from_ticks(250)), Fixed::from_ticks(0));
+        assert_eq!(tr.next(Fixed::from_ticks(360)), Fixed::from_ticks(110));
+        assert_eq!(tr.next(Fixed::from_ticks(330)), Fixed::from_ticks(30));
+    }
+
     #[test]
     fn test_reset() {
         let mut tr = TrueRange::<f64>::new();
@@ -160,6 +262,28 @@ mod tests {
         assert_eq!(tr.next(&bar3), 45.0);
     }
 
+    #[test]
+    fn test_warmup() {
+        let mut tr = TrueRange::<f64>::new();
+        assert_eq!(tr.warmup_period(), 2);
+
+        // the first raw value has no previous one to be compared to
+        tr.next(10.0);
+        assert!(!tr.is_ready());
+        tr.update_last(11.0);
+        assert!(!tr.is_ready());
+        tr.next(12.0);
+        assert!(tr.is_ready());
+
+        // the same goes for bars, whatever the range of the first one
+        tr.reset();
+        tr.next(&Bar::new().high(12).low(9).close(10));
+        assert_eq!(tr.warmup_period(), 2);
+        assert!(!tr.is_ready());
+        tr.next(&Bar::new().high(12).low(9).close(11));
+        assert!(tr.is_ready());
+    }
+
     #[test]
     fn test_default() {
         TrueRange::<f64>::default();
@@ -0,0 +1,374 @@
+use core::fmt;
+use core::ops::{Div, Mul};
+use core::str::FromStr;
+
+use num_traits::{FromPrimitive, Zero};
+
+use crate::errors::*;
+use crate::helpers::{parse_param, parse_params};
+use crate::{Close, High, Low, Next, Peek, Reset, Timestamp, UpdateLast, Volume, Warmup};
+
+/// Volume weighted average price (VWAP), anchored to sessions.
+///
+/// The VWAP is the average typical price of a session, weighted by volume.
+/// When bars carry a [Timestamp](../trait.Timestamp.html), the sums start over with the
+/// first bar of every session, otherwise they run from the first bar.
+///
+/// # Formula
+///
+/// Typical Price(TP) = (High + Low + Close)/3
+///
+/// VWAP = Sum(TP x Volume) / Sum(Volume)
+///
+/// Where the sums cover the bars of the current session. Until some volume is traded,
+/// the VWAP is the typical price.
+///
+/// # Parameters
+///
+/// * _session_length_ - length of a session in milliseconds, integer greater than 0.
+///   Sessions are aligned to the Unix epoch, so daily sessions start at midnight UTC.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::VolumeWeightedAveragePrice;
+/// use ta::{DataItem, Next};
+///
+/// const DAY: i64 = 86_400_000;
+/// let mut vwap = VolumeWeightedAveragePrice::<f64>::new(DAY).unwrap();
+///
+/// let bar = |timestamp, close, volume| {
+///     DataItem::builder()
+///         .open(close)
+///         .high(close)
+///         .low(close)
+///         .close(close)
+///         .volume(volume)
+///         .timestamp(timestamp)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// assert_eq!(vwap.next(&bar(0, 10.0, 100.0)), 10.0);
+/// assert_eq!(vwap.next(&bar(60_000, 13.0, 200.0)), 12.0);
+/// // a new day starts over
+/// assert_eq!(vwap.next(&bar(DAY, 20.0, 100.0)), 20.0);
+/// ```
+///
+/// # Links
+///
+/// * [Volume-weighted average price, Wikipedia](https://en.wikipedia.org/wiki/Volume-weighted_average_price)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeWeightedAveragePrice<T> {
+    session_length: i64,
+    session: Option<i64>,
+    price_volume: T,
+    volume: T,
+    count: usize,
+    // State before the latest input, restored by `update_last`
+    saved_session: Option<i64>,
+    saved_price_volume: T,
+    saved_volume: T,
+}
+
+impl<T> VolumeWeightedAveragePrice<T>
+where
+    T: Zero,
+{
+    pub fn new(session_length: i64) -> Result<Self> {
+        if session_length <= 0 {
+            return Err(Error::invalid_parameter(
+                "VWAP",
+                "session_length",
+                session_length,
+                "greater than 0",
+            ));
+        }
+        Ok(Self {
+            session_length,
+            session: None,
+            price_volume: T::zero(),
+            volume: T::zero(),
+            count: 0,
+            saved_session: None,
+            saved_price_volume: T::zero(),
+            saved_volume: T::zero(),
+        })
+    }
+
+    pub fn session_length(&self) -> i64 {
+        self.session_length
+    }
+}
+
+impl<T> VolumeWeightedAveragePrice<T>
+where
+    T: Copy + Zero + Mul<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    // Returns the session, the sums and the VWAP once `input` is added
+    fn accumulate<U>(&self, input: &U) -> (Option<i64>, T, T, T)
+    where
+        U: High<T> + Low<T> + Close<T> + Volume<T> + Timestamp,
+    {
+        let session = input
+            .timestamp()
+            .map(|timestamp| timestamp.div_euclid(self.session_length));
+        let (price_volume, volume) = if session.is_some() && session != self.session {
+            (T::zero(), T::zero())
+        } else {
+            (self.price_volume, self.volume)
+        };
+
+        let typical_price = (input.high() + input.low() + input.close())
+            / T::from_u32(3).expect("constant fits in T");
+        let price_volume = price_volume + typical_price * input.volume();
+        let volume = volume + input.volume();
+        let vwap = if volume.is_zero() {
+            typical_price
+        } else {
+            price_volume / volume
+        };
+        (session.or(self.session), price_volume, volume, vwap)
+    }
+}
+
+impl<'a, U, T> Next<&'a U, T> for VolumeWeightedAveragePrice<T>
+where
+    U: High<T> + Low<T> + Close<T> + Volume<T> + Timestamp,
+    T: Copy + Zero + Mul<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    type Output = T;
+
+    fn next(&mut self, input: &'a U) -> T {
+        let (session, price_volume, volume, vwap) = self.accumulate(input);
+
+        self.saved_session = self.session;
+        self.saved_price_volume = self.price_volume;
+        self.saved_volume = self.volume;
+
+        self.session = session;
+        self.price_volume = price_volume;
+        self.volume = volume;
+        self.count = self.count.saturating_add(1);
+        vwap
+    }
+}
+
+impl<'a, U, T> UpdateLast<&'a U, T> for VolumeWeightedAveragePrice<T>
+where
+    U: High<T> + Low<T> + Close<T> + Volume<T> + Timestamp,
+    T: Copy + Zero + Mul<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    fn update_last(&mut self, input: &'a U) -> T {
+        if self.count > 0 {
+            self.session = self.saved_session;
+            self.price_volume = self.saved_price_volume;
+            self.volume = self.saved_volume;
+            self.count -= 1;
+        }
+        self.next(input)
+    }
+}
+
+impl<'a, U, T> Peek<&'a U, T> for VolumeWeightedAveragePrice<T>
+where
+    U: High<T> + Low<T> + Close<T> + Volume<T> + Timestamp,
+    T: Copy + Zero + Mul<Output = T> + Div<Output = T> + FromPrimitive,
+{
+    fn peek(&self, input: &'a U) -> T {
+        self.accumulate(input).3
+    }
+}
+
+impl<T> Reset for VolumeWeightedAveragePrice<T>
+where
+    T: Zero,
+{
+    fn reset(&mut self) {
+        self.session = None;
+        self.price_volume = T::zero();
+        self.volume = T::zero();
+        self.count = 0;
+        self.saved_session = None;
+        self.saved_price_volume = T::zero();
+        self.saved_volume = T::zero();
+    }
+}
+
+impl<T> Warmup for VolumeWeightedAveragePrice<T> {
+    fn warmup_period(&self) -> usize {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= 1
+    }
+}
+
+impl<T> Default for VolumeWeightedAveragePrice<T>
+where
+    T: Zero,
+{
+    fn default() -> Self {
+        // one day
+        Self::new(86_400_000).unwrap()
+    }
+}
+
+impl<T> fmt::Display for VolumeWeightedAveragePrice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VWAP({})", self.session_length)
+    }
+}
+
+impl<T> FromStr for VolumeWeightedAveragePrice<T>
+where
+    T: Zero,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let params = parse_params(s, "VWAP", 1)?;
+        let session_length = parse_param(s, params[0])?;
+        Self::new(session_length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    const HOUR: i64 = 3_600_000;
+
+    fn bar(timestamp: i64, close: f64, volume: f64) -> Bar {
+        Bar::new()
+            .high(close + 1.0)
+            .low(close - 1.0)
+            .close(close)
+            .volume(volume)
+            .timestamp(timestamp)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeWeightedAveragePrice::<f64>::new(0).is_err());
+        assert!(VolumeWeightedAveragePrice::<f64>::new(-HOUR).is_err());
+        assert!(VolumeWeightedAveragePrice::<f64>::new(HOUR).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut vwap = VolumeWeightedAveragePrice::<f64>::new(HOUR).unwrap();
+
+        assert_eq!(vwap.next(&bar(0, 10.0, 0.0)), 10.0);
+        assert_eq!(vwap.next(&bar(60_000, 10.0, 100.0)), 10.0);
+        assert_eq!(vwap.next(&bar(120_000, 16.0, 200.0)), 14.0);
+        assert_eq!(vwap.next(&bar(HOUR, 20.0, 100.0)), 20.0);
+        assert_eq!(vwap.next(&bar(HOUR + 1, 30.0, 300.0)), 27.5);
+    }
+
+    #[test]
+    fn test_next_before_epoch() {
+        let mut vwap = VolumeWeightedAveragePrice::<f64>::new(HOUR).unwrap();
+
+        assert_eq!(vwap.next(&bar(-HOUR, 10.0, 100.0)), 10.0);
+        assert_eq!(vwap.next(&bar(-1, 16.0, 200.0)), 14.0);
+        assert_eq!(vwap.next(&bar(0, 20.0, 100.0)), 20.0);
+    }
+
+    #[test]
+    fn test_next_without_timestamps() {
+        let mut vwap = VolumeWeightedAveragePrice::<f64>::new(HOUR).unwrap();
+        let bar = |close, volume| {
+            Bar::new()
+                .high(close)
+                .low(close)
+                .close(close)
+                .volume(volume)
+        };
+
+        assert_eq!(vwap.next(&bar(10.0, 100.0)), 10.0);
+        assert_eq!(vwap.next(&bar(16.0, 200.0)), 14.0);
+        assert_eq!(vwap.next(&bar(2.0, 300.0)), 8.0);
+    }
+
+    #[test]
+    fn test_update_last() {
+        let bars = [
+            bar(0, 10.0, 100.0),
+            bar(60_000, 16.0, 200.0),
+            bar(HOUR, 20.0, 100.0),
+            bar(HOUR + 1, 30.0, 300.0),
+            bar(2 * HOUR, 5.0, 0.0),
+            bar(2 * HOUR + 1, 7.0, 50.0),
+        ];
+
+        let mut vwap = VolumeWeightedAveragePrice::<f64>::new(HOUR).unwrap();
+        let expected: Vec<_> = bars.iter().map(|bar| vwap.next(bar)).collect();
+
+        vwap.reset();
+        for (i, bar) in bars.iter().enumerate() {
+            vwap.next(&bars[(i + 3) % bars.len()]);
+            assert_eq!(vwap.update_last(bar), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut vwap = VolumeWeightedAveragePrice::<f64>::new(HOUR).unwrap();
+        vwap.next(&bar(0, 10.0, 100.0));
+
+        assert_eq!(vwap.peek(&bar(60_000, 16.0, 200.0)), 14.0);
+        assert_eq!(vwap.peek(&bar(HOUR, 20.0, 100.0)), 20.0);
+        assert_eq!(vwap.next(&bar(60_000, 16.0, 200.0)), 14.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vwap = VolumeWeightedAveragePrice::<f64>::new(HOUR).unwrap();
+        vwap.next(&bar(0, 10.0, 100.0));
+        assert!(vwap.is_ready());
+
+        vwap.reset();
+        assert!(!vwap.is_ready());
+        assert_eq!(vwap.next(&bar(60_000, 16.0, 200.0)), 16.0);
+    }
+
+    #[test]
+    fn test_default() {
+        let vwap = VolumeWeightedAveragePrice::<f64>::default();
+        assert_eq!(vwap.session_length(), 24 * HOUR);
+    }
+
+    #[test]
+    fn test_display() {
+        let vwap = VolumeWeightedAveragePrice::<f64>::new(HOUR).unwrap();
+        assert_eq!(format!("{}", vwap), "VWAP(3600000)");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let vwap: VolumeWeightedAveragePrice<f64> = "VWAP(3600000)".parse().unwrap();
+        assert_eq!(format!("{}", vwap), "VWAP(3600000)");
+
+        assert!("VWAP(0)"
+            .parse::<VolumeWeightedAveragePrice<f64>>()
+            .is_err());
+        assert!("VWAP()".parse::<VolumeWeightedAveragePrice<f64>>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut vwap = VolumeWeightedAveragePrice::<f64>::new(HOUR).unwrap();
+        vwap.next(&bar(0, 10.0, 100.0));
+
+        let json = serde_json::to_string(&vwap).unwrap();
+        let mut restored: VolumeWeightedAveragePrice<f64> = serde_json::from_str(&json).unwrap();
+        for next in &[bar(60_000, 16.0, 200.0), bar(HOUR, 20.0, 100.0)] {
+            assert_eq!(restored.next(next), vwap.next(next));
+        }
+    }
+}
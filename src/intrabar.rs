@@ -0,0 +1,156 @@
+use core::fmt;
+
+use crate::{Reset, UpdateLast, Warmup};
+
+/// Wraps an indicator to feed it with the ticks of a bar that is still forming.
+///
+/// Every call to `update` replaces the latest input of the indicator until the bar is
+/// committed, the first tick after `commit` starts a new period.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::Intrabar;
+///
+/// let mut sma = Intrabar::new(SimpleMovingAverage::<f64>::new(2).unwrap());
+/// assert_eq!(sma.update(10.0), 10.0);
+/// sma.commit();
+///
+/// assert_eq!(sma.update(11.0), 10.5);
+/// assert_eq!(sma.update(13.0), 11.5);
+/// assert_eq!(sma.update(12.0), 11.0);
+/// sma.commit();
+///
+/// assert_eq!(sma.update(14.0), 13.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Intrabar<N> {
+    indicator: N,
+    is_open: bool,
+}
+
+impl<N> Intrabar<N> {
+    pub fn new(indicator: N) -> Self {
+        Self {
+            indicator,
+            is_open: false,
+        }
+    }
+
+    /// Feeds a tick of the current bar, and returns the output as if the bar closed on it.
+    pub fn update<T, U>(&mut self, input: T) -> N::Output
+    where
+        N: UpdateLast<T, U>,
+    {
+        if self.is_open {
+            self.indicator.update_last(input)
+        } else {
+            self.is_open = true;
+            self.indicator.next(input)
+        }
+    }
+
+    /// Closes the current bar, the next tick starts a new period.
+    pub fn commit(&mut self) {
+        self.is_open = false;
+    }
+
+    /// Returns true while the current bar is not committed.
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// Returns the wrapped indicator.
+    pub fn indicator(&self) -> &N {
+        &self.indicator
+    }
+
+    pub fn into_inner(self) -> N {
+        self.indicator
+    }
+}
+
+impl<N: Reset> Reset for Intrabar<N> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.is_open = false;
+    }
+}
+
+impl<N: Warmup> Warmup for Intrabar<N> {
+    fn warmup_period(&self) -> usize {
+        self.indicator.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.indicator.is_ready()
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for Intrabar<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.indicator.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, Maximum, MoneyFlowIndex};
+    use crate::test_helper::*;
+    use crate::Next;
+
+    #[test]
+    fn test_update() {
+        let mut ema = Intrabar::new(ExponentialMovingAverage::<f64>::new(3).unwrap());
+        let mut expected = ExponentialMovingAverage::<f64>::new(3).unwrap();
+
+        for &close in &[2.0, 5.0, 1.0, 6.25] {
+            ema.update(close + 1.0);
+            ema.update(close - 1.0);
+            assert!(ema.is_open());
+            assert_eq!(ema.update(close), expected.next(close));
+            ema.commit();
+        }
+    }
+
+    #[test]
+    fn test_update_with_bars() {
+        let mut max = Intrabar::new(Maximum::new(2).unwrap());
+        let bar = |high| Bar::new().high(high);
+
+        assert_eq!(max.update(&bar(4.0)), 4.0);
+        max.commit();
+        assert_eq!(max.update(&bar(5.0)), 5.0);
+        assert_eq!(max.update(&bar(3.0)), 4.0);
+        max.commit();
+        assert_eq!(max.update(&bar(2.0)), 3.0);
+
+        let mut mfi = Intrabar::new(MoneyFlowIndex::new(2).unwrap());
+        let bar = Bar::new().high(3).low(1).close(2).volume(500.0);
+        assert_eq!(mfi.update(&bar), 50.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut max = Intrabar::new(Maximum::<f64>::new(2).unwrap());
+        max.update(4.0);
+        max.reset();
+
+        assert!(!max.is_open());
+        assert_eq!(max.update(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Intrabar::<Maximum<f64>>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let max = Intrabar::new(Maximum::<f64>::new(2).unwrap());
+        assert_eq!(format!("{}", max), "MAX(2)");
+    }
+}
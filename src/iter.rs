@@ -0,0 +1,210 @@
+use core::marker::PhantomData;
+
+use crate::{Next, Raw};
+
+/// Extends iterators with indicator adapters.
+pub trait IteratorExt: Iterator + Sized {
+    /// Feeds every item to `indicator` and lazily yields its outputs.
+    ///
+    /// The items may be raw values, references to raw values, e.g. from `prices.iter()`, or
+    /// references to bars. The adapter owns the indicator, pass a clone to keep the original
+    /// untouched. Adapters can be chained to feed the output of one indicator to the next one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ta::indicators::{ExponentialMovingAverage, RateOfChange};
+    /// use ta::IteratorExt;
+    ///
+    /// let prices = vec![2.0, 5.0, 1.0, 6.25];
+    /// let ema = ExponentialMovingAverage::new(3).unwrap();
+    ///
+    /// let emas: Vec<f64> = prices.iter().indicate(ema.clone()).collect();
+    /// assert_eq!(emas, vec![2.0, 3.5, 2.25, 4.25]);
+    ///
+    /// let rocs: Vec<f64> = prices
+    ///     .into_iter()
+    ///     .indicate(ema)
+    ///     .indicate(RateOfChange::new(1).unwrap())
+    ///     .collect();
+    /// assert_eq!(rocs, vec![0.0, 75.0, -35.714285714285715, 88.88888888888889]);
+    /// ```
+    fn indicate<N, M>(self, indicator: N) -> Indicate<Self, N, M>
+    where
+        N: Feed<Self::Item, M>,
+    {
+        Indicate {
+            iter: self,
+            indicator,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+/// Feeds an item of an iterator to an indicator, see
+/// [IteratorExt::indicate](trait.IteratorExt.html#method.indicate).
+///
+/// It is implemented for every indicator, passing the items it takes as they are, or copying
+/// references to raw values. `M` tells both apart, it is always inferred.
+pub trait Feed<I, M> {
+    type Output;
+    fn feed(&mut self, item: I) -> Self::Output;
+}
+
+/// Marker of [Feed](trait.Feed.html) for items passed as they are to `Next<I, U>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsIs<U>(PhantomData<U>);
+
+/// Marker of [Feed](trait.Feed.html) for references to raw values, copied and passed to
+/// `Next<T, Raw>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Copied {}
+
+impl<N, I, U> Feed<I, AsIs<U>> for N
+where
+    N: Next<I, U>,
+{
+    type Output = N::Output;
+
+    fn feed(&mut self, item: I) -> Self::Output {
+        self.next(item)
+    }
+}
+
+impl<'a, N, T> Feed<&'a T, Copied> for N
+where
+    N: Next<T, Raw>,
+    T: Copy,
+{
+    type Output = N::Output;
+
+    fn feed(&mut self, item: &'a T) -> Self::Output {
+        self.next(*item)
+    }
+}
+
+/// An iterator that feeds the items of `I` to an indicator and yields its outputs.
+///
+/// It is created by [IteratorExt::indicate](trait.IteratorExt.html#method.indicate).
+#[derive(Debug, Clone)]
+pub struct Indicate<I, N, M> {
+    iter: I,
+    indicator: N,
+    marker: PhantomData<M>,
+}
+
+impl<I, N, M> Indicate<I, N, M> {
+    /// Returns the wrapped indicator in its current state.
+    pub fn indicator(&self) -> &N {
+        &self.indicator
+    }
+}
+
+impl<I, N, M> Iterator for Indicate<I, N, M>
+where
+    I: Iterator,
+    N: Feed<I::Item, M>,
+{
+    type Item = N::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.iter.next()?;
+        Some(self.indicator.feed(input))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, N, M> ExactSizeIterator for Indicate<I, N, M>
+where
+    I: ExactSizeIterator,
+    N: Feed<I::Item, M>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        ExponentialMovingAverage, Maximum, RelativeStrengthIndex, SimpleMovingAverage,
+    };
+    use crate::test_helper::*;
+    use crate::{Compute, Reset};
+
+    #[test]
+    fn test_indicate_values() {
+        let values = vec![4.0, 5.0, 6.0, 6.0, 6.0, 6.0, 2.0];
+        let sma = SimpleMovingAverage::<f64>::new(4).unwrap();
+
+        let expected = sma.clone().compute(&values);
+        let output: Vec<f64> = values.into_iter().indicate(sma).collect();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_indicate_value_refs() {
+        let values = vec![4.0, 5.0, 6.0, 6.0, 6.0, 6.0, 2.0];
+        let ema = ExponentialMovingAverage::<f64>::new(9).unwrap();
+
+        let expected = ema.clone().compute(&values);
+        let output: Vec<f64> = values.iter().indicate(ema).collect();
+        assert_eq!(output, expected);
+
+        let prices = [2.0, 5.0, 1.0, 6.25];
+        let emas: Vec<f64> = prices
+            .iter()
+            .indicate(ExponentialMovingAverage::new(3).unwrap())
+            .collect();
+        assert_eq!(emas, vec![2.0, 3.5, 2.25, 4.25]);
+
+        // chained after references to raw values
+        let output: Vec<f64> = values
+            .iter()
+            .indicate(Maximum::<f64>::new(2).unwrap())
+            .indicate(SimpleMovingAverage::new(2).unwrap())
+            .collect();
+        assert_eq!(output, vec![4.0, 4.5, 5.5, 6.0, 6.0, 6.0, 6.0]);
+    }
+
+    #[test]
+    fn test_indicate_bars() {
+        let bars: Vec<Bar> = [1.1, 4.0, 3.5, 2.0]
+            .iter()
+            .map(|&high| Bar::new().high(high))
+            .collect();
+
+        let output: Vec<f64> = bars.iter().indicate(Maximum::new(2).unwrap()).collect();
+        assert_eq!(output, vec![1.1, 4.0, 4.0, 3.5]);
+    }
+
+    #[test]
+    fn test_indicate_chain() {
+        let values = vec![10.0, 10.5, 10.0, 9.5, 11.0, 12.5];
+        let mut ema = ExponentialMovingAverage::<f64>::new(3).unwrap();
+        let mut rsi = RelativeStrengthIndex::<f64>::new(3).unwrap();
+
+        let expected: Vec<f64> = values.iter().map(|&v| rsi.next(ema.next(v))).collect();
+
+        ema.reset();
+        rsi.reset();
+        let output: Vec<f64> = values.into_iter().indicate(ema).indicate(rsi).collect();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_indicate_is_lazy() {
+        let mut iter = vec![4.0, 10.0, 4.0]
+            .into_iter()
+            .indicate(Maximum::<f64>::new(3).unwrap());
+
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(4.0));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next(), Some(10.0));
+        assert_eq!(format!("{}", iter.indicator()), "MAX(3)");
+    }
+}
@@ -8,7 +8,34 @@
 //! which are the core concept of the library.
 //!
 //! Since `Next<T>` is a generic trait, most of the indicators can work with both input types: `T` and more complex
-//! structures like [DataItem](struct.DataItem.html).
+//! structures like [DataItem](struct.DataItem.html). The right implementation is picked from the input type,
+//! so no annotations are needed at call sites.
+//!
+//! Any indicator can also be fed with a whole slice at once through [Compute](trait.Compute.html),
+//! or lazily with items of an iterator through [IteratorExt](trait.IteratorExt.html).
+//!
+//! Outputs are not meaningful until an indicator has consumed enough inputs, which is reported
+//! by [Warmup](trait.Warmup.html).
+//!
+//! The latest input can be replaced while a bar is still forming with
+//! [UpdateLast](trait.UpdateLast.html), or through the [Intrabar](struct.Intrabar.html) wrapper.
+//! The output of a possible next input can be previewed with [Peek](trait.Peek.html).
+//!
+//! Missing values, e.g. `NaN` prices, can be kept away from the state of an indicator with
+//! [MissingValues](struct.MissingValues.html), or rejected with
+//! [RejectMissing](struct.RejectMissing.html).
+//!
+//! Indicators can be composed with [NextExt](trait.NextExt.html), e.g. to get the EMA of an RSI.
+//!
+//! Prices can be fixed-point integer tick counts with [Fixed](struct.Fixed.html), instead of
+//! floats.
+//!
+//! Bars can be built from a stream of trades with the aggregators of the [bars](bars/index.html)
+//! module.
+//!
+//! Indicators are parsed from their `Display` output with `FromStr`, e.g. `"EMA(9)".parse()`,
+//! and [AnyIndicator](indicators/enum.AnyIndicator.html) picks the indicator from such a spec at
+//! runtime.
 //!
 //! # Example
 //! ```
@@ -29,6 +56,7 @@
 //! * Trend
 //!   * [Exponential Moving Average (EMA)](indicators/struct.ExponentialMovingAverage.html)
 //!   * [Simple Moving Average (SMA)](indicators/struct.SimpleMovingAverage.html)
+//!   * [Ichimoku Kinko Hyo](indicators/struct.Ichimoku.html), and its [signals](indicators/struct.IchimokuSignals.html)
 //! * Oscillators
 //!   * [Relative Strength Index (RSI)](indicators/struct.RelativeStrengthIndex.html)
 //!   * [Fast Stochastic](indicators/struct.FastStochastic.html)
@@ -38,30 +66,51 @@
 //! * Other
 //!   * [Standard Deviation (SD)](indicators/struct.StandardDeviation.html)
 //!   * [Bollinger Bands (BB)](indicators/struct.BollingerBands.html)
-//!   * [Maximum](indicators/struct.Maximum.html)
-//!   * [Minimum](indicators/struct.Minimum.html)
+//!   * [Maximum](indicators/struct.Maximum.html), and its [age](indicators/struct.MaximumAge.html)
+//!   * [Minimum](indicators/struct.Minimum.html), and its [age](indicators/struct.MinimumAge.html)
 //!   * [True Range](indicators/struct.TrueRange.html)
 //!   * [Average True Range (ATR)](indicators/struct.AverageTrueRange.html)
 //!   * [Efficiency Ratio (ER)](indicators/struct.EfficiencyRatio.html)
 //!   * [Rate of Change (ROC)](indicators/struct.RateOfChange.html)
 //!   * [On Balance Volume (OBV)](indicators/struct.OnBalanceVolume.html)
+//!   * [Volume Weighted Average Price (VWAP)](indicators/struct.VolumeWeightedAveragePrice.html)
 //!
-#![feature(never_type)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-#[macro_use]
-extern crate error_chain;
+extern crate alloc;
 
 #[cfg(test)]
 #[macro_use]
 mod test_helper;
 
 mod helpers;
+mod rolling;
 
 pub mod errors;
 pub mod indicators;
 
+pub mod bars;
+
 mod traits;
 pub use crate::traits::*;
 
 mod data_item;
-pub use crate::data_item::DataItem;
+pub use crate::data_item::{DataItem, ValidationPolicy};
+
+mod fixed;
+pub use crate::fixed::Fixed;
+
+mod iter;
+pub use crate::iter::{AsIs, Copied, Feed, Indicate, IteratorExt};
+
+mod when_ready;
+pub use crate::when_ready::WhenReady;
+
+mod missing_values;
+pub use crate::missing_values::{MissingPolicy, MissingValues, RejectMissing};
+
+mod intrabar;
+pub use crate::intrabar::Intrabar;
+
+mod chain;
+pub use crate::chain::{Chain, NextExt, Project};
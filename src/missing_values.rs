@@ -0,0 +1,470 @@
+use alloc::boxed::Box;
+use core::any::Any;
+use core::fmt;
+
+use crate::errors::*;
+use crate::{MaybeMissing, Next, Peek, Raw, Reset, Warmup};
+
+/// What [MissingValues](struct.MissingValues.html) does with an input carrying a missing value.
+///
+/// To get an error instead, see [RejectMissing](struct.RejectMissing.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MissingPolicy {
+    /// The input goes through the formula with [peek](trait.Peek.html#tymethod.peek), so a `NaN`
+    /// shows up in the output, but the state is left untouched.
+    Propagate,
+    /// The input is skipped, the output is `None`.
+    Skip,
+    /// The last valid input is fed again in place of the missing one, so windows move on.
+    HoldLast,
+}
+
+/// Wraps an indicator to keep missing values, e.g. `NaN` prices, away from its state.
+///
+/// A single `NaN` fed to an indicator otherwise poisons it: the sum of an SMA or the current
+/// value of an EMA stays `NaN` until the indicator is reset. The wrapped indicator is never fed
+/// with a missing value, whatever the [policy](enum.MissingPolicy.html), so it carries on with the
+/// next valid input.
+///
+/// The output is `None` for a missing input with `Skip`, and with `HoldLast` before the first
+/// valid input.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{MissingPolicy, MissingValues, Next};
+///
+/// let sma = SimpleMovingAverage::<f64>::new(2).unwrap();
+/// let mut sma = MissingValues::new(sma, MissingPolicy::Skip);
+///
+/// assert_eq!(sma.next(10.0), Some(10.0));
+/// assert_eq!(sma.next(f64::NAN), None);
+/// assert_eq!(sma.next(12.0), Some(11.0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MissingValues<N> {
+    indicator: N,
+    policy: MissingPolicy,
+    // last valid input, held by HoldLast; it is not serialized, so a restored wrapper holds
+    // nothing until its next valid input
+    #[cfg_attr(feature = "serde", serde(skip))]
+    held: Option<Held>,
+}
+
+impl<N> MissingValues<N> {
+    pub fn new(indicator: N, policy: MissingPolicy) -> Self {
+        Self {
+            indicator,
+            policy,
+            held: None,
+        }
+    }
+
+    pub fn policy(&self) -> MissingPolicy {
+        self.policy
+    }
+
+    /// Returns the wrapped indicator.
+    pub fn indicator(&self) -> &N {
+        &self.indicator
+    }
+
+    pub fn into_inner(self) -> N {
+        self.indicator
+    }
+
+    fn hold<I: Any + Clone>(&mut self, input: &I) {
+        if self.policy != MissingPolicy::HoldLast {
+            return;
+        }
+        match self.held.as_mut().and_then(|held| held.get_mut::<I>()) {
+            Some(held) => held.clone_from(input),
+            None => self.held = Some(Held(Box::new(input.clone()))),
+        }
+    }
+}
+
+impl<N, T> Next<T, Raw> for MissingValues<N>
+where
+    N: Peek<T, Raw>,
+    T: Copy + MaybeMissing + 'static,
+{
+    type Output = Option<N::Output>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        if !input.is_missing() {
+            self.hold(&input);
+            return Some(self.indicator.next(input));
+        }
+
+        match self.policy {
+            MissingPolicy::Propagate => Some(self.indicator.peek(input)),
+            MissingPolicy::Skip => None,
+            MissingPolicy::HoldLast => {
+                let held = *self.held.as_ref()?.get::<T>()?;
+                Some(self.indicator.next(held))
+            }
+        }
+    }
+}
+
+// The price type `T` is bound to be `MaybeMissing`, which `Raw` is not, to keep the implementation
+// for bars apart from the one for raw values.
+impl<'a, N, U, T, O> Next<&'a U, T> for MissingValues<N>
+where
+    N: for<'b> Peek<&'b U, T, Output = O>,
+    U: Clone + MaybeMissing + 'static,
+    T: MaybeMissing,
+{
+    type Output = Option<O>;
+
+    fn next(&mut self, input: &'a U) -> Self::Output {
+        if !input.is_missing() {
+            self.hold(input);
+            return Some(self.indicator.next(input));
+        }
+
+        match self.policy {
+            MissingPolicy::Propagate => Some(self.indicator.peek(input)),
+            MissingPolicy::Skip => None,
+            MissingPolicy::HoldLast => {
+                let held = self.held.as_ref()?.get::<U>()?;
+                Some(self.indicator.next(held))
+            }
+        }
+    }
+}
+
+impl<N: Reset> Reset for MissingValues<N> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.held = None;
+    }
+}
+
+impl<N: Warmup> Warmup for MissingValues<N> {
+    fn warmup_period(&self) -> usize {
+        self.indicator.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.indicator.is_ready()
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for MissingValues<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.indicator.fmt(f)
+    }
+}
+
+// Input held by HoldLast. Its type is erased, so that it does not show up in the type of the
+// wrapper, which is fed with raw values or bars alike.
+struct Held(Box<dyn HeldInput>);
+
+trait HeldInput: Any {
+    fn clone_box(&self) -> Box<dyn HeldInput>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<I: Any + Clone> HeldInput for I {
+    fn clone_box(&self) -> Box<dyn HeldInput> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl Held {
+    fn get<I: Any>(&self) -> Option<&I> {
+        self.0.as_any().downcast_ref()
+    }
+
+    fn get_mut<I: Any>(&mut self) -> Option<&mut I> {
+        self.0.as_any_mut().downcast_mut()
+    }
+}
+
+impl Clone for Held {
+    fn clone(&self) -> Self {
+        Held(self.0.clone_box())
+    }
+}
+
+impl fmt::Debug for Held {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Held")
+    }
+}
+
+/// Wraps an indicator to return `Error::MissingValue` for an input carrying a missing value.
+///
+/// As with [MissingValues](struct.MissingValues.html), the wrapped indicator is never fed with a
+/// missing value, so it carries on with the next valid input.
+///
+/// # Example
+///
+/// ```
+/// use ta::errors::Error;
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Next, RejectMissing};
+///
+/// let mut sma = RejectMissing::new(SimpleMovingAverage::<f64>::new(2).unwrap());
+///
+/// assert_eq!(sma.next(10.0), Ok(10.0));
+/// assert_eq!(sma.next(f64::NAN), Err(Error::MissingValue));
+/// assert_eq!(sma.next(12.0), Ok(11.0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RejectMissing<N> {
+    indicator: N,
+}
+
+impl<N> RejectMissing<N> {
+    pub fn new(indicator: N) -> Self {
+        Self { indicator }
+    }
+
+    /// Returns the wrapped indicator.
+    pub fn indicator(&self) -> &N {
+        &self.indicator
+    }
+
+    pub fn into_inner(self) -> N {
+        self.indicator
+    }
+}
+
+impl<N, T> Next<T, Raw> for RejectMissing<N>
+where
+    N: Next<T, Raw>,
+    T: MaybeMissing,
+{
+    type Output = Result<N::Output>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        if input.is_missing() {
+            return Err(Error::MissingValue);
+        }
+        Ok(self.indicator.next(input))
+    }
+}
+
+impl<'a, N, U, T> Next<&'a U, T> for RejectMissing<N>
+where
+    N: Next<&'a U, T>,
+    U: MaybeMissing,
+    T: MaybeMissing,
+{
+    type Output = Result<N::Output>;
+
+    fn next(&mut self, input: &'a U) -> Self::Output {
+        if input.is_missing() {
+            return Err(Error::MissingValue);
+        }
+        Ok(self.indicator.next(input))
+    }
+}
+
+impl<N: Reset> Reset for RejectMissing<N> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+impl<N: Warmup> Warmup for RejectMissing<N> {
+    fn warmup_period(&self) -> usize {
+        self.indicator.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.indicator.is_ready()
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for RejectMissing<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.indicator.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, Maximum, SimpleMovingAverage};
+    use crate::test_helper::*;
+    use alloc::vec::Vec;
+
+    fn sma(policy: MissingPolicy) -> MissingValues<SimpleMovingAverage<f64>> {
+        MissingValues::new(SimpleMovingAverage::new(2).unwrap(), policy)
+    }
+
+    #[test]
+    fn test_next_propagate() {
+        let mut sma = sma(MissingPolicy::Propagate);
+
+        assert!(sma.next(f64::NAN).unwrap().is_nan());
+        assert_eq!(sma.next(10.0), Some(10.0));
+        assert!(sma.next(f64::NAN).unwrap().is_nan());
+        assert_eq!(sma.next(12.0), Some(11.0));
+        assert_eq!(sma.next(14.0), Some(13.0));
+    }
+
+    #[test]
+    fn test_next_skip() {
+        let mut sma = sma(MissingPolicy::Skip);
+
+        assert_eq!(sma.next(f64::NAN), None);
+        assert_eq!(sma.next(10.0), Some(10.0));
+        assert_eq!(sma.next(f64::NAN), None);
+        assert_eq!(sma.next(f64::NAN), None);
+        assert_eq!(sma.next(12.0), Some(11.0));
+        assert_eq!(sma.next(14.0), Some(13.0));
+    }
+
+    #[test]
+    fn test_next_hold_last() {
+        let mut sma = sma(MissingPolicy::HoldLast);
+
+        assert_eq!(sma.next(f64::NAN), None);
+        assert_eq!(sma.next(10.0), Some(10.0));
+        assert_eq!(sma.next(12.0), Some(11.0));
+        // 12 is fed again
+        assert_eq!(sma.next(f64::NAN), Some(12.0));
+        assert_eq!(sma.next(14.0), Some(13.0));
+    }
+
+    #[test]
+    fn test_next_reject() {
+        let mut sma = RejectMissing::new(SimpleMovingAverage::<f64>::new(2).unwrap());
+
+        assert_eq!(sma.next(10.0), Ok(10.0));
+        assert_eq!(sma.next(f64::NAN), Err(Error::MissingValue));
+        assert_eq!(sma.next(12.0), Ok(11.0));
+    }
+
+    #[test]
+    fn test_state_recovers() {
+        let inputs = [4.0, 2.0, f64::NAN, 7.0, 1.0, f64::NAN, 5.0, 3.0];
+        let valid: Vec<f64> = inputs.iter().copied().filter(|x| !x.is_nan()).collect();
+
+        // without the wrapper, a NaN poisons the EMA for good
+        let mut ema = ExponentialMovingAverage::<f64>::new(3).unwrap();
+        for &input in &inputs {
+            ema.next(input);
+        }
+        assert!(ema.next(1.0).is_nan());
+
+        let mut expected = ExponentialMovingAverage::<f64>::new(3).unwrap();
+        let mut ema = MissingValues::new(
+            ExponentialMovingAverage::new(3).unwrap(),
+            MissingPolicy::Propagate,
+        );
+        let outputs: Vec<f64> = inputs
+            .iter()
+            .map(|&input| ema.next(input).unwrap())
+            .filter(|output| !output.is_nan())
+            .collect();
+        let expected: Vec<f64> = valid.iter().map(|&input| expected.next(input)).collect();
+        assert_eq!(outputs, expected);
+
+        // same for the sum of an SMA
+        let mut sma = SimpleMovingAverage::<f64>::new(2).unwrap();
+        sma.next(f64::NAN);
+        assert!(sma.next(1.0).is_nan());
+        assert!(sma.next(2.0).is_nan());
+
+        let mut sma = MissingValues::new(
+            SimpleMovingAverage::new(2).unwrap(),
+            MissingPolicy::Propagate,
+        );
+        sma.next(f64::NAN);
+        assert_eq!(sma.next(1.0), Some(1.0));
+        assert_eq!(sma.next(2.0), Some(1.5));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let bars = [
+            Bar::new().high(4).low(1).close(2),
+            Bar::new().high(f64::NAN).low(1).close(2),
+            Bar::new().high(3).low(1).close(2),
+        ];
+
+        let mut max = MissingValues::new(Maximum::new(2).unwrap(), MissingPolicy::HoldLast);
+        assert_eq!(max.next(&bars[0]), Some(4.0));
+        assert_eq!(max.next(&bars[1]), Some(4.0));
+        // the held bar is still in the window
+        assert_eq!(max.next(&bars[2]), Some(4.0));
+        assert_eq!(max.next(&bars[2]), Some(3.0));
+
+        let mut max = MissingValues::new(Maximum::new(2).unwrap(), MissingPolicy::Skip);
+        assert_eq!(max.next(&bars[0]), Some(4.0));
+        assert_eq!(max.next(&bars[1]), None);
+        assert_eq!(max.next(&bars[2]), Some(4.0));
+        assert_eq!(max.next(&bars[2]), Some(3.0));
+
+        let mut max = RejectMissing::new(Maximum::new(2).unwrap());
+        assert_eq!(max.next(&bars[0]), Ok(4.0));
+        assert_eq!(max.next(&bars[1]), Err(Error::MissingValue));
+        assert_eq!(max.next(&bars[2]), Ok(4.0));
+        assert_eq!(max.next(&bars[2]), Ok(3.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sma = sma(MissingPolicy::HoldLast);
+        sma.next(10.0);
+
+        sma.reset();
+        assert_eq!(sma.next(f64::NAN), None);
+        assert_eq!(sma.next(4.0), Some(4.0));
+    }
+
+    #[test]
+    fn test_warmup() {
+        let mut sma = sma(MissingPolicy::Skip);
+        assert_eq!(sma.warmup_period(), 2);
+
+        sma.next(10.0);
+        sma.next(f64::NAN);
+        assert!(!sma.is_ready());
+        sma.next(12.0);
+        assert!(sma.is_ready());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut sma = sma(MissingPolicy::HoldLast);
+        sma.next(10.0);
+
+        let json = serde_json::to_string(&sma).unwrap();
+        let mut restored: MissingValues<SimpleMovingAverage<f64>> =
+            serde_json::from_str(&json).unwrap();
+        // the held input is not restored
+        assert_eq!(restored.next(f64::NAN), None);
+        assert_eq!(restored.next(12.0), sma.next(12.0));
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = sma(MissingPolicy::HoldLast);
+        assert_eq!(sma.policy(), MissingPolicy::HoldLast);
+        assert_eq!(format!("{}", sma), "SMA(2)");
+
+        let sma = RejectMissing::new(SimpleMovingAverage::<f64>::new(2).unwrap());
+        assert_eq!(format!("{}", sma), "SMA(2)");
+    }
+}
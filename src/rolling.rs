@@ -0,0 +1,272 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+/// Which extremum a [RollingExtremum] tracks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Extremum {
+    Max,
+    Min,
+}
+
+impl Extremum {
+    // Whether `value` is at least as extreme as `other`
+    fn dominates<T: PartialOrd>(self, value: &T, other: &T) -> bool {
+        match self {
+            Extremum::Max => value >= other,
+            Extremum::Min => value <= other,
+        }
+    }
+}
+
+/// Highest or lowest value of a rolling window, in amortized O(1) per input.
+///
+/// It keeps a monotonic deque of the inputs which may still become the extremum: an input is
+/// dropped as soon as a more recent one is at least as extreme, since it cannot be the extremum
+/// of any later window. The front of the deque is the extremum of the window, the latest one on
+/// ties.
+///
+/// The latest input can be taken back, once, which is what `update_last` relies on.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RollingExtremum<T> {
+    extremum: Extremum,
+    length: usize,
+    // Number of inputs so far, the index of the next one
+    count: usize,
+    // Indices and values of the candidates, oldest first, each strictly more extreme than the
+    // ones after it
+    deque: VecDeque<(usize, T)>,
+    // Candidates dropped by the latest input, latest first, to take it back
+    evicted: Vec<(usize, T)>,
+    // Candidate which left the window with the latest input, to take it back
+    expired: Option<(usize, T)>,
+}
+
+impl<T> RollingExtremum<T> {
+    /// `length` must be greater than 0.
+    pub fn new(extremum: Extremum, length: usize) -> Self {
+        Self {
+            extremum,
+            length,
+            count: 0,
+            deque: VecDeque::new(),
+            evicted: Vec::new(),
+            expired: None,
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the number of inputs so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns whether `length` inputs were seen.
+    pub fn is_full(&self) -> bool {
+        self.count >= self.length
+    }
+
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.deque.clear();
+        self.evicted.clear();
+        self.expired = None;
+    }
+}
+
+impl<T> RollingExtremum<T>
+where
+    T: Copy + PartialOrd,
+{
+    /// Adds an input to the window and returns the extremum of the window.
+    pub fn push(&mut self, value: T) -> T {
+        self.evicted.clear();
+        while let Some(candidate) = self.deque.pop_back() {
+            if !self.extremum.dominates(&value, &candidate.1) {
+                self.deque.push_back(candidate);
+                break;
+            }
+            self.evicted.push(candidate);
+        }
+        self.deque.push_back((self.count, value));
+        self.count += 1;
+
+        // Only the front can leave the window, one input at a time
+        self.expired = match self.deque.front() {
+            Some(&(index, _)) if self.count.wrapping_sub(index) > self.length => {
+                self.deque.pop_front()
+            }
+            _ => None,
+        };
+        self.deque
+            .front()
+            .expect("the latest input stays in the window")
+            .1
+    }
+
+    /// Returns the index, counted from the first input, and the value of the extremum of the
+    /// window. `None` before the first input.
+    pub fn extreme(&self) -> Option<(usize, T)> {
+        self.deque.front().copied()
+    }
+
+    /// Takes the latest input back, restoring the window as it was before it.
+    ///
+    /// It must follow a `push`, there is no going back further.
+    pub fn pop(&mut self) {
+        if self.count == 0 {
+            return;
+        }
+        self.deque.pop_back();
+        while let Some(candidate) = self.evicted.pop() {
+            self.deque.push_back(candidate);
+        }
+        if let Some(candidate) = self.expired.take() {
+            self.deque.push_front(candidate);
+        }
+        self.count -= 1;
+    }
+
+    /// Replaces the latest input and returns the extremum of the window.
+    pub fn update_last(&mut self, value: T) -> T {
+        self.pop();
+        self.push(value)
+    }
+
+    /// Returns the extremum of the window if `value` was pushed, without pushing it.
+    pub fn peek(&self, value: T) -> T {
+        self.peek_extreme(value).1
+    }
+
+    /// Returns what `extreme` would return if `value` was pushed, without pushing it.
+    pub fn peek_extreme(&self, value: T) -> (usize, T) {
+        // The first candidate still in the window once the input is in, the front or the next one
+        let candidate = self
+            .deque
+            .iter()
+            .find(|&&(index, _)| self.count.wrapping_sub(index) < self.length);
+        match candidate {
+            Some(&candidate) if !self.extremum.dominates(&value, &candidate.1) => candidate,
+            _ => (self.count, value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    // Extremum of the last `length` inputs, by scanning them
+    fn scan(inputs: &[f64], length: usize, extremum: Extremum) -> f64 {
+        let start = inputs.len().saturating_sub(length);
+        inputs[start..]
+            .iter()
+            .copied()
+            .reduce(|acc, x| match extremum {
+                Extremum::Max => acc.max(x),
+                Extremum::Min => acc.min(x),
+            })
+            .unwrap()
+    }
+
+    const INPUTS: [f64; 16] = [
+        3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0, 5.0, 3.0, 5.0, 8.0, 9.0, 7.0, 9.0, 3.0,
+    ];
+
+    #[test]
+    fn test_push() {
+        for &extremum in &[Extremum::Max, Extremum::Min] {
+            for length in 1..5 {
+                let mut rolling = RollingExtremum::new(extremum, length);
+                let mut seen = Vec::new();
+
+                for &input in INPUTS.iter() {
+                    seen.push(input);
+                    assert_eq!(rolling.push(input), scan(&seen, length, extremum));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_peek_and_update_last() {
+        for &extremum in &[Extremum::Max, Extremum::Min] {
+            for length in 1..5 {
+                let mut rolling = RollingExtremum::new(extremum, length);
+                let mut seen = Vec::new();
+
+                for &input in INPUTS.iter() {
+                    seen.push(input);
+                    let expected = scan(&seen, length, extremum);
+                    assert_eq!(rolling.peek(input), expected);
+
+                    // push another value first, then replace it
+                    rolling.push(input + 4.0);
+                    rolling.update_last(input - 4.0);
+                    assert_eq!(rolling.update_last(input), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pop() {
+        let mut rolling = RollingExtremum::new(Extremum::Max, 2);
+        rolling.pop();
+        assert!(!rolling.is_full());
+
+        rolling.push(5.0);
+        rolling.push(3.0);
+        assert!(rolling.is_full());
+        // drops 3.0 and pushes 5.0 out of the window
+        assert_eq!(rolling.push(4.0), 4.0);
+
+        rolling.pop();
+        assert_eq!(rolling.peek(1.0), 3.0);
+        assert_eq!(rolling.push(2.0), 3.0);
+    }
+
+    #[test]
+    fn test_extreme() {
+        let mut rolling = RollingExtremum::new(Extremum::Min, 3);
+        assert_eq!(rolling.extreme(), None);
+        assert_eq!(rolling.peek_extreme(2.0), (0, 2.0));
+
+        rolling.push(2.0);
+        rolling.push(1.0);
+        assert_eq!(rolling.extreme(), Some((1, 1.0)));
+        assert_eq!(rolling.count(), 2);
+
+        // the latest one on ties
+        assert_eq!(rolling.peek_extreme(1.0), (2, 1.0));
+        rolling.push(1.0);
+        assert_eq!(rolling.extreme(), Some((2, 1.0)));
+
+        rolling.push(3.0);
+        rolling.push(4.0);
+        assert_eq!(rolling.extreme(), Some((2, 1.0)));
+        // 1.0 leaves the window
+        assert_eq!(rolling.peek_extreme(5.0), (3, 3.0));
+        rolling.push(5.0);
+        assert_eq!(rolling.extreme(), Some((3, 3.0)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rolling = RollingExtremum::new(Extremum::Min, 3);
+        rolling.push(1.0);
+        rolling.push(2.0);
+        rolling.push(3.0);
+        assert!(rolling.is_full());
+
+        rolling.reset();
+        assert!(!rolling.is_full());
+        assert_eq!(rolling.push(4.0), 4.0);
+        assert_eq!(rolling.length(), 3);
+    }
+}
@@ -1,12 +1,13 @@
-use super::{Close, High, Low, Open, Volume};
+use super::{Close, High, Low, MaybeMissing, Open, Timestamp, Volume};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Bar {
     open: f64,
     high: f64,
     low: f64,
     close: f64,
     volume: f64,
+    timestamp: Option<i64>,
 }
 
 impl Bar {
@@ -17,6 +18,7 @@ impl Bar {
             low: 0.0,
             high: 0.0,
             volume: 0.0,
+            timestamp: None,
         }
     }
 
@@ -44,6 +46,11 @@ impl Bar {
         self.volume = val;
         self
     }
+
+    pub fn timestamp(mut self, val: i64) -> Self {
+        self.timestamp = Some(val);
+        self
+    }
 }
 
 impl Open<f64> for Bar {
@@ -76,6 +83,22 @@ impl Volume<f64> for Bar {
     }
 }
 
+impl Timestamp for Bar {
+    fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+}
+
+impl MaybeMissing for Bar {
+    fn is_missing(&self) -> bool {
+        self.open.is_nan()
+            || self.high.is_nan()
+            || self.low.is_nan()
+            || self.close.is_nan()
+            || self.volume.is_nan()
+    }
+}
+
 pub fn round(num: f64) -> f64 {
     (num * 1000.0).round() / 1000.00
 }
@@ -84,6 +107,8 @@ macro_rules! test_indicator {
     ($i:tt) => {
         #[test]
         fn test_indicator() {
+            use crate::{Compute, Peek, UpdateLast, Warmup};
+
             let bar = Bar::new();
 
             // ensure Default trait is implemented
@@ -100,7 +125,185 @@ macro_rules! test_indicator {
             assert_eq!(indicator.next(12.3), first_output);
 
             // ensure Display is implemented
-            format!("{}", indicator);
+            assert!(!format!("{}", indicator).is_empty());
+
+            // ensure FromStr parses the Display output back
+            let spec = format!("{}", indicator);
+            let parsed: $i<f64> = spec.parse().unwrap();
+            assert_eq!(format!("{}", parsed), spec);
+
+            // ensure Compute gives the same results as Next, for values and bars
+            let values = [12.3, 15.0, 11.2, 11.2, 18.75, 9.5];
+            let mut streaming = $i::default();
+            let expected: Vec<_> = values.iter().map(|&v| streaming.next(v)).collect();
+            indicator.reset();
+            assert_eq!(indicator.compute(&values), expected);
+
+            let bars: Vec<Bar> = values
+                .iter()
+                .map(|&v| {
+                    Bar::new()
+                        .open(v)
+                        .high(v + 1.0)
+                        .low(v - 1.0)
+                        .close(v)
+                        .volume(v)
+                })
+                .collect();
+            let mut streaming = $i::default();
+            let expected: Vec<_> = bars.iter().map(|bar| streaming.next(bar)).collect();
+            indicator.reset();
+            let mut output = Vec::new();
+            indicator.compute_into(&bars, &mut output);
+            assert_eq!(output, expected);
+
+            // ensure UpdateLast amends the latest input as if it had been passed to next,
+            // for values and bars, before and after the warm-up period
+            let to_bar = |v: f64| {
+                Bar::new()
+                    .open(v)
+                    .high(v + 1.0)
+                    .low(v - 1.0)
+                    .close(v)
+                    .volume(v)
+            };
+            let values: Vec<f64> = (0..60)
+                .map(|i| 10.0 + ((i * 7) % 11) as f64 * 0.75)
+                .collect();
+            let mut streaming = $i::default();
+            indicator.reset();
+            for &v in &values {
+                indicator.next(v + 3.0);
+                indicator.update_last(v - 2.0);
+                assert_eq!(indicator.update_last(v), streaming.next(v));
+            }
+            let mut streaming = $i::default();
+            indicator.reset();
+            for &v in &values {
+                indicator.next(&to_bar(v - 2.0));
+                indicator.update_last(&to_bar(v + 3.0));
+                assert_eq!(
+                    indicator.update_last(&to_bar(v)),
+                    streaming.next(&to_bar(v))
+                );
+            }
+            indicator.reset();
+            assert_eq!(indicator.update_last(12.3), first_output);
+
+            // ensure Peek gives the output of next without changing the indicator
+            let mut streaming = $i::default();
+            indicator.reset();
+            for &v in &values {
+                indicator.peek(v + 3.0);
+                let expected = streaming.next(v);
+                assert_eq!(indicator.peek(v), expected);
+                assert_eq!(indicator.next(v), expected);
+            }
+            let mut streaming = $i::default();
+            indicator.reset();
+            for &v in &values {
+                indicator.peek(&to_bar(v + 3.0));
+                let expected = streaming.next(&to_bar(v));
+                assert_eq!(indicator.peek(&to_bar(v)), expected);
+                assert_eq!(indicator.next(&to_bar(v)), expected);
+            }
+
+            // ensure Warmup is implemented and is ready right after the warm-up period
+            indicator.reset();
+            assert!(!indicator.is_ready());
+            for _ in 1..indicator.warmup_period() {
+                indicator.next(12.3);
+                assert!(!indicator.is_ready());
+            }
+            indicator.next(12.3);
+            assert!(indicator.is_ready());
+        }
+
+        // ensure the indicator works with exact decimals, with the same guarantees as floats
+        #[cfg(feature = "rust_decimal")]
+        #[test]
+        fn test_indicator_decimal() {
+            use crate::{Compute, DataItem, Peek, UpdateLast, Warmup};
+            use rust_decimal::Decimal;
+
+            let to_bar = |v: Decimal| {
+                DataItem::builder()
+                    .open(v)
+                    .high(v + Decimal::ONE)
+                    .low(v - Decimal::ONE)
+                    .close(v)
+                    .volume(v)
+                    .build()
+                    .unwrap()
+            };
+            let values: Vec<Decimal> = (0..60)
+                .map(|i| Decimal::new(1000 + ((i * 7) % 11) * 75, 2))
+                .collect();
+            let bars: Vec<_> = values.iter().map(|&v| to_bar(v)).collect();
+
+            let mut indicator: $i<Decimal> = Default::default();
+            let first_output = indicator.next(values[0]);
+            indicator.next(&bars[1]);
+            indicator.reset();
+            assert_eq!(indicator.next(values[0]), first_output);
+
+            let spec = format!("{}", indicator);
+            let parsed: $i<Decimal> = spec.parse().unwrap();
+            assert_eq!(format!("{}", parsed), spec);
+
+            let mut streaming: $i<Decimal> = Default::default();
+            let expected: Vec<_> = values.iter().map(|&v| streaming.next(v)).collect();
+            indicator.reset();
+            assert_eq!(indicator.compute(&values), expected);
+
+            let mut streaming: $i<Decimal> = Default::default();
+            let expected: Vec<_> = bars.iter().map(|bar| streaming.next(bar)).collect();
+            indicator.reset();
+            assert_eq!(indicator.compute(&bars), expected);
+
+            let mut streaming: $i<Decimal> = Default::default();
+            indicator.reset();
+            for &v in &values {
+                indicator.next(v + Decimal::TWO);
+                indicator.update_last(v - Decimal::ONE);
+                assert_eq!(indicator.update_last(v), streaming.next(v));
+            }
+
+            let mut streaming: $i<Decimal> = Default::default();
+            indicator.reset();
+            for bar in &bars {
+                indicator.peek(bar);
+                let expected = streaming.next(bar);
+                assert_eq!(indicator.peek(bar), expected);
+                assert_eq!(indicator.next(bar), expected);
+            }
+
+            indicator.reset();
+            for _ in 1..indicator.warmup_period() {
+                indicator.next(values[0]);
+                assert!(!indicator.is_ready());
+            }
+            indicator.next(values[0]);
+            assert!(indicator.is_ready());
+        }
+
+        // ensure a restored snapshot continues with exactly the same outputs
+        #[cfg(feature = "serde")]
+        #[test]
+        fn test_serde() {
+            let values: Vec<f64> = (0..60)
+                .map(|i| 10.0 + ((i * 7) % 11) as f64 * 0.3)
+                .collect();
+            let mut indicator = $i::default();
+            for &v in &values[..30] {
+                indicator.next(v);
+            }
+
+            let json = serde_json::to_string(&indicator).unwrap();
+            let mut restored: $i<f64> = serde_json::from_str(&json).unwrap();
+            for &v in &values[30..] {
+                assert_eq!(restored.next(v), indicator.next(v));
+            }
         }
     };
 }
@@ -1,6 +1,9 @@
 // Indicator traits
 //
 
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
 /// Resets an indicator to the initial state.
 pub trait Reset {
     fn reset(&mut self);
@@ -12,14 +15,159 @@ pub trait Reset {
 /// traits necessary to calculate value of a particular indicator.
 ///
 /// In most cases `Output` is `f64`, but sometimes it can be different. For example for
-/// [MACD](indicators/struct.MovingAverageConvergenceDivergence.html) it is `(f64, f64, f64)` since
-/// MACD returns 3 values.
+/// [MACD](indicators/struct.MovingAverageConvergenceDivergence.html) it is
+/// [MacdOutput](indicators/struct.MacdOutput.html) since MACD returns 3 values.
+///
+/// The second parameter `U` only exists to tell apart the implementations for raw values and
+/// for bars, which would otherwise overlap (a raw `T` could itself be a reference):
+///
+/// * `Next<T, Raw>` - consumes a raw value, e.g. `f64`;
+/// * `Next<&U, T>` - consumes a reference to a bar `U` that provides prices of type `T`.
+///
+/// It never has to be written at call sites, since it is always inferred from the input.
 ///
 pub trait Next<T, U> {
     type Output;
     fn next(&mut self, input: T) -> Self::Output;
 }
 
+/// Marker used as the second parameter of [Next](trait.Next.html) by implementations
+/// that consume raw values.
+///
+/// It is an uninhabited type, it never has to be constructed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Raw {}
+
+/// Replaces the input of the latest period instead of starting a new one.
+///
+/// It is meant for live data, where the current bar keeps changing until it closes: `next`
+/// starts a new period, and `update_last` can then be called for every following tick of the same
+/// period. See also [Intrabar](struct.Intrabar.html).
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Next, UpdateLast};
+///
+/// let mut sma = SimpleMovingAverage::<f64>::new(2).unwrap();
+/// assert_eq!(sma.next(10.0), 10.0);
+/// assert_eq!(sma.next(11.0), 10.5);
+/// assert_eq!(sma.update_last(13.0), 11.5);
+/// assert_eq!(sma.update_last(12.0), 11.0);
+/// assert_eq!(sma.next(14.0), 13.0);
+/// ```
+pub trait UpdateLast<T, U>: Next<T, U> {
+    /// Returns the output the latest call to `next` would have returned for `input`, and keeps
+    /// the state accordingly.
+    ///
+    /// Behaves like `next` when no input was consumed since creation or the last reset.
+    fn update_last(&mut self, input: T) -> Self::Output;
+}
+
+/// Previews the output of `next` without changing the indicator.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RelativeStrengthIndex;
+/// use ta::{Next, Peek};
+///
+/// let mut rsi = RelativeStrengthIndex::<f64>::new(3).unwrap();
+/// rsi.next(10.0);
+/// rsi.next(10.5);
+///
+/// let what_if = rsi.peek(10.0);
+/// assert_eq!(what_if.round(), 35.0);
+/// assert_eq!(rsi.next(10.0), what_if);
+/// ```
+pub trait Peek<T, U>: Next<T, U> {
+    /// Returns what `next` would return for `input`.
+    fn peek(&self, input: T) -> Self::Output;
+}
+
+/// Feeds a whole slice of inputs to an indicator and collects the outputs.
+///
+/// It is implemented for every [Next](trait.Next.html) implementor, and gives exactly the same
+/// results as calling `next` for every item in order:
+///
+/// * raw values are copied out of the slice (`U` is [Raw](enum.Raw.html));
+/// * bars are passed by reference (`U` is [`Bars<T>`](struct.Bars.html)).
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Compute, DataItem};
+///
+/// let mut sma = SimpleMovingAverage::<f64>::new(2).unwrap();
+/// assert_eq!(sma.compute(&[1.0, 2.0, 4.0]), vec![1.0, 1.5, 3.0]);
+///
+/// let bar = DataItem::builder()
+///     .open(5.0)
+///     .high(7.0)
+///     .low(4.0)
+///     .close(6.0)
+///     .volume(1000.0)
+///     .build()
+///     .unwrap();
+/// assert_eq!(sma.compute(&[bar]), vec![5.0]);
+/// ```
+pub trait Compute<I, U> {
+    type Output;
+
+    /// Appends the output for every item of `inputs` to `output`, so the same buffer can be
+    /// reused between calls.
+    fn compute_into(&mut self, inputs: &[I], output: &mut Vec<Self::Output>);
+
+    fn compute(&mut self, inputs: &[I]) -> Vec<Self::Output> {
+        let mut output = Vec::with_capacity(inputs.len());
+        self.compute_into(inputs, &mut output);
+        output
+    }
+}
+
+/// Marker used as the second parameter of [Compute](trait.Compute.html) by implementations
+/// that consume slices of bars, which provide prices of type `T`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bars<T>(PhantomData<T>);
+
+impl<N, T> Compute<T, Raw> for N
+where
+    N: Next<T, Raw>,
+    T: Copy,
+{
+    type Output = N::Output;
+
+    fn compute_into(&mut self, inputs: &[T], output: &mut Vec<Self::Output>) {
+        output.extend(inputs.iter().map(|&input| self.next(input)));
+    }
+}
+
+impl<N, U, T, O> Compute<U, Bars<T>> for N
+where
+    N: for<'a> Next<&'a U, T, Output = O>,
+{
+    type Output = O;
+
+    fn compute_into(&mut self, inputs: &[U], output: &mut Vec<Self::Output>) {
+        output.extend(inputs.iter().map(|input| self.next(input)));
+    }
+}
+
+/// Reports how many inputs an indicator needs before its output is meaningful.
+///
+/// Indicators return a value from the very first input, but until the warm-up period is over it
+/// is computed from an incomplete window. Use [WhenReady](struct.WhenReady.html) to get `None`
+/// instead of those values.
+pub trait Warmup {
+    /// Number of inputs needed before the output is meaningful.
+    fn warmup_period(&self) -> usize;
+
+    /// Returns `true` once `warmup_period()` inputs were consumed since creation or the last reset.
+    fn is_ready(&self) -> bool;
+}
+
 /// Open price of a particular period.
 pub trait Open<T> {
     fn open(&self) -> T;
@@ -44,3 +192,80 @@ pub trait High<T> {
 pub trait Volume<T> {
     fn volume(&self) -> T;
 }
+
+/// Start time of a particular period, in milliseconds since the Unix epoch (UTC).
+///
+/// It is optional, since bars do not always carry a time. Time-aware indicators fall back
+/// to their time-independent behavior when it is missing.
+pub trait Timestamp {
+    fn timestamp(&self) -> Option<i64>;
+}
+
+/// Square root of a number, used by volatility indicators such as
+/// [StandardDeviation](indicators/struct.StandardDeviation.html).
+///
+/// It is implemented for `f32` and `f64` with their native square root, which needs either the
+/// `std` or the `libm` feature, and for `rust_decimal::Decimal` with the `rust_decimal` feature.
+/// Other number types, e.g. exact decimals, can provide their own implementation.
+///
+/// # Example
+///
+/// ```
+/// use ta::Sqrt;
+///
+/// assert_eq!(Sqrt::sqrt(2.25_f64), 1.5);
+/// ```
+pub trait Sqrt {
+    fn sqrt(self) -> Self;
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl Sqrt for f32 {
+    fn sqrt(self) -> Self {
+        num_traits::Float::sqrt(self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl Sqrt for f64 {
+    fn sqrt(self) -> Self {
+        num_traits::Float::sqrt(self)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl Sqrt for rust_decimal::Decimal {
+    fn sqrt(self) -> Self {
+        // Only negative numbers have no square root
+        rust_decimal::MathematicalOps::sqrt(&self).unwrap_or(rust_decimal::Decimal::ZERO)
+    }
+}
+
+/// Tells whether an input carries a missing value, e.g. a `NaN` price sent by a feed.
+///
+/// It is used by [MissingValues](struct.MissingValues.html) and
+/// [RejectMissing](struct.RejectMissing.html) to keep missing values away from the state of an
+/// indicator. Floats are missing when they are `NaN`, and bars when any of their
+/// prices or volume is.
+pub trait MaybeMissing {
+    fn is_missing(&self) -> bool;
+}
+
+impl MaybeMissing for f32 {
+    fn is_missing(&self) -> bool {
+        self.is_nan()
+    }
+}
+
+impl MaybeMissing for f64 {
+    fn is_missing(&self) -> bool {
+        self.is_nan()
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl MaybeMissing for rust_decimal::Decimal {
+    fn is_missing(&self) -> bool {
+        false
+    }
+}
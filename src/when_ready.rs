@@ -0,0 +1,196 @@
+use core::fmt;
+
+use crate::{Next, Peek, Reset, UpdateLast, Warmup};
+
+/// Wraps an indicator to return `None` until its warm-up period is over.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Next, WhenReady};
+///
+/// let mut sma = WhenReady::new(SimpleMovingAverage::<f64>::new(3).unwrap());
+/// assert_eq!(sma.next(10.0), None);
+/// assert_eq!(sma.next(11.0), None);
+/// assert_eq!(sma.next(12.0), Some(11.0));
+/// assert_eq!(sma.next(13.0), Some(12.0));
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WhenReady<N> {
+    indicator: N,
+}
+
+impl<N> WhenReady<N> {
+    pub fn new(indicator: N) -> Self {
+        Self { indicator }
+    }
+
+    /// Returns the wrapped indicator.
+    pub fn indicator(&self) -> &N {
+        &self.indicator
+    }
+
+    pub fn into_inner(self) -> N {
+        self.indicator
+    }
+}
+
+impl<N, T, U> Next<T, U> for WhenReady<N>
+where
+    N: Next<T, U> + Warmup,
+{
+    type Output = Option<N::Output>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let output = self.indicator.next(input);
+        if self.indicator.is_ready() {
+            Some(output)
+        } else {
+            None
+        }
+    }
+}
+
+impl<N, T, U> UpdateLast<T, U> for WhenReady<N>
+where
+    N: UpdateLast<T, U> + Warmup,
+{
+    fn update_last(&mut self, input: T) -> Self::Output {
+        let output = self.indicator.update_last(input);
+        if self.indicator.is_ready() {
+            Some(output)
+        } else {
+            None
+        }
+    }
+}
+
+/// During the warm-up, whether the input would end it is only known by feeding it to a copy of
+/// the indicator, so `peek` clones the indicator until it is ready.
+impl<N, T, U> Peek<T, U> for WhenReady<N>
+where
+    N: Peek<T, U> + Warmup + Clone,
+    T: Copy,
+{
+    fn peek(&self, input: T) -> Self::Output {
+        let output = self.indicator.peek(input);
+        let ready = self.indicator.is_ready() || {
+            let mut indicator = self.indicator.clone();
+            indicator.next(input);
+            indicator.is_ready()
+        };
+        if ready {
+            Some(output)
+        } else {
+            None
+        }
+    }
+}
+
+impl<N: Reset> Reset for WhenReady<N> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+impl<N: Warmup> Warmup for WhenReady<N> {
+    fn warmup_period(&self) -> usize {
+        self.indicator.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.indicator.is_ready()
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for WhenReady<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.indicator.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        BollingerBands, MoneyFlowIndex, MovingAverageConvergenceDivergence, SimpleMovingAverage,
+        SlowStochastic,
+    };
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut macd = WhenReady::new(MovingAverageConvergenceDivergence::new(3, 6, 4).unwrap());
+        assert_eq!(macd.warmup_period(), 9);
+
+        for i in 0..8 {
+            assert_eq!(macd.next(i as f64), None);
+        }
+        assert!(macd.next(8.0).is_some());
+        assert!(macd.next(9.0).is_some());
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut mfi = WhenReady::new(MoneyFlowIndex::new(2).unwrap());
+        let bar = Bar::new().high(3).low(1).close(2).volume(500.0);
+
+        assert_eq!(mfi.next(&bar), None);
+        assert_eq!(mfi.next(&bar), None);
+        assert_eq!(mfi.next(&bar), Some(100.0));
+    }
+
+    #[test]
+    fn test_update_last() {
+        let mut sma = WhenReady::new(SimpleMovingAverage::<f64>::new(2).unwrap());
+        assert_eq!(sma.update_last(4.0), None);
+        assert_eq!(sma.update_last(6.0), None);
+        assert_eq!(sma.next(8.0), Some(7.0));
+        assert_eq!(sma.update_last(10.0), Some(8.0));
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut sma = WhenReady::new(SimpleMovingAverage::<f64>::new(3).unwrap());
+        assert_eq!(sma.peek(4.0), None);
+        sma.next(4.0);
+        assert_eq!(sma.peek(6.0), None);
+        sma.next(6.0);
+        assert_eq!(sma.peek(8.0), Some(6.0));
+        assert_eq!(sma.peek(8.0), sma.next(8.0));
+        assert_eq!(sma.peek(12.0), sma.next(12.0));
+
+        // readiness follows the indicator through update_last and reset
+        let mut sma = WhenReady::new(SimpleMovingAverage::<f64>::new(2).unwrap());
+        sma.update_last(4.0);
+        sma.update_last(6.0);
+        assert_eq!(sma.peek(8.0), sma.clone().next(8.0));
+        sma.next(8.0);
+        sma.reset();
+        assert_eq!(sma.peek(8.0), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bb = WhenReady::new(BollingerBands::new(2, 2.0_f64).unwrap());
+        assert_eq!(bb.next(1.0), None);
+        assert!(bb.next(2.0).is_some());
+
+        bb.reset();
+        assert!(!bb.is_ready());
+        assert_eq!(bb.next(1.0), None);
+    }
+
+    #[test]
+    fn test_default() {
+        WhenReady::<SlowStochastic<f64>>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let stoch = WhenReady::new(SlowStochastic::<f64>::new(10, 2).unwrap());
+        assert_eq!(format!("{}", stoch), "SLOW_STOCH(10, 2)");
+    }
+}